@@ -0,0 +1,237 @@
+use super::usecases::{AccessTokenCreateCommand, AccessTokenDto, IssuedAccessTokenDto, PersonalAccessTokenUseCase};
+use crate::application::oauth2::generate_rand;
+use crate::common::database::DbConnProvider;
+use crate::common::error::error_code::ErrorCode;
+use crate::common::token_hasher::hash_token;
+use crate::common::{AppError, LoginMember, Permission, SecurityRole};
+use crate::config::ConfigProvider;
+use crate::domain::member::access_token::entity::AccessTokenEntity;
+use crate::domain::member::access_token::repository::{LoadAccessTokenPort, SaveAccessTokenPort};
+use crate::domain::member::repository::LoadMemberPort;
+use shaku::Component;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// 개인 액세스 토큰의 접두사. Authorization 헤더에서 JWT 와 구분하고, 유출된 토큰을 검색할 때도 쓴다.
+pub const PERSONAL_ACCESS_TOKEN_PREFIX: &str = "bpat_";
+const TOKEN_RANDOM_LENGTH: usize = 40;
+/// 목록에서 토큰을 알아볼 수 있도록 저장하는 무작위 부분의 길이
+const TOKEN_DISPLAY_LENGTH: usize = 8;
+const TOKEN_NAME_MAX_LENGTH: usize = 100;
+/// 마지막 사용 시각은 이 간격(초)보다 자주 갱신하지 않는다. (요청마다 쓰기가 일어나지 않도록)
+const LAST_USED_TOUCH_INTERVAL_SECONDS: i64 = 60;
+
+#[derive(Component)]
+#[shaku(interface = PersonalAccessTokenUseCase)]
+pub struct PersonalAccessTokenUseCaseImpl {
+    #[shaku(inject)]
+    db: Arc<dyn DbConnProvider>,
+    #[shaku(inject)]
+    config: Arc<dyn ConfigProvider>,
+    #[shaku(inject)]
+    load_member_port: Arc<dyn LoadMemberPort>,
+    #[shaku(inject)]
+    load_access_token_port: Arc<dyn LoadAccessTokenPort>,
+    #[shaku(inject)]
+    save_access_token_port: Arc<dyn SaveAccessTokenPort>,
+}
+
+fn to_access_token_dto(access_token: AccessTokenEntity) -> AccessTokenDto {
+    AccessTokenDto {
+        id: access_token.get_id(),
+        name: access_token.get_name().clone(),
+        token_prefix: access_token.get_token_prefix().clone(),
+        scopes: access_token.get_scopes().clone(),
+        expires_at: access_token.get_expires_at(),
+        last_used_at: access_token.get_last_used_at(),
+        created_at: access_token.get_created_at(),
+    }
+}
+
+fn check_member_access(login_member: &LoginMember, member_id: Uuid) -> Result<(), AppError> {
+    if login_member.id != member_id && !login_member.has_permission(Permission::MemberAdmin) {
+        return Err(AppError::from(ErrorCode::Forbidden));
+    }
+    Ok(())
+}
+
+/// 요청한 권한 이름을 확인한다. 발급하는 회원이 지금 쓸 수 있는 권한만 토큰에 줄 수 있다.
+fn validate_scopes(login_member: &LoginMember, scopes: &[String]) -> Result<Vec<String>, AppError> {
+    if scopes.is_empty() {
+        return Err(AppError::with_message(ErrorCode::BadRequest, "토큰에 줄 권한을 하나 이상 지정해주세요."));
+    }
+
+    let mut permissions: Vec<Permission> = Vec::new();
+    for scope in scopes {
+        let permission = Permission::from_str(scope.trim())
+            .ok_or_else(|| AppError::with_message(ErrorCode::BadRequest, "알 수 없는 권한입니다."))?;
+        if !login_member.has_permission(permission) {
+            return Err(AppError::with_message(ErrorCode::Forbidden, "가지고 있지 않은 권한은 토큰에 줄 수 없습니다."));
+        }
+        if !permissions.contains(&permission) {
+            permissions.push(permission);
+        }
+    }
+
+    Ok(permissions.iter().map(|permission| permission.as_str().to_owned()).collect())
+}
+
+#[async_trait::async_trait]
+impl PersonalAccessTokenUseCase for PersonalAccessTokenUseCaseImpl {
+    async fn create(&self, login_member: LoginMember, member_id: Uuid, command: AccessTokenCreateCommand) -> Result<IssuedAccessTokenDto, AppError> {
+        // 다른 회원 대신 토큰을 만들 수는 없다.
+        if login_member.id != member_id {
+            return Err(AppError::from(ErrorCode::Forbidden));
+        }
+
+        let name = command.name.trim().to_owned();
+        if name.is_empty() || name.chars().count() > TOKEN_NAME_MAX_LENGTH {
+            return Err(AppError::with_message(ErrorCode::BadRequest, "토큰 이름은 1자 이상 100자 이하로 입력해주세요."));
+        }
+        let scopes = validate_scopes(&login_member, &command.scopes)?;
+
+        let config = self.config.get();
+        let expires_in_days = command.expires_in_days.unwrap_or(config.access_token_default_days);
+        if expires_in_days <= 0 || expires_in_days > config.access_token_max_days {
+            return Err(AppError::with_message(ErrorCode::BadRequest, "토큰 유효 기간이 허용 범위를 벗어났습니다."));
+        }
+        let now = chrono::Utc::now().naive_utc();
+
+        let token = format!("{}{}", PERSONAL_ACCESS_TOKEN_PREFIX, generate_rand(TOKEN_RANDOM_LENGTH));
+        let access_token = AccessTokenEntity::new(
+            Uuid::new_v4(),
+            member_id,
+            name,
+            token[..PERSONAL_ACCESS_TOKEN_PREFIX.len() + TOKEN_DISPLAY_LENGTH].to_owned(),
+            hash_token(&token),
+            scopes,
+            login_member.mfa_authenticated,
+            now + chrono::Duration::days(expires_in_days),
+            None,
+            Some(now),
+            None,
+        );
+
+        let txn = self.db.rw_txn().await?;
+        let access_token = self.save_access_token_port.save(&txn, access_token).await?;
+        txn.commit().await?;
+
+        Ok(IssuedAccessTokenDto {
+            token,
+            access_token: to_access_token_dto(access_token),
+        })
+    }
+
+    async fn get_tokens(&self, login_member: LoginMember, member_id: Uuid) -> Result<Vec<AccessTokenDto>, AppError> {
+        check_member_access(&login_member, member_id)?;
+
+        let txn = self.db.ro_txn().await?;
+        let access_tokens = self
+            .load_access_token_port
+            .find_by_member_id(&txn, member_id)
+            .await?;
+        txn.commit().await?;
+
+        Ok(access_tokens.into_iter().map(to_access_token_dto).collect())
+    }
+
+    async fn revoke(&self, login_member: LoginMember, member_id: Uuid, token_id: Uuid) -> Result<(), AppError> {
+        check_member_access(&login_member, member_id)?;
+
+        let txn = self.db.rw_txn().await?;
+        let access_token = self
+            .load_access_token_port
+            .find_by_id(&txn, token_id)
+            .await?
+            .filter(|access_token| access_token.get_member_id() == member_id && access_token.get_revoked_at().is_none())
+            .ok_or_else(|| AppError::with_message(ErrorCode::NotFound, "토큰을 찾을 수 없습니다."))?;
+
+        self.save_access_token_port.revoke(&txn, access_token.get_id()).await?;
+        txn.commit().await?;
+
+        Ok(())
+    }
+
+    async fn authenticate(&self, token: &str) -> Result<LoginMember, ErrorCode> {
+        let now = chrono::Utc::now().naive_utc();
+
+        let txn = self.db.rw_txn().await?;
+        let access_token = self
+            .load_access_token_port
+            .find_by_token_hash(&txn, &hash_token(token))
+            .await?
+            .filter(|access_token| access_token.is_usable(now))
+            .ok_or(ErrorCode::InvalidAccessToken)?;
+        let member = self
+            .load_member_port
+            .find_by_id(&txn, access_token.get_member_id())
+            .await?
+            .ok_or(ErrorCode::InvalidAccessToken)?;
+
+        let touch_needed = access_token.get_last_used_at().is_none_or(|last_used_at| {
+            now - last_used_at >= chrono::Duration::seconds(LAST_USED_TOUCH_INTERVAL_SECONDS)
+        });
+        if touch_needed {
+            self.save_access_token_port
+                .touch_last_used(&txn, access_token.get_id(), now)
+                .await?;
+        }
+        txn.commit().await?;
+
+        Ok(LoginMember {
+            id: access_token.get_member_id(),
+            nickname: member.nickname,
+            email: member.email,
+            roles: SecurityRole::from_claim(&format!("ROLE_{}", member.role))
+                .into_iter()
+                .collect(),
+            is_activated: member.is_activated,
+            session_id: None,
+            mfa_authenticated: access_token.is_mfa_authenticated(),
+            token_scopes: Some(
+                access_token
+                    .get_scopes()
+                    .iter()
+                    .filter_map(|scope| Permission::from_str(scope))
+                    .collect(),
+            ),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::validate_scopes;
+    use crate::common::{LoginMember, Permission, SecurityRole};
+    use uuid::Uuid;
+
+    fn login_member(roles: Vec<SecurityRole>, token_scopes: Option<Vec<Permission>>) -> LoginMember {
+        LoginMember {
+            id: Uuid::new_v4(),
+            nickname: "admin".to_owned(),
+            email: "admin@dohoon-kim.kr".to_owned(),
+            roles,
+            is_activated: true,
+            session_id: None,
+            mfa_authenticated: false,
+            token_scopes,
+        }
+    }
+
+    #[test]
+    fn validate_scopes_test() {
+        let admin = login_member(vec![SecurityRole::Admin], None);
+        let scopes = validate_scopes(&admin, &["post:write".to_owned(), "post:write".to_owned()]).ok().unwrap();
+        assert_eq!(scopes, vec!["post:write".to_owned()]);
+        assert!(validate_scopes(&admin, &[]).is_err());
+        assert!(validate_scopes(&admin, &["post:delete".to_owned()]).is_err());
+
+        let member = login_member(vec![SecurityRole::Member], None);
+        assert!(validate_scopes(&member, &["post:write".to_owned()]).is_err());
+
+        // 토큰으로 인증하면 토큰에 허용된 권한만 쓸 수 있다.
+        let token_member = login_member(vec![SecurityRole::Admin], Some(vec![Permission::PostWrite]));
+        assert!(token_member.has_permission(Permission::PostWrite));
+        assert!(!token_member.has_permission(Permission::BoardManage));
+    }
+}
@@ -1,20 +1,31 @@
+use super::login_guard::{LoginAttemptGuard, MAX_ACCOUNT_KEY_LENGTH};
+use super::mfa_service::{verify_second_factor, MFA_PENDING_PURPOSE, MFA_PENDING_TOKEN_MINUTES};
 use super::usecases::{
-    JwtReissueResult, JwtUseCase, LoginCommand, LoginCommandResult, LoginUseCase, OAuth2LoginCommand,
+    JwtReissueResult, JwtUseCase, LoginCommand, LoginCommandResult, LoginOutcome, LoginUseCase,
+    LogoutUseCase, MfaChallenge, MfaLoginCommand, OAuth2LoginCommand,
 };
 //use crate::application::member::{MemberCreateCommand, MemberCreateUseCase};
 use crate::application::oauth2::generate_rand;
 use crate::common::database::DbConnProvider;
 use crate::common::error::error_code::ErrorCode;
 use crate::common::jwt::JwtService;
-use crate::common::AppError;
+use crate::common::token_hasher::hash_token;
+use crate::common::{AppError, ClientInfo, LoginMember};
+use crate::config::ConfigProvider;
 use crate::domain::member::entity::MemberEntity;
+use crate::domain::member::mfa::repository::{LoadMfaPort, SaveMfaPort};
 use crate::domain::member::oauth2_member::entity::OAuth2MemberEntity;
 use crate::domain::member::oauth2_member::repository::{LoadOAuth2MemberPort, SaveOAuth2MemberPort};
+use crate::domain::member::refresh_token::entity::RefreshTokenEntity;
+use crate::domain::member::refresh_token::repository::{LoadRefreshTokenPort, SaveRefreshTokenPort};
 use crate::domain::member::repository::{LoadMemberPort, SaveMemberPort};
+use crate::domain::member::session::entity::SessionEntity;
+use crate::domain::member::session::repository::{LoadSessionPort, SaveSessionPort};
+use chrono::NaiveDateTime;
 use sea_orm::DatabaseTransaction;
 use shaku::Component;
 use std::process::exit;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 use uuid::Uuid;
 
 #[derive(Component)]
@@ -32,10 +43,132 @@ pub struct AuthService {
     load_oauth2_member_port: Arc<dyn LoadOAuth2MemberPort>,
     #[shaku(inject)]
     save_oauth2_member_port: Arc<dyn SaveOAuth2MemberPort>,
+    #[shaku(inject)]
+    save_refresh_token_port: Arc<dyn SaveRefreshTokenPort>,
+    #[shaku(inject)]
+    save_session_port: Arc<dyn SaveSessionPort>,
+    #[shaku(inject)]
+    load_mfa_port: Arc<dyn LoadMfaPort>,
+    #[shaku(inject)]
+    save_mfa_port: Arc<dyn SaveMfaPort>,
+    #[shaku(inject)]
+    login_attempt_guard: Arc<dyn LoginAttemptGuard>,
+    #[shaku(inject)]
+    config: Arc<dyn ConfigProvider>,
+}
+
+/// 가입되지 않은 이메일로 로그인할 때 비교할 해시. 응답 시간으로 가입 여부를 알 수 없도록 항상 bcrypt 검증을 거친다.
+fn dummy_password_hash() -> &'static str {
+    static DUMMY_PASSWORD_HASH: OnceLock<String> = OnceLock::new();
+    DUMMY_PASSWORD_HASH.get_or_init(|| bcrypt::hash(generate_rand(32), 10).unwrap())
+}
+
+/// 지금 발급하는 refresh token 의 만료 시각. 세션의 만료 시각도 같은 값으로 맞춘다.
+fn refresh_token_expires_at(config: &dyn ConfigProvider, now: NaiveDateTime) -> NaiveDateTime {
+    now + chrono::Duration::milliseconds(config.get().jwt_refresh_token_expire as i64)
+}
+
+/// refresh token 을 발급하고 해시값을 서버에 저장한다. 반환값은 (토큰 원문, jti) 이다.
+async fn issue_refresh_token(
+    txn: &DatabaseTransaction,
+    jwt_service: &dyn JwtService,
+    save_refresh_token_port: &dyn SaveRefreshTokenPort,
+    member: &MemberEntity,
+    family_id: Uuid,
+    expires_at: NaiveDateTime,
+) -> Result<(String, Uuid), ErrorCode> {
+    let jti = Uuid::new_v4();
+    let refresh_token = jwt_service
+        .create_refresh_token(member, jti)
+        .map_err(|_| ErrorCode::Unauthorized)?;
+
+    let now = chrono::Utc::now().naive_utc();
+    let entity = RefreshTokenEntity::new(
+        jti,
+        member.id.ok_or(ErrorCode::MemberNotFound)?,
+        family_id,
+        hash_token(&refresh_token),
+        expires_at,
+        Some(now),
+        None,
+        None,
+    );
+
+    save_refresh_token_port.save(txn, entity).await?;
+
+    Ok((refresh_token, jti))
 }
 
 impl AuthService {
 
+    /// 새 로그인 세션을 만들고 해당 세션에 묶인 access/refresh token 을 발급한다.
+    async fn issue_tokens(
+        &self,
+        txn: &DatabaseTransaction,
+        member: &MemberEntity,
+        client: ClientInfo,
+        mfa_authenticated: bool,
+    ) -> Result<LoginCommandResult, ErrorCode> {
+        let expires_at = refresh_token_expires_at(self.config.as_ref(), chrono::Utc::now().naive_utc());
+        let session = SessionEntity::new(
+            Uuid::new_v4(),
+            member.id.ok_or(ErrorCode::MemberNotFound)?,
+            client.user_agent,
+            client.ip_address,
+            None,
+            None,
+            expires_at,
+            None,
+            mfa_authenticated,
+        );
+        let session = self.save_session_port.save(txn, session).await?;
+
+        let (refresh_token, _) = issue_refresh_token(
+            txn,
+            self.jwt_service.as_ref(),
+            self.save_refresh_token_port.as_ref(),
+            member,
+            session.get_id(),
+            expires_at,
+        ).await?;
+
+        Ok(LoginCommandResult {
+            access_token: self
+                .jwt_service
+                .create_access_token(member, session.get_id(), mfa_authenticated)
+                .map_err(|_| ErrorCode::Unauthorized)?,
+            refresh_token,
+        })
+    }
+
+    /// 2단계 인증이 설정된 회원이면 토큰 대신 mfa_token 을 발급한다. 그렇지 않으면 바로 토큰을 발급한다.
+    async fn complete_first_factor(
+        &self,
+        txn: &DatabaseTransaction,
+        member: &MemberEntity,
+        client: ClientInfo,
+    ) -> Result<LoginOutcome, AppError> {
+        let member_id = member.id.ok_or(ErrorCode::MemberNotFound)?;
+        let mfa_enabled = self
+            .load_mfa_port
+            .find_by_member_id(txn, member_id)
+            .await?
+            .is_some_and(|mfa| mfa.is_enabled());
+
+        if !mfa_enabled {
+            return Ok(LoginOutcome::Authenticated(self.issue_tokens(txn, member, client, false).await?));
+        }
+
+        let expires_at = chrono::Utc::now().naive_utc() + chrono::Duration::minutes(MFA_PENDING_TOKEN_MINUTES);
+        let mfa_token = self
+            .jwt_service
+            .create_verification_token(member_id, Uuid::new_v4(), MFA_PENDING_PURPOSE, expires_at)?;
+        Ok(LoginOutcome::MfaRequired(MfaChallenge {
+            mfa_token,
+            expires_in: MFA_PENDING_TOKEN_MINUTES * 60,
+        }))
+    }
+
     fn create_new_member(&self, email: String, nickname: String) -> MemberEntity {
         let entity = MemberEntity {
             id: None,
@@ -46,6 +179,7 @@ impl AuthService {
             created_at: chrono::Utc::now().naive_utc(),
             updated_at: None,
             is_activated: true,
+            has_password: false,
         };
 
         tracing::debug!("create_new_member: {:?}", entity);
@@ -53,47 +187,31 @@ impl AuthService {
         entity
     }
 
-    async fn do_join_or_union(&self, 
-        txn: &DatabaseTransaction, 
+    /// 처음 로그인한 OAuth2 계정으로 새 회원을 만든다.
+    /// 같은 이메일로 가입한 회원이 있어도 자동으로 합치지 않는다. 제공자가 알려준 이메일만으로는
+    /// 계정 소유를 확인할 수 없으므로, 기존 회원은 로그인한 뒤 계정 연결로 직접 연결해야 한다.
+    async fn do_join(&self,
+        txn: &DatabaseTransaction,
         command: OAuth2LoginCommand
     ) -> Result<MemberEntity, AppError> {
 
-        let virtual_email_addr = format!("{}-{}@dohoon-kim.kr", command.provider.clone(), command.user_id.clone());
-        
-        let member = match command.email.clone() {
-            Some(email) => {
-                let exist_member = self.load_member_port
-                    .find_by_email(txn, &email)
-                    .await?;
-
-                let exist_member = match exist_member {
-                    Some(member) => {
-                        member
-                    },
-                    None => {
-                        let new_member = self.create_new_member(
-                            format!("{}-{}@dohoon-kim.kr", command.provider, command.user_id).to_string(), 
-                            format!("{}:{}", command.provider, command.user_id).to_string()
-                        );
-
-                        self.save_member_port
-                            .save(txn, new_member)
-                            .await?
-                    }
-                };
-                tracing::debug!("exist_member: {:?}", exist_member);
-                exist_member
-            },
-            None => {
-                let new_member = self.create_new_member(
-                    format!("{}-{}@dohoon-kim.kr", command.provider, command.user_id).to_string(), 
-                    format!("{}:{}", command.provider, command.user_id).to_string()
-                );
-                self.save_member_port
-                    .save(txn, new_member)
-                    .await?
+        if let Some(email) = command.email.as_ref() {
+            if self.load_member_port.find_by_email(txn, email).await?.is_some() {
+                return Err(AppError::with_message(
+                    ErrorCode::Conflict,
+                    "이미 같은 이메일로 가입된 회원이 있습니다. 로그인한 뒤 계정 연결을 이용해주세요.",
+                ));
             }
-        };
+        }
+
+        let virtual_email_addr = format!("{}-{}@dohoon-kim.kr", command.provider, command.user_id);
+        let new_member = self.create_new_member(
+            virtual_email_addr,
+            format!("{}:{}", command.provider, command.user_id),
+        );
+        let member = self.save_member_port
+            .save(txn, new_member)
+            .await?;
 
         let oauth2_member = OAuth2MemberEntity::new(
             None,
@@ -119,69 +237,124 @@ impl LoginUseCase for AuthService {
     async fn login_by_oauth2(
         &self,
         command: OAuth2LoginCommand,
-    ) -> Result<LoginCommandResult, AppError> {
+    ) -> Result<LoginOutcome, AppError> {
         let txn = self.db.rw_txn().await.map_err(|_| ErrorCode::InternalServerError)?;
 
         let oauth2_member = self
             .load_oauth2_member_port
             .find_by_provider_and_user_id(&txn, command.provider.clone(), command.user_id.clone())
-            .await;
+            .await?;
 
         let member = match oauth2_member {
             Some(oauth2_member) => {
                 self.load_member_port.find_by_id(&txn, oauth2_member.get_member_id()).await?
             },
             None => {
-                Some(self.do_join_or_union(&txn, command.clone()).await?)
+                Some(self.do_join(&txn, command.clone()).await?)
             }
         }.unwrap();
 
+        let result = self.complete_first_factor(&txn, &member, command.client).await?;
         txn.commit().await?;
 
-        Ok(LoginCommandResult {
-            access_token: self
-                .jwt_service
-                .create_access_token(&member)
-                .map_err(|_| ErrorCode::Unauthorized)?,
-            refresh_token: self
-                .jwt_service
-                .create_refresh_token(&member)
-                .map_err(|_| ErrorCode::Unauthorized)?,
-        })
+        Ok(result)
     }
 
-    async fn login(&self, command: LoginCommand) -> Result<LoginCommandResult, ErrorCode> {
-        let txn = self.db.ro_txn().await.map_err(|_| ErrorCode::InternalServerError)?;
+    async fn login(&self, command: LoginCommand) -> Result<LoginOutcome, ErrorCode> {
+        // 이보다 긴 이메일은 가입될 수 없으므로 실패 기록 없이 같은 에러로 응답한다.
+        if command.principal.trim().chars().count() > MAX_ACCOUNT_KEY_LENGTH {
+            return Err(ErrorCode::EmailPasswordMismatch);
+        }
+        let txn = self.db.rw_txn().await.map_err(|_| ErrorCode::InternalServerError)?;
+        let ip_address = command.client.ip_address.clone();
+        self.login_attempt_guard
+            .check(&txn, &command.principal, ip_address.as_deref())
+            .await?;
+
         let member = self
             .load_member_port
             .find_by_email(&txn, &command.principal)
-            .await;
+            .await
+            .map_err(|_| ErrorCode::InternalServerError)?;
 
-        txn.commit().await?;
+        let valid_password = match &member {
+            Some(member) => bcrypt::verify(command.credential.as_bytes(), &member.password).unwrap_or(false),
+            None => {
+                let _ = bcrypt::verify(command.credential.as_bytes(), dummy_password_hash());
+                false
+            }
+        };
 
+        // 가입되지 않은 이메일과 틀린 비밀번호는 같은 에러로 응답한다.
         let member = match member {
-            Ok(Some(member)) => member,
-            Ok(None) => return Err(ErrorCode::MemberNotFound),
-            Err(_) => return Err(ErrorCode::InternalServerError),
+            Some(member) if valid_password => member,
+            member => {
+                self.login_attempt_guard
+                    .record_failure(&txn, &command.principal, member.and_then(|member| member.id), ip_address.as_deref())
+                    .await?;
+                txn.commit().await?;
+                return Err(ErrorCode::EmailPasswordMismatch);
+            }
         };
 
-        let valid_password = bcrypt::verify(&command.credential.as_bytes(), &member.password)
-            .map_err(|_| ErrorCode::EmailPasswordMismatch)?;
+        let result = self
+            .complete_first_factor(&txn, &member, command.client)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to complete login: {}", e.message);
+                ErrorCode::InternalServerError
+            })?;
+        // 2단계 인증이 남아 있으면 실패 기록을 지우지 않는다. (비밀번호를 아는 상태로 인증 코드를 무작위 대입하지 못하도록)
+        if let LoginOutcome::Authenticated(_) = result {
+            self.login_attempt_guard.record_success(&txn, &command.principal).await?;
+        }
+        txn.commit().await?;
 
-        if !valid_password {
-            return Err(ErrorCode::EmailPasswordMismatch);
+        Ok(result)
+    }
+
+    async fn login_with_mfa(&self, command: MfaLoginCommand) -> Result<LoginCommandResult, AppError> {
+        let expired = || AppError::with_message(ErrorCode::Unauthorized, "2단계 인증 시간이 만료되었습니다. 다시 로그인해주세요.");
+        let claims = self
+            .jwt_service
+            .decode_verification_token(&command.mfa_token)
+            .map_err(|_| expired())?;
+        if claims.purpose != MFA_PENDING_PURPOSE {
+            return Err(expired());
         }
+        let member_id = Uuid::parse_str(&claims.sub).map_err(|_| expired())?;
 
-        Ok(LoginCommandResult {
-            access_token: self
-                .jwt_service
-                .create_access_token(&member)
-                .map_err(|_| ErrorCode::Unauthorized)?,
-            refresh_token: self
-                .jwt_service
-                .create_refresh_token(&member)
-                .map_err(|_| ErrorCode::Unauthorized)?,
-        })
+        let txn = self.db.rw_txn().await?;
+        let member = self
+            .load_member_port
+            .find_by_id(&txn, member_id)
+            .await?
+            .ok_or_else(|| AppError::from(ErrorCode::MemberNotFound))?;
+        let mut mfa = self
+            .load_mfa_port
+            .find_by_member_id(&txn, member_id)
+            .await?
+            .filter(|mfa| mfa.is_enabled())
+            .ok_or_else(expired)?;
+
+        let ip_address = command.client.ip_address.clone();
+        self.login_attempt_guard
+            .check(&txn, &member.email, ip_address.as_deref())
+            .await?;
+        if !verify_second_factor(&mut mfa, &command.code, &self.config.get().mfa_issuer, &member.email)? {
+            self.login_attempt_guard
+                .record_failure(&txn, &member.email, Some(member_id), ip_address.as_deref())
+                .await?;
+            txn.commit().await?;
+            return Err(AppError::from(ErrorCode::InvalidMfaCode));
+        }
+        self.save_mfa_port.update(&txn, mfa).await?;
+        self.login_attempt_guard.record_success(&txn, &member.email).await?;
+
+        let result = self.issue_tokens(&txn, &member, command.client, true).await?;
+        txn.commit().await?;
+
+        Ok(result)
     }
 }
 
@@ -194,32 +367,164 @@ pub struct JwtUseCaseImpl {
     load_member_port: Arc<dyn LoadMemberPort>,
     #[shaku(inject)]
     jwt_service: Arc<dyn JwtService>,
+    #[shaku(inject)]
+    load_refresh_token_port: Arc<dyn LoadRefreshTokenPort>,
+    #[shaku(inject)]
+    save_refresh_token_port: Arc<dyn SaveRefreshTokenPort>,
+    #[shaku(inject)]
+    load_session_port: Arc<dyn LoadSessionPort>,
+    #[shaku(inject)]
+    save_session_port: Arc<dyn SaveSessionPort>,
+    #[shaku(inject)]
+    config: Arc<dyn ConfigProvider>,
 }
 
 
+impl JwtUseCaseImpl {
+    /// 이미 교체된 토큰이 다시 사용되었다면 탈취된 것으로 보고 같은 family 전체를 폐기한다.
+    async fn revoke_reused_family(&self, txn: DatabaseTransaction, member_id: Uuid, family_id: Uuid) -> Result<JwtReissueResult, ErrorCode> {
+        tracing::warn!("refresh token reuse detected. member: {}, family: {}", member_id, family_id);
+        self.save_refresh_token_port
+            .revoke_family(&txn, family_id)
+            .await?;
+        self.save_session_port
+            .revoke(&txn, family_id)
+            .await?;
+        txn.commit().await?;
+        Err(ErrorCode::RefreshTokenReused)
+    }
+}
+
 #[async_trait::async_trait]
 impl JwtUseCase for JwtUseCaseImpl {
-    async fn refresh_jwt(&self, refresh_token: String) -> Result<JwtReissueResult, ErrorCode> {
-        let txn = self.db.ro_txn().await?;
-
-        let member_id = self
+    async fn refresh_jwt(&self, refresh_token: String, client: ClientInfo) -> Result<JwtReissueResult, ErrorCode> {
+        let claims = self
             .jwt_service
             .decode_refresh_token(&refresh_token)
-            .map_err(|_| ErrorCode::Unauthorized)
-            .and_then(|claims| Uuid::parse_str(claims.sub.as_str()).map_err(|_| ErrorCode::Unauthorized))?;
+            .map_err(|_| ErrorCode::Unauthorized)?;
+        let member_id = Uuid::parse_str(claims.sub.as_str()).map_err(|_| ErrorCode::Unauthorized)?;
+        let jti = Uuid::parse_str(claims.jti.as_str()).map_err(|_| ErrorCode::Unauthorized)?;
+
+        let txn = self.db.rw_txn().await?;
+
+        let stored_token = self
+            .load_refresh_token_port
+            .find_by_id(&txn, jti)
+            .await?
+            .ok_or(ErrorCode::InvalidJwtToken)?;
+
+        if stored_token.get_member_id() != member_id || !stored_token.matches(&hash_token(&refresh_token)) {
+            return Err(ErrorCode::InvalidJwtToken);
+        }
+
+        if stored_token.is_revoked() {
+            return self.revoke_reused_family(txn, member_id, stored_token.get_family_id()).await;
+        }
+
+        let now = chrono::Utc::now().naive_utc();
+        if stored_token.is_expired(now) {
+            return Err(ErrorCode::InvalidJwtToken);
+        }
+
+        let mut session = self
+            .load_session_port
+            .find_by_id(&txn, stored_token.get_family_id())
+            .await?
+            .filter(|session| session.is_active(now))
+            .ok_or(ErrorCode::RevokedSession)?;
+        let expires_at = refresh_token_expires_at(self.config.as_ref(), now);
+        session.touch(client.ip_address, expires_at);
+        let session = self.save_session_port.update(&txn, session).await?;
 
         let member = match self.load_member_port.find_by_id(&txn, member_id).await? {
             Some(member) => member,
             None => return Err(ErrorCode::NotFound),
         };
 
+        let (new_refresh_token, new_jti) = issue_refresh_token(
+            &txn,
+            self.jwt_service.as_ref(),
+            self.save_refresh_token_port.as_ref(),
+            &member,
+            stored_token.get_family_id(),
+            expires_at,
+        ).await?;
+
+        // 조회 이후 다른 요청이 먼저 같은 토큰을 교체했다면 재사용으로 본다.
+        let rotated = self.save_refresh_token_port
+            .rotate_if_active(&txn, jti, new_jti)
+            .await?;
+        if !rotated {
+            return self.revoke_reused_family(txn, member_id, stored_token.get_family_id()).await;
+        }
+
         txn.commit().await?;
 
         Ok(JwtReissueResult {
             access_token: self
                 .jwt_service
-                .create_access_token(&member)
+                .create_access_token(&member, session.get_id(), session.is_mfa_authenticated())
                 .map_err(|_| ErrorCode::Unauthorized)?,
+            refresh_token: new_refresh_token,
         })
     }
 }
+
+#[derive(Component)]
+#[shaku(interface = LogoutUseCase)]
+pub struct LogoutUseCaseImpl {
+    #[shaku(inject)]
+    db: Arc<dyn DbConnProvider>,
+    #[shaku(inject)]
+    jwt_service: Arc<dyn JwtService>,
+    #[shaku(inject)]
+    load_refresh_token_port: Arc<dyn LoadRefreshTokenPort>,
+    #[shaku(inject)]
+    save_refresh_token_port: Arc<dyn SaveRefreshTokenPort>,
+    #[shaku(inject)]
+    save_session_port: Arc<dyn SaveSessionPort>,
+}
+
+#[async_trait::async_trait]
+impl LogoutUseCase for LogoutUseCaseImpl {
+    async fn logout(&self, refresh_token: String) -> Result<(), ErrorCode> {
+        // 만료되었거나 변조된 토큰이라면 폐기할 세션이 없으므로 그대로 성공 처리한다.
+        let jti = match self
+            .jwt_service
+            .decode_refresh_token(&refresh_token)
+            .ok()
+            .and_then(|claims| Uuid::parse_str(claims.jti.as_str()).ok())
+        {
+            Some(jti) => jti,
+            None => return Ok(()),
+        };
+
+        let txn = self.db.rw_txn().await?;
+
+        if let Some(stored_token) = self.load_refresh_token_port.find_by_id(&txn, jti).await? {
+            if stored_token.matches(&hash_token(&refresh_token)) {
+                self.save_refresh_token_port
+                    .revoke_family(&txn, stored_token.get_family_id())
+                    .await?;
+                self.save_session_port
+                    .revoke(&txn, stored_token.get_family_id())
+                    .await?;
+            }
+        }
+
+        txn.commit().await?;
+        Ok(())
+    }
+
+    async fn logout_all(&self, login_member: LoginMember) -> Result<(), ErrorCode> {
+        let txn = self.db.rw_txn().await?;
+        self.save_refresh_token_port
+            .revoke_all_by_member(&txn, login_member.id)
+            .await?;
+        self.save_session_port
+            .revoke_all_by_member(&txn, login_member.id)
+            .await?;
+        txn.commit().await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,279 @@
+use super::usecases::{IdentityDto, LinkTicketDto, OAuth2IdentityUseCase, OAuth2LinkCommand, OAuth2TokenReencryptUseCase};
+use super::verification_service::{consume_token, issue_token};
+use crate::application::oauth2::OAuth2Provider;
+use crate::common::database::DbConnProvider;
+use crate::common::error::error_code::ErrorCode;
+use crate::common::jwt::JwtService;
+use crate::common::{AppError, LoginMember, Permission};
+use crate::domain::member::oauth2_member::entity::OAuth2MemberEntity;
+use crate::domain::member::oauth2_member::repository::{LoadOAuth2MemberPort, SaveOAuth2MemberPort};
+use crate::domain::member::repository::LoadMemberPort;
+use crate::domain::member::verification_token::entity::VerificationPurpose;
+use crate::domain::member::verification_token::repository::{LoadVerificationTokenPort, SaveVerificationTokenPort};
+use shaku::Component;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// 계정 연결 티켓 유효 시간. 발급 직후 제공자 로그인 화면으로 이동하는 데만 쓴다.
+const OAUTH2_LINK_TICKET_MINUTES: i64 = 5;
+
+#[derive(Component)]
+#[shaku(interface = OAuth2IdentityUseCase)]
+pub struct OAuth2IdentityUseCaseImpl {
+    #[shaku(inject)]
+    db: Arc<dyn DbConnProvider>,
+    #[shaku(inject)]
+    load_member_port: Arc<dyn LoadMemberPort>,
+    #[shaku(inject)]
+    load_oauth2_member_port: Arc<dyn LoadOAuth2MemberPort>,
+    #[shaku(inject)]
+    save_oauth2_member_port: Arc<dyn SaveOAuth2MemberPort>,
+    #[shaku(inject)]
+    jwt_service: Arc<dyn JwtService>,
+    #[shaku(inject)]
+    load_verification_token_port: Arc<dyn LoadVerificationTokenPort>,
+    #[shaku(inject)]
+    save_verification_token_port: Arc<dyn SaveVerificationTokenPort>,
+}
+
+fn to_identity_dto(identity: &OAuth2MemberEntity) -> IdentityDto {
+    IdentityDto {
+        provider: identity.get_provider().clone(),
+        user_id: identity.get_user_id().clone(),
+        email: identity.get_email().clone(),
+    }
+}
+
+fn check_member_access(login_member: &LoginMember, member_id: Uuid) -> Result<(), AppError> {
+    if login_member.id != member_id && !login_member.has_permission(Permission::MemberAdmin) {
+        return Err(AppError::from(ErrorCode::Forbidden));
+    }
+    Ok(())
+}
+
+#[async_trait::async_trait]
+impl OAuth2IdentityUseCase for OAuth2IdentityUseCaseImpl {
+    async fn link(&self, command: OAuth2LinkCommand) -> Result<IdentityDto, AppError> {
+        let txn = self.db.rw_txn().await?;
+        let member = self
+            .load_member_port
+            .find_by_id(&txn, command.member_id)
+            .await?
+            .ok_or_else(|| AppError::from(ErrorCode::MemberNotFound))?;
+
+        let linked = self
+            .load_oauth2_member_port
+            .find_by_provider_and_user_id(&txn, command.provider.clone(), command.user_id.clone())
+            .await?;
+        if let Some(linked) = linked {
+            return Err(if linked.get_member_id() == command.member_id {
+                AppError::with_message(ErrorCode::Conflict, "이미 연결된 계정입니다.")
+            } else {
+                AppError::with_message(ErrorCode::Conflict, "다른 회원에게 연결된 계정입니다.")
+            });
+        }
+
+        let identities = self
+            .load_oauth2_member_port
+            .find_by_member_id(&txn, command.member_id)
+            .await?;
+        if identities.iter().any(|identity| *identity.get_provider() == command.provider) {
+            return Err(AppError::with_message(ErrorCode::Conflict, "이미 같은 제공자의 계정이 연결되어 있습니다."));
+        }
+
+        // 제공자가 이메일을 알려주지 않으면 회원 이메일을 기록한다. (oauth2_member.email 은 not null)
+        let identity = OAuth2MemberEntity::new(
+            None,
+            command.member_id,
+            command.provider,
+            command.user_id,
+            command.email.or(Some(member.email)),
+            command.access_token,
+        );
+        let identity = self.save_oauth2_member_port.save(&txn, identity).await?;
+        txn.commit().await?;
+
+        Ok(to_identity_dto(&identity))
+    }
+
+    async fn get_identities(&self, login_member: LoginMember, member_id: Uuid) -> Result<Vec<IdentityDto>, AppError> {
+        check_member_access(&login_member, member_id)?;
+
+        let txn = self.db.ro_txn().await?;
+        let identities = self
+            .load_oauth2_member_port
+            .find_by_member_id(&txn, member_id)
+            .await?;
+        txn.commit().await?;
+
+        Ok(identities.iter().map(to_identity_dto).collect())
+    }
+
+    async fn unlink(&self, login_member: LoginMember, member_id: Uuid, provider: String) -> Result<(), AppError> {
+        check_member_access(&login_member, member_id)?;
+        let provider = OAuth2Provider::from_path(&provider.to_lowercase())
+            .ok_or_else(|| AppError::with_message(ErrorCode::NotFound, "지원하지 않는 OAuth2 제공자입니다."))?;
+
+        let txn = self.db.rw_txn().await?;
+        let member = self
+            .load_member_port
+            .find_by_id(&txn, member_id)
+            .await?
+            .ok_or_else(|| AppError::from(ErrorCode::MemberNotFound))?;
+        let identities = self
+            .load_oauth2_member_port
+            .find_by_member_id(&txn, member_id)
+            .await?;
+
+        let target = identities
+            .iter()
+            .find(|identity| identity.get_provider() == provider.as_str())
+            .ok_or_else(|| AppError::with_message(ErrorCode::NotFound, "연결되지 않은 제공자입니다."))?;
+
+        if !member.has_password && identities.len() <= 1 {
+            return Err(AppError::with_message(ErrorCode::BadRequest, "마지막 로그인 수단은 연결을 해제할 수 없습니다."));
+        }
+
+        self.save_oauth2_member_port
+            .delete(&txn, target.get_id().expect("저장된 계정은 ID 가 있어야 합니다."))
+            .await?;
+        txn.commit().await?;
+
+        Ok(())
+    }
+
+    async fn issue_link_ticket(&self, login_member: LoginMember) -> Result<LinkTicketDto, AppError> {
+        let txn = self.db.rw_txn().await?;
+        let ticket = issue_token(
+            &txn,
+            self.jwt_service.as_ref(),
+            self.save_verification_token_port.as_ref(),
+            login_member.id,
+            VerificationPurpose::OAuth2Link,
+            chrono::Duration::minutes(OAUTH2_LINK_TICKET_MINUTES),
+        )
+        .await?;
+        txn.commit().await?;
+
+        Ok(LinkTicketDto {
+            ticket,
+            expires_in: OAUTH2_LINK_TICKET_MINUTES * 60,
+        })
+    }
+
+    async fn consume_link_ticket(&self, ticket: String) -> Result<Uuid, AppError> {
+        let txn = self.db.rw_txn().await?;
+        let member_id = consume_token(
+            &txn,
+            self.jwt_service.as_ref(),
+            self.load_verification_token_port.as_ref(),
+            self.save_verification_token_port.as_ref(),
+            &ticket,
+            VerificationPurpose::OAuth2Link,
+        )
+        .await?;
+        txn.commit().await?;
+
+        Ok(member_id)
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = OAuth2TokenReencryptUseCase)]
+pub struct OAuth2TokenReencryptUseCaseImpl {
+    #[shaku(inject)]
+    db: Arc<dyn DbConnProvider>,
+    #[shaku(inject)]
+    save_oauth2_member_port: Arc<dyn SaveOAuth2MemberPort>,
+}
+
+#[async_trait::async_trait]
+impl OAuth2TokenReencryptUseCase for OAuth2TokenReencryptUseCaseImpl {
+    async fn reencrypt_all(&self) -> Result<u64, AppError> {
+        let txn = self.db.rw_txn().await?;
+        let reencrypted = self.save_oauth2_member_port.reencrypt_access_tokens(&txn).await?;
+        txn.commit().await?;
+
+        Ok(reencrypted)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::OAuth2IdentityUseCaseImpl;
+    use crate::application::auth::OAuth2IdentityUseCase;
+    use crate::common::database::DbConnProviderImpl;
+    use crate::common::jwt::JwtServiceImpl;
+    use crate::common::{LoginMember, SecurityRole, TokenCipherImpl};
+    use crate::config::test_config;
+    use crate::domain::member::oauth2_member::repository::{
+        SeaormLoadOAuth2MemberAdapter, SeaormSaveOAuth2MemberAdapter,
+    };
+    use crate::domain::member::oauth2_member::schema::Model as OAuth2MemberModel;
+    use crate::domain::member::repository::MemberQueryRepository;
+    use crate::domain::member::schema::Model as MemberModel;
+    use crate::domain::member::verification_token::repository::{
+        SeaormLoadVerificationTokenAdapter, SeaormSaveVerificationTokenAdapter,
+    };
+    use sea_orm::{DatabaseBackend, MockDatabase};
+    use std::sync::Arc;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn refuse_unlinking_last_login_method_test() {
+        let member_id = Uuid::new_v4();
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([vec![MemberModel {
+                id: member_id,
+                nickname: "GITHUB:1".to_owned(),
+                email: "GITHUB-1@dohoon-kim.kr".to_owned(),
+                password: "random".to_owned(),
+                role: "MEMBER".to_owned(),
+                created_at: chrono::Utc::now().naive_utc(),
+                updated_at: None,
+                is_activated: true,
+                has_password: false,
+            }]])
+            .append_query_results([vec![OAuth2MemberModel {
+                id: Uuid::new_v4(),
+                provider: "GITHUB".to_owned(),
+                user_id: "1".to_owned(),
+                member_id,
+                email: Some("GITHUB-1@dohoon-kim.kr".to_owned()),
+                access_token: "token".to_owned(),
+            }]])
+            .into_connection();
+
+        let config = test_config(&[]);
+        let token_cipher = Arc::new(TokenCipherImpl {
+            config: config.clone(),
+            key_ring: Default::default(),
+        });
+        let usecase = OAuth2IdentityUseCaseImpl {
+            db: Arc::new(DbConnProviderImpl { db }),
+            load_member_port: Arc::new(MemberQueryRepository {}),
+            load_oauth2_member_port: Arc::new(SeaormLoadOAuth2MemberAdapter { token_cipher: token_cipher.clone() }),
+            save_oauth2_member_port: Arc::new(SeaormSaveOAuth2MemberAdapter { token_cipher }),
+            jwt_service: Arc::new(JwtServiceImpl { config, key_ring: Default::default() }),
+            load_verification_token_port: Arc::new(SeaormLoadVerificationTokenAdapter {}),
+            save_verification_token_port: Arc::new(SeaormSaveVerificationTokenAdapter {}),
+        };
+        let login_member = LoginMember {
+            id: member_id,
+            nickname: "GITHUB:1".to_owned(),
+            email: "GITHUB-1@dohoon-kim.kr".to_owned(),
+            roles: vec![SecurityRole::Member],
+            is_activated: true,
+            session_id: None,
+            mfa_authenticated: false,
+            token_scopes: None,
+        };
+
+        let err = usecase
+            .unlink(login_member, member_id, "github".to_owned())
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(err.message, "마지막 로그인 수단은 연결을 해제할 수 없습니다.");
+    }
+}
@@ -0,0 +1,157 @@
+use crate::common::error::error_code::ErrorCode;
+use crate::config::{AppConfig, ConfigProvider};
+use crate::domain::audit::entity::{AuditEventType, AuditLogEntity};
+use crate::domain::audit::repository::SaveAuditLogPort;
+use crate::domain::member::login_attempt::entity::{LockoutPolicy, LoginAttemptEntity, LoginAttemptScope};
+use crate::domain::member::login_attempt::repository::{LoadLoginAttemptPort, SaveLoginAttemptPort};
+use chrono::{Duration, NaiveDateTime};
+use sea_orm::DatabaseTransaction;
+use shaku::{Component, Interface};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// 로그인 실패를 계정(이메일)과 IP 별로 세고, 실패가 쌓이면 일정 시간 로그인을 막는다.
+/// 가입되지 않은 이메일도 같은 방식으로 세어 잠금 여부로 가입 여부를 알 수 없게 한다.
+#[async_trait::async_trait]
+pub trait LoginAttemptGuard: Interface {
+    /// 계정이나 IP 가 잠겨 있으면 `LoginLocked` 를 반환한다.
+    async fn check(&self, txn: &DatabaseTransaction, account: &str, ip_address: Option<&str>) -> Result<(), ErrorCode>;
+
+    /// 실패를 기록한다. 이번 실패로 잠기면 감사 로그를 남긴다.
+    async fn record_failure(
+        &self,
+        txn: &DatabaseTransaction,
+        account: &str,
+        member_id: Option<Uuid>,
+        ip_address: Option<&str>,
+    ) -> Result<(), ErrorCode>;
+
+    /// 로그인에 성공하면 계정의 실패 기록을 지운다. IP 기록은 남겨둔다. (가진 계정으로 로그인해 IP 기록을 지울 수 없도록)
+    async fn record_success(&self, txn: &DatabaseTransaction, account: &str) -> Result<(), ErrorCode>;
+}
+
+#[derive(Component)]
+#[shaku(interface = LoginAttemptGuard)]
+pub struct LoginAttemptGuardImpl {
+    #[shaku(inject)]
+    config: Arc<dyn ConfigProvider>,
+    #[shaku(inject)]
+    load_login_attempt_port: Arc<dyn LoadLoginAttemptPort>,
+    #[shaku(inject)]
+    save_login_attempt_port: Arc<dyn SaveLoginAttemptPort>,
+    #[shaku(inject)]
+    save_audit_log_port: Arc<dyn SaveAuditLogPort>,
+}
+
+/// 실패 기록 키(login_attempt.attempt_key)에 저장할 수 있는 길이
+pub const MAX_ACCOUNT_KEY_LENGTH: usize = 255;
+
+/// 이메일은 대소문자와 앞뒤 공백을 무시하고 센다.
+fn account_key(account: &str) -> String {
+    account.trim().to_lowercase()
+}
+
+fn lockout_policy(config: &AppConfig, scope: LoginAttemptScope) -> LockoutPolicy {
+    LockoutPolicy {
+        threshold: match scope {
+            LoginAttemptScope::Account => config.login_lockout_threshold,
+            LoginAttemptScope::Ip => config.login_ip_lockout_threshold,
+        },
+        base: Duration::seconds(config.login_lockout_base_seconds),
+        max: Duration::seconds(config.login_lockout_max_seconds),
+        reset_after: Duration::minutes(config.login_failure_reset_minutes),
+    }
+}
+
+impl LoginAttemptGuardImpl {
+    fn attempt_keys(account: &str, ip_address: Option<&str>) -> Vec<(LoginAttemptScope, String)> {
+        let mut keys = vec![(LoginAttemptScope::Account, account_key(account))];
+        if let Some(ip_address) = ip_address {
+            keys.push((LoginAttemptScope::Ip, ip_address.to_owned()));
+        }
+        keys
+    }
+
+    async fn audit_lockout(
+        &self,
+        txn: &DatabaseTransaction,
+        attempt: &LoginAttemptEntity,
+        locked_until: NaiveDateTime,
+        member_id: Option<Uuid>,
+        ip_address: Option<&str>,
+    ) -> Result<(), ErrorCode> {
+        tracing::warn!(
+            "Login locked: scope={}, key={}, failures={}, until={}",
+            attempt.get_scope().as_str(),
+            attempt.get_key(),
+            attempt.get_failure_count(),
+            locked_until
+        );
+
+        self.save_audit_log_port
+            .save(txn, AuditLogEntity::new(
+                None,
+                AuditEventType::LoginLocked,
+                member_id,
+                ip_address.map(str::to_owned),
+                serde_json::json!({
+                    "scope": attempt.get_scope().as_str(),
+                    "key": attempt.get_key(),
+                    "failure_count": attempt.get_failure_count(),
+                    "locked_until": locked_until.and_utc().to_rfc3339(),
+                }),
+                None,
+            ))
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl LoginAttemptGuard for LoginAttemptGuardImpl {
+    async fn check(&self, txn: &DatabaseTransaction, account: &str, ip_address: Option<&str>) -> Result<(), ErrorCode> {
+        let now = chrono::Utc::now().naive_utc();
+        for (scope, key) in Self::attempt_keys(account, ip_address) {
+            let locked = self
+                .load_login_attempt_port
+                .find(txn, scope, &key)
+                .await?
+                .and_then(|attempt| attempt.locked_until(now));
+            if locked.is_some() {
+                return Err(ErrorCode::LoginLocked);
+            }
+        }
+        Ok(())
+    }
+
+    async fn record_failure(
+        &self,
+        txn: &DatabaseTransaction,
+        account: &str,
+        member_id: Option<Uuid>,
+        ip_address: Option<&str>,
+    ) -> Result<(), ErrorCode> {
+        let config = self.config.get();
+        let now = chrono::Utc::now().naive_utc();
+        for (scope, key) in Self::attempt_keys(account, ip_address) {
+            let policy = lockout_policy(&config, scope);
+            let mut attempt = self
+                .save_login_attempt_port
+                .increment_failure(txn, scope, &key, now, now - policy.reset_after)
+                .await?;
+
+            if let Some(locked_until) = attempt.lock_after_failure(now, &policy) {
+                self.save_login_attempt_port.lock(txn, scope, &key, locked_until).await?;
+                self.audit_lockout(txn, &attempt, locked_until, member_id, ip_address).await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn record_success(&self, txn: &DatabaseTransaction, account: &str) -> Result<(), ErrorCode> {
+        self.save_login_attempt_port
+            .delete(txn, LoginAttemptScope::Account, &account_key(account))
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,246 @@
+use super::login_guard::LoginAttemptGuard;
+use super::usecases::{MfaUseCase, TotpEnrollmentDto};
+use crate::common::database::DbConnProvider;
+use crate::common::error::error_code::ErrorCode;
+use crate::common::token_hasher::hash_token;
+use crate::common::{AppError, ClientInfo, LoginMember};
+use crate::config::ConfigProvider;
+use crate::domain::member::mfa::entity::MfaEntity;
+use crate::domain::member::mfa::repository::{LoadMfaPort, SaveMfaPort};
+use crate::domain::member::repository::LoadMemberPort;
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use shaku::Component;
+use std::sync::Arc;
+use totp_rs::{Algorithm, Secret, TOTP};
+use uuid::Uuid;
+
+/// TOTP 코드 자릿수와 시간 구간(초). 대부분의 인증 앱이 이 값만 지원한다.
+const TOTP_DIGITS: usize = 6;
+const TOTP_STEP: u64 = 30;
+/// 기기 시간이 어긋난 경우를 위해 앞뒤 한 구간까지 허용한다.
+const TOTP_SKEW: i64 = 1;
+const RECOVERY_CODE_COUNT: usize = 10;
+const RECOVERY_CODE_LENGTH: usize = 10;
+
+/// 로그인 1단계를 통과한 회원에게 발급하는 토큰의 용도와 유효 시간
+pub(crate) const MFA_PENDING_PURPOSE: &str = "MFA_PENDING";
+pub(crate) const MFA_PENDING_TOKEN_MINUTES: i64 = 5;
+
+fn build_totp(secret: &str, issuer: &str, account_name: &str) -> Result<TOTP, AppError> {
+    let invalid_secret = |e: String| {
+        tracing::error!("Invalid TOTP secret: {}", e);
+        AppError::from(ErrorCode::InternalServerError)
+    };
+
+    let secret = Secret::Encoded(secret.to_owned())
+        .to_bytes()
+        .map_err(|e| invalid_secret(e.to_string()))?;
+    TOTP::new(
+        Algorithm::SHA1,
+        TOTP_DIGITS,
+        TOTP_SKEW as u8,
+        TOTP_STEP,
+        secret,
+        Some(issuer.to_owned()),
+        account_name.to_owned(),
+    )
+    .map_err(|e| invalid_secret(e.to_string()))
+}
+
+/// TOTP 코드가 맞으면 해당 시간 구간을 반환한다.
+fn match_totp_step(totp: &TOTP, code: &str) -> Option<i64> {
+    let now = chrono::Utc::now().timestamp();
+    (-TOTP_SKEW..=TOTP_SKEW)
+        .map(|offset| now + offset * TOTP_STEP as i64)
+        .filter(|time| *time >= 0)
+        .find(|time| totp.generate(*time as u64) == code)
+        .map(|time| time / TOTP_STEP as i64)
+}
+
+fn normalize_recovery_code(code: &str) -> String {
+    code.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_lowercase()
+}
+
+/// 복구 코드 원문 목록과 저장할 해시 목록을 만든다.
+fn generate_recovery_codes() -> (Vec<String>, Vec<String>) {
+    let mut rng = rand::thread_rng();
+    let codes: Vec<String> = (0..RECOVERY_CODE_COUNT)
+        .map(|_| {
+            let code: String = (0..RECOVERY_CODE_LENGTH)
+                .map(|_| rng.sample(Alphanumeric) as char)
+                .collect::<String>()
+                .to_lowercase();
+            format!("{}-{}", &code[..RECOVERY_CODE_LENGTH / 2], &code[RECOVERY_CODE_LENGTH / 2..])
+        })
+        .collect();
+    let hashes = codes
+        .iter()
+        .map(|code| hash_token(&normalize_recovery_code(code)))
+        .collect();
+    (codes, hashes)
+}
+
+/// TOTP 코드(6자리) 또는 복구 코드를 확인한다. 확인되면 사용한 코드를 엔티티에 기록하므로 저장해야 한다.
+pub(crate) fn verify_second_factor(mfa: &mut MfaEntity, code: &str, issuer: &str, account_name: &str) -> Result<bool, AppError> {
+    let code = code.trim();
+    if code.len() == TOTP_DIGITS && code.chars().all(|c| c.is_ascii_digit()) {
+        let totp = build_totp(mfa.get_secret(), issuer, account_name)?;
+        return Ok(match_totp_step(&totp, code).is_some_and(|step| mfa.accept_step(step)));
+    }
+
+    Ok(mfa.use_recovery_code(&hash_token(&normalize_recovery_code(code))))
+}
+
+#[derive(Component)]
+#[shaku(interface = MfaUseCase)]
+pub struct MfaUseCaseImpl {
+    #[shaku(inject)]
+    db: Arc<dyn DbConnProvider>,
+    #[shaku(inject)]
+    config: Arc<dyn ConfigProvider>,
+    #[shaku(inject)]
+    load_member_port: Arc<dyn LoadMemberPort>,
+    #[shaku(inject)]
+    load_mfa_port: Arc<dyn LoadMfaPort>,
+    #[shaku(inject)]
+    save_mfa_port: Arc<dyn SaveMfaPort>,
+    #[shaku(inject)]
+    login_attempt_guard: Arc<dyn LoginAttemptGuard>,
+}
+
+/// 2단계 인증 설정은 본인만 바꿀 수 있다.
+fn check_owner(login_member: &LoginMember, member_id: Uuid) -> Result<(), AppError> {
+    if login_member.id != member_id {
+        return Err(AppError::from(ErrorCode::Forbidden));
+    }
+    Ok(())
+}
+
+#[async_trait::async_trait]
+impl MfaUseCase for MfaUseCaseImpl {
+    async fn begin_totp_enrollment(&self, login_member: LoginMember, member_id: Uuid) -> Result<TotpEnrollmentDto, AppError> {
+        check_owner(&login_member, member_id)?;
+
+        let txn = self.db.rw_txn().await?;
+        let member = self
+            .load_member_port
+            .find_by_id(&txn, member_id)
+            .await?
+            .ok_or_else(|| AppError::from(ErrorCode::MemberNotFound))?;
+        if let Some(mfa) = self.load_mfa_port.find_by_member_id(&txn, member_id).await? {
+            if mfa.is_enabled() {
+                return Err(AppError::with_message(ErrorCode::Conflict, "이미 2단계 인증이 설정되어 있습니다."));
+            }
+        }
+
+        let secret = Secret::generate_secret().to_encoded().to_string();
+        let totp = build_totp(&secret, &self.config.get().mfa_issuer, &member.email)?;
+        self.save_mfa_port
+            .save(&txn, MfaEntity::new(member_id, secret.clone(), vec![], None, None, None))
+            .await?;
+        txn.commit().await?;
+
+        Ok(TotpEnrollmentDto {
+            secret,
+            otpauth_uri: totp.get_url(),
+        })
+    }
+
+    async fn confirm_totp_enrollment(&self, login_member: LoginMember, member_id: Uuid, code: String, client: ClientInfo) -> Result<Vec<String>, AppError> {
+        check_owner(&login_member, member_id)?;
+
+        let txn = self.db.rw_txn().await?;
+        let ip_address = client.ip_address;
+        self.login_attempt_guard
+            .check(&txn, &login_member.email, ip_address.as_deref())
+            .await?;
+        let mut mfa = self
+            .load_mfa_port
+            .find_by_member_id(&txn, member_id)
+            .await?
+            .ok_or_else(|| AppError::with_message(ErrorCode::BadRequest, "2단계 인증 등록을 먼저 시작해주세요."))?;
+        if mfa.is_enabled() {
+            return Err(AppError::with_message(ErrorCode::Conflict, "이미 2단계 인증이 설정되어 있습니다."));
+        }
+
+        // 등록 확인은 인증 앱의 코드로만 받는다. (아직 복구 코드가 없다)
+        let totp = build_totp(mfa.get_secret(), &self.config.get().mfa_issuer, &login_member.email)?;
+        let Some(step) = match_totp_step(&totp, code.trim()) else {
+            self.login_attempt_guard
+                .record_failure(&txn, &login_member.email, Some(member_id), ip_address.as_deref())
+                .await?;
+            txn.commit().await?;
+            return Err(AppError::from(ErrorCode::InvalidMfaCode));
+        };
+        mfa.accept_step(step);
+
+        let (recovery_codes, recovery_code_hashes) = generate_recovery_codes();
+        mfa.enable(recovery_code_hashes);
+        self.save_mfa_port.update(&txn, mfa).await?;
+        txn.commit().await?;
+
+        Ok(recovery_codes)
+    }
+
+    async fn disable_totp(&self, login_member: LoginMember, member_id: Uuid, code: String, client: ClientInfo) -> Result<(), AppError> {
+        check_owner(&login_member, member_id)?;
+
+        let txn = self.db.rw_txn().await?;
+        let ip_address = client.ip_address;
+        // 액세스 토큰만 가진 사람이 코드를 무작위 대입해 2단계 인증을 끄지 못하도록 로그인과 같은 잠금을 적용한다.
+        self.login_attempt_guard
+            .check(&txn, &login_member.email, ip_address.as_deref())
+            .await?;
+        let mut mfa = self
+            .load_mfa_port
+            .find_by_member_id(&txn, member_id)
+            .await?
+            .filter(|mfa| mfa.is_enabled())
+            .ok_or_else(|| AppError::with_message(ErrorCode::NotFound, "2단계 인증이 설정되어 있지 않습니다."))?;
+
+        if !verify_second_factor(&mut mfa, &code, &self.config.get().mfa_issuer, &login_member.email)? {
+            self.login_attempt_guard
+                .record_failure(&txn, &login_member.email, Some(member_id), ip_address.as_deref())
+                .await?;
+            txn.commit().await?;
+            return Err(AppError::from(ErrorCode::InvalidMfaCode));
+        }
+
+        self.save_mfa_port.delete(&txn, member_id).await?;
+        txn.commit().await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{build_totp, generate_recovery_codes, verify_second_factor};
+    use crate::domain::member::mfa::entity::MfaEntity;
+    use totp_rs::Secret;
+    use uuid::Uuid;
+
+    #[test]
+    fn verify_second_factor_test() {
+        let secret = Secret::generate_secret().to_encoded().to_string();
+        let totp = build_totp(&secret, "dohoon-kim.kr", "member@dohoon-kim.kr").ok().unwrap();
+        assert!(totp.get_url().starts_with("otpauth://totp/dohoon-kim.kr:member%40dohoon-kim.kr?secret="));
+
+        let (recovery_codes, recovery_code_hashes) = generate_recovery_codes();
+        let mut mfa = MfaEntity::new(Uuid::new_v4(), secret, vec![], None, None, None);
+        mfa.enable(recovery_code_hashes);
+
+        let code = totp.generate_current().unwrap();
+        assert!(verify_second_factor(&mut mfa, &code, "dohoon-kim.kr", "member@dohoon-kim.kr").ok().unwrap());
+        // 같은 코드는 다시 쓸 수 없다.
+        assert!(!verify_second_factor(&mut mfa, &code, "dohoon-kim.kr", "member@dohoon-kim.kr").ok().unwrap());
+
+        let recovery_code = recovery_codes[0].to_uppercase();
+        assert!(verify_second_factor(&mut mfa, &recovery_code, "dohoon-kim.kr", "member@dohoon-kim.kr").ok().unwrap());
+        assert!(!verify_second_factor(&mut mfa, &recovery_code, "dohoon-kim.kr", "member@dohoon-kim.kr").ok().unwrap());
+    }
+}
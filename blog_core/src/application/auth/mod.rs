@@ -1,5 +1,17 @@
+pub mod access_token_service;
 pub mod auth_service;
+pub mod identity_service;
+pub mod login_guard;
+pub mod mfa_service;
+pub mod session_service;
 pub mod usecases;
+pub mod verification_service;
 
-pub use auth_service::{AuthService, JwtUseCaseImpl};
-pub use usecases::JwtUseCase;
+pub use access_token_service::{PersonalAccessTokenUseCaseImpl, PERSONAL_ACCESS_TOKEN_PREFIX};
+pub use auth_service::{AuthService, JwtUseCaseImpl, LogoutUseCaseImpl};
+pub use identity_service::{OAuth2IdentityUseCaseImpl, OAuth2TokenReencryptUseCaseImpl};
+pub use login_guard::LoginAttemptGuardImpl;
+pub use mfa_service::MfaUseCaseImpl;
+pub use session_service::{SessionQueryUseCaseImpl, SessionRevokeUseCaseImpl};
+pub use verification_service::{EmailVerificationUseCaseImpl, PasswordResetUseCaseImpl};
+pub use usecases::{EmailVerificationUseCase, JwtUseCase, LogoutUseCase, MfaUseCase, OAuth2IdentityUseCase, OAuth2TokenReencryptUseCase, PasswordResetUseCase, PersonalAccessTokenUseCase, SessionQueryUseCase, SessionRevokeUseCase};
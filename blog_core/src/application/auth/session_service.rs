@@ -0,0 +1,99 @@
+use super::usecases::{SessionDto, SessionQueryUseCase, SessionRevokeUseCase};
+use crate::common::database::DbConnProvider;
+use crate::common::error::error_code::ErrorCode;
+use crate::common::{LoginMember, Permission};
+use crate::domain::member::refresh_token::repository::SaveRefreshTokenPort;
+use crate::domain::member::session::entity::SessionEntity;
+use crate::domain::member::session::repository::{LoadSessionPort, SaveSessionPort};
+use shaku::Component;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Component)]
+#[shaku(interface = SessionQueryUseCase)]
+pub struct SessionQueryUseCaseImpl {
+    #[shaku(inject)]
+    db: Arc<dyn DbConnProvider>,
+    #[shaku(inject)]
+    load_session_port: Arc<dyn LoadSessionPort>,
+}
+
+#[derive(Component)]
+#[shaku(interface = SessionRevokeUseCase)]
+pub struct SessionRevokeUseCaseImpl {
+    #[shaku(inject)]
+    db: Arc<dyn DbConnProvider>,
+    #[shaku(inject)]
+    load_session_port: Arc<dyn LoadSessionPort>,
+    #[shaku(inject)]
+    save_session_port: Arc<dyn SaveSessionPort>,
+    #[shaku(inject)]
+    save_refresh_token_port: Arc<dyn SaveRefreshTokenPort>,
+}
+
+fn to_session_dto(session: SessionEntity, current_session_id: Option<Uuid>) -> SessionDto {
+    SessionDto {
+        id: session.get_id(),
+        user_agent: session.get_user_agent().clone(),
+        ip_address: session.get_ip_address().clone(),
+        created_at: session.get_created_at(),
+        last_used_at: session.get_last_used_at(),
+        is_current: current_session_id == Some(session.get_id()),
+    }
+}
+
+#[async_trait::async_trait]
+impl SessionQueryUseCase for SessionQueryUseCaseImpl {
+    async fn get_sessions(&self, login_member: LoginMember, member_id: Uuid) -> Result<Vec<SessionDto>, ErrorCode> {
+        if login_member.id != member_id && !login_member.has_permission(Permission::MemberAdmin) {
+            return Err(ErrorCode::Forbidden);
+        }
+
+        let txn = self.db.ro_txn().await?;
+        let sessions = self
+            .load_session_port
+            .find_active_by_member_id(&txn, member_id)
+            .await?;
+        txn.commit().await?;
+
+        Ok(sessions
+            .into_iter()
+            .map(|session| to_session_dto(session, login_member.session_id))
+            .collect())
+    }
+
+    async fn is_active(&self, session_id: Uuid) -> Result<bool, ErrorCode> {
+        let txn = self.db.ro_txn().await?;
+        let session = self.load_session_port.find_by_id(&txn, session_id).await?;
+        txn.commit().await?;
+
+        let now = chrono::Utc::now().naive_utc();
+        Ok(session.is_some_and(|session| session.is_active(now)))
+    }
+}
+
+#[async_trait::async_trait]
+impl SessionRevokeUseCase for SessionRevokeUseCaseImpl {
+    async fn revoke(&self, login_member: LoginMember, member_id: Uuid, session_id: Uuid) -> Result<(), ErrorCode> {
+        if login_member.id != member_id && !login_member.has_permission(Permission::MemberAdmin) {
+            return Err(ErrorCode::Forbidden);
+        }
+
+        let txn = self.db.rw_txn().await?;
+        let session = self
+            .load_session_port
+            .find_by_id(&txn, session_id)
+            .await?
+            .filter(|session| session.check_ownership(member_id))
+            .ok_or(ErrorCode::NotFound)?;
+
+        // 세션을 폐기하면 해당 세션의 refresh token 도 더 이상 재발급에 쓸 수 없다.
+        self.save_session_port.revoke(&txn, session.get_id()).await?;
+        self.save_refresh_token_port
+            .revoke_family(&txn, session.get_id())
+            .await?;
+        txn.commit().await?;
+
+        Ok(())
+    }
+}
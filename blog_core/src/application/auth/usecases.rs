@@ -1,10 +1,13 @@
 use crate::common::error::error_code::ErrorCode;
-use crate::common::AppError;
+use crate::common::{AppError, ClientInfo, LoginMember};
+use chrono::NaiveDateTime;
 use shaku::Interface;
+use uuid::Uuid;
 
 pub struct LoginCommand {
     pub principal: String,
     pub credential: String,
+    pub client: ClientInfo,
 }
 
 #[derive(Debug)]
@@ -13,27 +16,206 @@ pub struct LoginCommandResult {
     pub refresh_token: String,
 }
 
+#[derive(Debug)]
+pub enum LoginOutcome {
+    Authenticated(LoginCommandResult),
+    /// 2단계 인증이 설정된 회원. mfa_token 과 인증 코드로 다시 요청해야 토큰이 발급된다.
+    MfaRequired(MfaChallenge),
+}
+
+#[derive(Debug)]
+pub struct MfaChallenge {
+    pub mfa_token: String,
+    /// mfa_token 유효 시간(초)
+    pub expires_in: i64,
+}
+
+pub struct MfaLoginCommand {
+    pub mfa_token: String,
+    /// TOTP 코드 또는 복구 코드
+    pub code: String,
+    pub client: ClientInfo,
+}
+
+#[derive(Debug)]
+pub struct TotpEnrollmentDto {
+    /// base32 로 인코딩된 secret (QR 코드를 읽을 수 없을 때 직접 입력)
+    pub secret: String,
+    /// 인증 앱에서 읽을 QR 코드의 내용 (`otpauth://totp/...`)
+    pub otpauth_uri: String,
+}
+
 #[derive(Clone)]
 pub struct OAuth2LoginCommand {
     pub provider: String,
     pub user_id: String,
     pub email: Option<String>,
     pub access_token: String,
+    pub client: ClientInfo,
+}
+
+/// 로그인한 회원에게 OAuth2 제공자 계정을 연결한다.
+#[derive(Clone)]
+pub struct OAuth2LinkCommand {
+    pub member_id: Uuid,
+    pub provider: String,
+    pub user_id: String,
+    pub email: Option<String>,
+    pub access_token: String,
+}
+
+#[derive(Debug)]
+pub struct IdentityDto {
+    pub provider: String,
+    pub user_id: String,
+    pub email: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct SessionDto {
+    pub id: Uuid,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub last_used_at: NaiveDateTime,
+    /// 요청에 사용된 access token 이 이 세션에서 발급되었는지 여부
+    pub is_current: bool,
+}
+
+pub struct AccessTokenCreateCommand {
+    pub name: String,
+    /// `post:write` 와 같은 권한 이름. 발급하는 회원이 가진 권한만 지정할 수 있다.
+    pub scopes: Vec<String>,
+    /// 없으면 설정의 기본 유효 기간을 쓴다.
+    pub expires_in_days: Option<i64>,
+}
+
+#[derive(Debug)]
+pub struct AccessTokenDto {
+    pub id: Uuid,
+    pub name: String,
+    pub token_prefix: String,
+    pub scopes: Vec<String>,
+    pub expires_at: NaiveDateTime,
+    pub last_used_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug)]
+pub struct IssuedAccessTokenDto {
+    /// 토큰 원문. 발급할 때 한 번만 돌려준다.
+    pub token: String,
+    pub access_token: AccessTokenDto,
 }
 
 #[derive(Debug)]
 pub struct JwtReissueResult {
     pub access_token: String,
+    pub refresh_token: String,
 }
 
 #[async_trait::async_trait]
 pub trait LoginUseCase: Interface {
-    async fn login(&self, command: LoginCommand) -> Result<LoginCommandResult, ErrorCode>;
+    async fn login(&self, command: LoginCommand) -> Result<LoginOutcome, ErrorCode>;
+
+    async fn login_by_oauth2(&self, command: OAuth2LoginCommand) -> Result<LoginOutcome, AppError>;
 
-    async fn login_by_oauth2(&self, command: OAuth2LoginCommand) -> Result<LoginCommandResult, AppError>;
+    /// 로그인 1단계에서 받은 mfa_token 과 인증 코드를 확인하고 토큰을 발급한다.
+    async fn login_with_mfa(&self, command: MfaLoginCommand) -> Result<LoginCommandResult, AppError>;
 }
 
 #[async_trait::async_trait]
 pub trait JwtUseCase: Interface {
-    async fn refresh_jwt(&self, refresh_token: String) -> Result<JwtReissueResult, ErrorCode>;
+    async fn refresh_jwt(&self, refresh_token: String, client: ClientInfo) -> Result<JwtReissueResult, ErrorCode>;
+}
+
+#[async_trait::async_trait]
+pub trait LogoutUseCase: Interface {
+    /// refresh token 이 속한 로그인 세션(token family)을 폐기한다.
+    async fn logout(&self, refresh_token: String) -> Result<(), ErrorCode>;
+
+    /// 회원의 모든 로그인 세션을 폐기한다.
+    async fn logout_all(&self, login_member: LoginMember) -> Result<(), ErrorCode>;
+}
+
+#[async_trait::async_trait]
+pub trait SessionQueryUseCase: Interface {
+    async fn get_sessions(&self, login_member: LoginMember, member_id: Uuid) -> Result<Vec<SessionDto>, ErrorCode>;
+
+    async fn is_active(&self, session_id: Uuid) -> Result<bool, ErrorCode>;
+}
+
+#[async_trait::async_trait]
+pub trait PersonalAccessTokenUseCase: Interface {
+    async fn create(&self, login_member: LoginMember, member_id: Uuid, command: AccessTokenCreateCommand) -> Result<IssuedAccessTokenDto, AppError>;
+
+    async fn get_tokens(&self, login_member: LoginMember, member_id: Uuid) -> Result<Vec<AccessTokenDto>, AppError>;
+
+    async fn revoke(&self, login_member: LoginMember, member_id: Uuid, token_id: Uuid) -> Result<(), AppError>;
+
+    /// 요청의 Bearer 토큰으로 받은 개인 액세스 토큰을 검증하고 토큰 주인의 인증 정보를 만든다.
+    async fn authenticate(&self, token: &str) -> Result<LoginMember, ErrorCode>;
+}
+
+#[async_trait::async_trait]
+pub trait SessionRevokeUseCase: Interface {
+    async fn revoke(&self, login_member: LoginMember, member_id: Uuid, session_id: Uuid) -> Result<(), ErrorCode>;
+}
+
+#[derive(Debug)]
+pub struct LinkTicketDto {
+    pub ticket: String,
+    pub expires_in: i64,
+}
+
+#[async_trait::async_trait]
+pub trait OAuth2IdentityUseCase: Interface {
+    async fn link(&self, command: OAuth2LinkCommand) -> Result<IdentityDto, AppError>;
+
+    async fn get_identities(&self, login_member: LoginMember, member_id: Uuid) -> Result<Vec<IdentityDto>, AppError>;
+
+    /// 비밀번호도 없고 다른 연결된 계정도 없다면 로그인할 방법이 사라지므로 거부한다.
+    async fn unlink(&self, login_member: LoginMember, member_id: Uuid, provider: String) -> Result<(), AppError>;
+
+    /// 계정 연결 인가 요청에 실을 일회용 티켓을 발급한다.
+    /// 브라우저 이동에는 bearer token 이 실리지 않으므로 로그인한 상태에서 미리 받아 둔다.
+    async fn issue_link_ticket(&self, login_member: LoginMember) -> Result<LinkTicketDto, AppError>;
+
+    /// 티켓을 사용 처리하고 발급받은 회원 ID 를 반환한다.
+    async fn consume_link_ticket(&self, ticket: String) -> Result<Uuid, AppError>;
+}
+
+#[async_trait::async_trait]
+pub trait OAuth2TokenReencryptUseCase: Interface {
+    /// 키 교체 후 저장된 OAuth2 access token 을 활성 키로 다시 암호화하고, 바뀐 계정 수를 반환한다.
+    async fn reencrypt_all(&self) -> Result<u64, AppError>;
+}
+
+#[async_trait::async_trait]
+pub trait PasswordResetUseCase: Interface {
+    /// 가입 여부가 드러나지 않도록 없는 이메일이어도 성공으로 응답한다.
+    async fn request_reset(&self, email: String) -> Result<(), AppError>;
+
+    /// 비밀번호를 바꾸고 회원의 모든 로그인 세션을 폐기한다.
+    async fn confirm_reset(&self, token: String, new_password: String) -> Result<(), AppError>;
+}
+
+#[async_trait::async_trait]
+pub trait EmailVerificationUseCase: Interface {
+    async fn request_verification(&self, member_id: Uuid) -> Result<(), AppError>;
+
+    async fn confirm_verification(&self, token: String) -> Result<(), AppError>;
+}
+
+#[async_trait::async_trait]
+pub trait MfaUseCase: Interface {
+    /// 새 TOTP secret 을 만든다. 첫 인증 코드를 확인하기 전까지는 로그인에 적용되지 않는다.
+    async fn begin_totp_enrollment(&self, login_member: LoginMember, member_id: Uuid) -> Result<TotpEnrollmentDto, AppError>;
+
+    /// 인증 코드를 확인해 2단계 인증을 활성화하고 복구 코드를 반환한다. 복구 코드는 이때만 확인할 수 있다.
+    /// 틀린 코드는 로그인 실패와 함께 세어 잠금에 반영한다.
+    async fn confirm_totp_enrollment(&self, login_member: LoginMember, member_id: Uuid, code: String, client: ClientInfo) -> Result<Vec<String>, AppError>;
+
+    /// 인증 코드(또는 복구 코드)를 확인해 2단계 인증을 해제한다. 틀린 코드는 로그인 실패와 함께 세어 잠금에 반영한다.
+    async fn disable_totp(&self, login_member: LoginMember, member_id: Uuid, code: String, client: ClientInfo) -> Result<(), AppError>;
 }
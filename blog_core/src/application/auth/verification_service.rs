@@ -0,0 +1,353 @@
+use super::usecases::{EmailVerificationUseCase, PasswordResetUseCase};
+use crate::common::database::DbConnProvider;
+use crate::common::error::error_code::ErrorCode;
+use crate::common::jwt::JwtService;
+use crate::common::mailer::{Mail, Mailer};
+use crate::common::AppError;
+use crate::config::ConfigProvider;
+use crate::domain::member::refresh_token::repository::SaveRefreshTokenPort;
+use crate::domain::member::repository::{LoadMemberPort, SaveMemberPort};
+use crate::domain::member::session::repository::SaveSessionPort;
+use crate::domain::member::verification_token::entity::{VerificationPurpose, VerificationTokenEntity};
+use crate::domain::member::verification_token::repository::{LoadVerificationTokenPort, SaveVerificationTokenPort};
+use sea_orm::DatabaseTransaction;
+use shaku::Component;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// 비밀번호 재설정 링크 유효 시간
+const PASSWORD_RESET_TOKEN_MINUTES: i64 = 30;
+/// 이메일 인증 링크 유효 시간
+const EMAIL_VERIFICATION_TOKEN_HOURS: i64 = 24;
+
+#[derive(Component)]
+#[shaku(interface = PasswordResetUseCase)]
+pub struct PasswordResetUseCaseImpl {
+    #[shaku(inject)]
+    db: Arc<dyn DbConnProvider>,
+    #[shaku(inject)]
+    config: Arc<dyn ConfigProvider>,
+    #[shaku(inject)]
+    jwt_service: Arc<dyn JwtService>,
+    #[shaku(inject)]
+    mailer: Arc<dyn Mailer>,
+    #[shaku(inject)]
+    load_member_port: Arc<dyn LoadMemberPort>,
+    #[shaku(inject)]
+    save_member_port: Arc<dyn SaveMemberPort>,
+    #[shaku(inject)]
+    load_verification_token_port: Arc<dyn LoadVerificationTokenPort>,
+    #[shaku(inject)]
+    save_verification_token_port: Arc<dyn SaveVerificationTokenPort>,
+    #[shaku(inject)]
+    save_refresh_token_port: Arc<dyn SaveRefreshTokenPort>,
+    #[shaku(inject)]
+    save_session_port: Arc<dyn SaveSessionPort>,
+}
+
+#[derive(Component)]
+#[shaku(interface = EmailVerificationUseCase)]
+pub struct EmailVerificationUseCaseImpl {
+    #[shaku(inject)]
+    db: Arc<dyn DbConnProvider>,
+    #[shaku(inject)]
+    config: Arc<dyn ConfigProvider>,
+    #[shaku(inject)]
+    jwt_service: Arc<dyn JwtService>,
+    #[shaku(inject)]
+    mailer: Arc<dyn Mailer>,
+    #[shaku(inject)]
+    load_member_port: Arc<dyn LoadMemberPort>,
+    #[shaku(inject)]
+    save_member_port: Arc<dyn SaveMemberPort>,
+    #[shaku(inject)]
+    load_verification_token_port: Arc<dyn LoadVerificationTokenPort>,
+    #[shaku(inject)]
+    save_verification_token_port: Arc<dyn SaveVerificationTokenPort>,
+}
+
+/// 같은 용도로 이전에 보낸 토큰은 무효로 만들고 새 토큰을 발급한다.
+pub(super) async fn issue_token(
+    txn: &DatabaseTransaction,
+    jwt_service: &dyn JwtService,
+    save_verification_token_port: &dyn SaveVerificationTokenPort,
+    member_id: Uuid,
+    purpose: VerificationPurpose,
+    ttl: chrono::Duration,
+) -> Result<String, AppError> {
+    save_verification_token_port
+        .expire_unused(txn, member_id, purpose)
+        .await?;
+
+    let expires_at = chrono::Utc::now().naive_utc() + ttl;
+    let token = save_verification_token_port
+        .save(txn, VerificationTokenEntity::new(Uuid::new_v4(), member_id, purpose, expires_at, None, None))
+        .await?;
+
+    Ok(jwt_service.create_verification_token(member_id, token.get_id(), purpose.as_str(), expires_at)?)
+}
+
+/// 응답이 메일 서버를 기다리지 않도록 따로 보내고, 실패하면 기록만 남긴다.
+fn send_in_background(mailer: Arc<dyn Mailer>, mail: Mail) {
+    tokio::spawn(async move {
+        if let Err(e) = mailer.send(mail).await {
+            tracing::error!("Failed to send mail: {}", e.message);
+        }
+    });
+}
+
+/// 서명과 용도, 만료 여부를 확인하고 토큰을 사용 처리한다. 토큰을 발급받은 회원 ID 를 반환한다.
+pub(super) async fn consume_token(
+    txn: &DatabaseTransaction,
+    jwt_service: &dyn JwtService,
+    load_verification_token_port: &dyn LoadVerificationTokenPort,
+    save_verification_token_port: &dyn SaveVerificationTokenPort,
+    token: &str,
+    purpose: VerificationPurpose,
+) -> Result<Uuid, AppError> {
+    let invalid = || AppError::from(ErrorCode::InvalidVerificationToken);
+
+    let claims = jwt_service.decode_verification_token(token)?;
+    if claims.purpose != purpose.as_str() {
+        return Err(invalid());
+    }
+    let token_id = Uuid::parse_str(&claims.jti).map_err(|_| invalid())?;
+
+    let stored = load_verification_token_port
+        .find_by_id(txn, token_id)
+        .await?
+        .ok_or_else(invalid)?;
+    if !stored.is_usable(purpose, chrono::Utc::now().naive_utc())
+        || stored.get_member_id().to_string() != claims.sub
+    {
+        return Err(invalid());
+    }
+
+    if !save_verification_token_port.mark_used(txn, token_id).await? {
+        return Err(invalid());
+    }
+    Ok(stored.get_member_id())
+}
+
+#[async_trait::async_trait]
+impl PasswordResetUseCase for PasswordResetUseCaseImpl {
+    async fn request_reset(&self, email: String) -> Result<(), AppError> {
+        let txn = self.db.rw_txn().await?;
+        let Some(member) = self.load_member_port.find_by_email(&txn, &email).await? else {
+            tracing::info!("Password reset requested for unknown email");
+            return Ok(());
+        };
+
+        let token = issue_token(
+            &txn,
+            self.jwt_service.as_ref(),
+            self.save_verification_token_port.as_ref(),
+            member.id.unwrap(),
+            VerificationPurpose::PasswordReset,
+            chrono::Duration::minutes(PASSWORD_RESET_TOKEN_MINUTES),
+        )
+        .await?;
+        txn.commit().await?;
+
+        // 가입된 이메일인지 응답 시간이나 전송 실패로 드러나지 않도록 메일은 기다리지 않는다.
+        send_in_background(self.mailer.clone(), Mail {
+            to: member.email,
+            subject: "[dohoon-kim.kr] 비밀번호 재설정 안내".to_string(),
+            body: format!(
+                "아래 링크에서 {}분 안에 새 비밀번호를 설정해주세요.\n\n{}/password-reset?token={}\n\n요청하지 않았다면 이 메일을 무시하세요.",
+                PASSWORD_RESET_TOKEN_MINUTES,
+                self.config.get().web_origin,
+                token
+            ),
+        });
+        Ok(())
+    }
+
+    async fn confirm_reset(&self, token: String, new_password: String) -> Result<(), AppError> {
+        if new_password.is_empty() {
+            return Err(AppError::with_message(ErrorCode::BadRequest, "비밀번호를 입력해주세요."));
+        }
+
+        let txn = self.db.rw_txn().await?;
+        let member_id = consume_token(
+            &txn,
+            self.jwt_service.as_ref(),
+            self.load_verification_token_port.as_ref(),
+            self.save_verification_token_port.as_ref(),
+            &token,
+            VerificationPurpose::PasswordReset,
+        )
+        .await?;
+
+        let mut member = self
+            .load_member_port
+            .find_by_id(&txn, member_id)
+            .await?
+            .ok_or_else(|| AppError::from(ErrorCode::MemberNotFound))?;
+        member.password = bcrypt::hash(new_password, 10).unwrap();
+        member.has_password = true;
+        self.save_member_port.update(&txn, member).await?;
+
+        // 비밀번호가 유출되어 재설정하는 경우를 위해 기존 로그인은 모두 끊는다.
+        self.save_refresh_token_port
+            .revoke_all_by_member(&txn, member_id)
+            .await?;
+        self.save_session_port
+            .revoke_all_by_member(&txn, member_id)
+            .await?;
+        txn.commit().await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl EmailVerificationUseCase for EmailVerificationUseCaseImpl {
+    async fn request_verification(&self, member_id: Uuid) -> Result<(), AppError> {
+        let txn = self.db.rw_txn().await?;
+        let member = self
+            .load_member_port
+            .find_by_id(&txn, member_id)
+            .await?
+            .ok_or_else(|| AppError::from(ErrorCode::MemberNotFound))?;
+        if member.is_activated {
+            return Err(AppError::with_message(ErrorCode::Conflict, "이미 인증된 이메일입니다."));
+        }
+
+        let token = issue_token(
+            &txn,
+            self.jwt_service.as_ref(),
+            self.save_verification_token_port.as_ref(),
+            member_id,
+            VerificationPurpose::EmailVerification,
+            chrono::Duration::hours(EMAIL_VERIFICATION_TOKEN_HOURS),
+        )
+        .await?;
+        txn.commit().await?;
+
+        send_in_background(self.mailer.clone(), Mail {
+            to: member.email,
+            subject: "[dohoon-kim.kr] 이메일 인증 안내".to_string(),
+            body: format!(
+                "아래 링크를 눌러 이메일 인증을 완료해주세요. 링크는 {}시간 동안 유효합니다.\n\n{}/email-verification?token={}",
+                EMAIL_VERIFICATION_TOKEN_HOURS,
+                self.config.get().web_origin,
+                token
+            ),
+        });
+        Ok(())
+    }
+
+    async fn confirm_verification(&self, token: String) -> Result<(), AppError> {
+        let txn = self.db.rw_txn().await?;
+        let member_id = consume_token(
+            &txn,
+            self.jwt_service.as_ref(),
+            self.load_verification_token_port.as_ref(),
+            self.save_verification_token_port.as_ref(),
+            &token,
+            VerificationPurpose::EmailVerification,
+        )
+        .await?;
+
+        let mut member = self
+            .load_member_port
+            .find_by_id(&txn, member_id)
+            .await?
+            .ok_or_else(|| AppError::from(ErrorCode::MemberNotFound))?;
+        member.is_activated = true;
+        self.save_member_port.update(&txn, member).await?;
+        txn.commit().await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PasswordResetUseCaseImpl;
+    use crate::application::auth::PasswordResetUseCase;
+    use crate::common::database::DbConnProviderImpl;
+    use crate::common::jwt::{JwtService, JwtServiceImpl};
+    use crate::common::mailer::MailerImpl;
+    use crate::config::test_config;
+    use crate::domain::member::refresh_token::repository::SeaormSaveRefreshTokenAdapter;
+    use crate::domain::member::repository::{MemberCommandRepository, MemberQueryRepository};
+    use crate::domain::member::schema::Model as MemberModel;
+    use crate::domain::member::session::repository::SeaormSaveSessionAdapter;
+    use crate::domain::member::verification_token::repository::{
+        SeaormLoadVerificationTokenAdapter, SeaormSaveVerificationTokenAdapter,
+    };
+    use crate::domain::member::verification_token::schema::Model as VerificationTokenModel;
+    use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
+    use std::sync::Arc;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn request_reset_sends_single_use_token_test() {
+        let member_id = Uuid::new_v4();
+        let token_id = Uuid::new_v4();
+        let now = chrono::Utc::now().naive_utc();
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([vec![MemberModel {
+                id: member_id,
+                nickname: "member".to_owned(),
+                email: "member@dohoon-kim.kr".to_owned(),
+                password: "password".to_owned(),
+                role: "MEMBER".to_owned(),
+                created_at: now,
+                updated_at: None,
+                is_activated: true,
+                has_password: true,
+            }]])
+            .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 1 }])
+            .append_query_results([vec![VerificationTokenModel {
+                id: token_id,
+                member_id,
+                purpose: "PASSWORD_RESET".to_owned(),
+                expires_at: now + chrono::Duration::minutes(30),
+                created_at: now,
+                used_at: None,
+            }]])
+            .into_connection();
+
+        let config = test_config(&["--mail-transport", "memory"]);
+        let jwt_service = Arc::new(JwtServiceImpl { config: config.clone(), key_ring: Default::default() });
+        let mailer = Arc::new(MailerImpl { config: config.clone(), outbox: Default::default() });
+        let usecase = PasswordResetUseCaseImpl {
+            db: Arc::new(DbConnProviderImpl { db }),
+            config,
+            jwt_service: jwt_service.clone(),
+            mailer: mailer.clone(),
+            load_member_port: Arc::new(MemberQueryRepository {}),
+            save_member_port: Arc::new(MemberCommandRepository {}),
+            load_verification_token_port: Arc::new(SeaormLoadVerificationTokenAdapter {}),
+            save_verification_token_port: Arc::new(SeaormSaveVerificationTokenAdapter {}),
+            save_refresh_token_port: Arc::new(SeaormSaveRefreshTokenAdapter {}),
+            save_session_port: Arc::new(SeaormSaveSessionAdapter {}),
+        };
+
+        usecase
+            .request_reset("member@dohoon-kim.kr".to_owned())
+            .await
+            .ok()
+            .unwrap();
+
+        // 메일은 응답과 별도로 보낸다.
+        for _ in 0..10 {
+            if !mailer.sent_mails().is_empty() {
+                break;
+            }
+            tokio::task::yield_now().await;
+        }
+        let mails = mailer.sent_mails();
+        assert_eq!(mails.len(), 1);
+        assert_eq!(mails[0].to, "member@dohoon-kim.kr");
+
+        let token = mails[0].body.split("token=").nth(1).unwrap().lines().next().unwrap();
+        let claims = jwt_service.decode_verification_token(token).ok().unwrap();
+        assert_eq!(claims.jti, token_id.to_string());
+        assert_eq!(claims.sub, member_id.to_string());
+        assert_eq!(claims.purpose, "PASSWORD_RESET");
+    }
+}
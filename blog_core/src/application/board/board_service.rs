@@ -1,5 +1,5 @@
 use crate::{
-    common::{error_code::ErrorCode, AppError, DbConnProvider, LoginMember},
+    common::{error_code::ErrorCode, AppError, DbConnProvider, LoginMember, Permission},
     domain::board::{
         entity::command::board_entity::BoardEntity,
         repository::{LoadBoardPort, SaveBoardPort},
@@ -59,9 +59,9 @@ impl BoardCreateUsecase for BoardCreateUsecaseImpl {
         login_member: LoginMember,
         command: CreateBoardCommand,
     ) -> Result<BoardDto, AppError> {
-        if login_member.role != "ROLE_ADMIN" {
+        if !login_member.has_permission(Permission::BoardManage) {
             return Err(AppError::with_message(
-                ErrorCode::Forbidden,
+                ErrorCode::NotEnoughPermission,
                 "You are not authorized to create a board",
             ));
         }
@@ -82,9 +82,9 @@ impl BoardModifyUsecase for BoardModifyUsecaseImpl {
         id: i64,
         command: ModifyBoardCommand,
     ) -> Result<BoardDto, AppError> {
-        if login_member.role != "ROLE_ADMIN" {
+        if !login_member.has_permission(Permission::BoardManage) {
             return Err(AppError::with_message(
-                ErrorCode::Forbidden,
+                ErrorCode::NotEnoughPermission,
                 "You are not authorized to modify a board",
             ));
         }
@@ -111,9 +111,9 @@ impl BoardModifyUsecase for BoardModifyUsecaseImpl {
 #[async_trait::async_trait]
 impl BoardDeleteUsecase for BoardDeleteUsecaseImpl {
     async fn delete(&self, login_member: LoginMember, id: i64) -> Result<(), AppError> {
-        if login_member.role != "ROLE_ADMIN" {
+        if !login_member.has_permission(Permission::BoardManage) {
             return Err(AppError::with_message(
-                ErrorCode::Forbidden,
+                ErrorCode::NotEnoughPermission,
                 "You are not authorized to delete a board",
             ));
         }
@@ -0,0 +1,334 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use shaku::Component;
+use uuid::Uuid;
+use crate::common::error_code::ErrorCode;
+use crate::common::{AppError, DbConnProvider, LoginMember};
+use crate::domain::board::entity::command::comment_entity::CommentEntityBuilder;
+use crate::domain::board::entity::query::QCommentEntity;
+use crate::domain::board::repository::{LoadCommentPort, LoadPostPort, LoadReactionPort, SaveCommentPort};
+
+use super::reaction_service::find_comment_reactions;
+use super::{CommentCreateUsecase, CommentDeleteUsecase, CommentDto, CommentModifyUsecase, CommentQueryUsecase, CreateCommentCommand, ModifyCommentCommand, QCommentDto};
+
+#[derive(Component)]
+#[shaku( interface = CommentCreateUsecase )]
+pub struct CommentCreateUsecaseImpl {
+    #[shaku(inject)]
+    db: Arc<dyn DbConnProvider>,
+    #[shaku(inject)]
+    load_post_port: Arc<dyn LoadPostPort>,
+    #[shaku(inject)]
+    load_comment_port: Arc<dyn LoadCommentPort>,
+    #[shaku(inject)]
+    save_comment_port: Arc<dyn SaveCommentPort>,
+}
+
+#[derive(Component)]
+#[shaku( interface = CommentModifyUsecase )]
+pub struct CommentModifyUsecaseImpl {
+    #[shaku(inject)]
+    db: Arc<dyn DbConnProvider>,
+    #[shaku(inject)]
+    load_comment_port: Arc<dyn LoadCommentPort>,
+    #[shaku(inject)]
+    save_comment_port: Arc<dyn SaveCommentPort>,
+}
+
+#[derive(Component)]
+#[shaku( interface = CommentDeleteUsecase )]
+pub struct CommentDeleteUsecaseImpl {
+    #[shaku(inject)]
+    db: Arc<dyn DbConnProvider>,
+    #[shaku(inject)]
+    load_comment_port: Arc<dyn LoadCommentPort>,
+    #[shaku(inject)]
+    save_comment_port: Arc<dyn SaveCommentPort>,
+}
+
+#[derive(Component)]
+#[shaku( interface = CommentQueryUsecase )]
+pub struct CommentQueryUsecaseImpl {
+    #[shaku(inject)]
+    db: Arc<dyn DbConnProvider>,
+    #[shaku(inject)]
+    load_post_port: Arc<dyn LoadPostPort>,
+    #[shaku(inject)]
+    load_comment_port: Arc<dyn LoadCommentPort>,
+    #[shaku(inject)]
+    load_reaction_port: Arc<dyn LoadReactionPort>,
+}
+
+#[async_trait::async_trait]
+impl CommentCreateUsecase for CommentCreateUsecaseImpl {
+    async fn create(
+        &self,
+        login_member: LoginMember,
+        post_id: Uuid,
+        command: CreateCommentCommand,
+    ) -> Result<CommentDto, AppError> {
+        let new_comment = CommentEntityBuilder::default()
+            .id(None)
+            .post_id(post_id)
+            .member_id(login_member.id)
+            .parent_id(command.parent_id)
+            .contents(command.contents)
+            .created_at(chrono::Utc::now().naive_utc())
+            .build()
+            .map_err(|err| {
+                tracing::error!("Failed to build comment entity: {}", err);
+                AppError::with_message(ErrorCode::BadRequest, "Invalid comment entity")
+            })?;
+        new_comment.validate()?;
+
+        let txn = self.db.rw_txn().await?;
+
+        self.load_post_port.load_by_id(&txn, post_id)
+            .await
+            .filter(|post| post.is_visible_to(Some(login_member.id), chrono::Utc::now().naive_utc()))
+            .ok_or_else(|| {
+                AppError::with_message(ErrorCode::NotFound, "게시글을 찾을 수 없습니다.")
+            })?;
+
+        if let Some(parent_id) = command.parent_id {
+            let parent = self.load_comment_port.load_by_id(&txn, parent_id)
+                .await
+                .ok_or_else(|| {
+                    AppError::with_message(ErrorCode::NotFound, "답글을 작성할 댓글을 찾을 수 없습니다.")
+                })?;
+            parent.check_post(post_id)?;
+
+            if parent.is_deleted() {
+                return Err(AppError::with_message(
+                    ErrorCode::BadRequest,
+                    "삭제된 댓글에는 답글을 작성할 수 없습니다.",
+                ));
+            }
+        }
+
+        let comment = self.save_comment_port.save(&txn, new_comment).await?;
+        txn.commit().await?;
+
+        Ok(CommentDto::from(comment))
+    }
+}
+
+#[async_trait::async_trait]
+impl CommentModifyUsecase for CommentModifyUsecaseImpl {
+    async fn update(
+        &self,
+        login_member: LoginMember,
+        post_id: Uuid,
+        id: i64,
+        command: ModifyCommentCommand,
+    ) -> Result<CommentDto, AppError> {
+        let txn = self.db.rw_txn().await?;
+
+        let mut comment = self.load_comment_port.load_by_id(&txn, id)
+            .await
+            .ok_or_else(|| {
+                AppError::with_message(ErrorCode::NotFound, "댓글을 찾을 수 없습니다.")
+            })?;
+
+        comment.check_post(post_id)?;
+        comment.check_ownership(login_member.id)?;
+        comment.change_contents(command.contents)?;
+
+        let saved_comment = self.save_comment_port.update(&txn, comment).await?;
+        txn.commit().await?;
+
+        Ok(CommentDto::from(saved_comment))
+    }
+}
+
+#[async_trait::async_trait]
+impl CommentDeleteUsecase for CommentDeleteUsecaseImpl {
+    async fn delete(&self, login_member: LoginMember, post_id: Uuid, id: i64) -> Result<(), AppError> {
+        let txn = self.db.rw_txn().await?;
+
+        let mut comment = self.load_comment_port.load_by_id(&txn, id)
+            .await
+            .ok_or_else(|| {
+                AppError::with_message(ErrorCode::NotFound, "댓글을 찾을 수 없습니다.")
+            })?;
+
+        comment.check_post(post_id)?;
+        comment.check_ownership(login_member.id)?;
+
+        // 답글이 있는 댓글을 지우면 답글들이 최상위 댓글로 올라가므로 삭제 표시만 남긴다.
+        if self.load_comment_port.has_replies(&txn, id).await? {
+            comment.mark_deleted();
+            self.save_comment_port.update(&txn, comment).await?;
+        } else {
+            self.save_comment_port.delete(&txn, id).await?;
+        }
+        txn.commit().await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl CommentQueryUsecase for CommentQueryUsecaseImpl {
+    async fn get_comments(
+        &self,
+        viewer: Option<LoginMember>,
+        post_id: Uuid,
+        cursor: Option<i64>,
+        size: u64,
+    ) -> Result<Vec<QCommentDto>, AppError> {
+        let txn = self.db.ro_txn().await?;
+
+        // 작성자만 볼 수 있는 글(작성 중, 예약, 보관)의 댓글은 다른 사람에게 보여주지 않는다.
+        self.load_post_port.load_by_id(&txn, post_id)
+            .await
+            .filter(|post| post.is_visible_to(viewer.as_ref().map(|viewer| viewer.id), chrono::Utc::now().naive_utc()))
+            .ok_or_else(|| {
+                AppError::with_message(ErrorCode::NotFound, "게시글을 찾을 수 없습니다.")
+            })?;
+
+        let roots = self.load_comment_port.find_comments(&txn, post_id, cursor, size + 1)
+            .await
+            .unwrap_or_default();
+
+        // 다음 페이지 커서로만 쓰이는 마지막 댓글의 답글은 조회하지 않는다.
+        let parent_ids = roots.iter()
+            .take(size as usize)
+            .map(|comment| comment.id)
+            .collect::<Vec<i64>>();
+
+        let replies = self.load_comment_port.find_replies(&txn, parent_ids)
+            .await
+            .unwrap_or_default();
+        let mut reactions = find_comment_reactions(
+            self.load_reaction_port.as_ref(),
+            &txn,
+            roots.iter().chain(replies.iter()).map(|comment| comment.id).collect(),
+            viewer.map(|viewer| viewer.id),
+        ).await?;
+        txn.commit().await?;
+
+        let mut to_dto = |comment: QCommentEntity| {
+            let mut comment = QCommentDto::from(comment);
+            comment.reactions = reactions.remove(&comment.id).unwrap_or_default();
+            comment
+        };
+        let roots = roots.into_iter().map(&mut to_dto).collect();
+        let replies = replies.into_iter().map(&mut to_dto).collect();
+
+        Ok(build_comment_tree(roots, replies))
+    }
+}
+
+/// 평탄하게 조회된 답글 목록을 parent_id 기준으로 최상위 댓글 아래에 중첩시킨다.
+fn build_comment_tree(roots: Vec<QCommentDto>, replies: Vec<QCommentDto>) -> Vec<QCommentDto> {
+    let mut children: HashMap<i64, Vec<QCommentDto>> = HashMap::new();
+
+    for reply in replies {
+        if let Some(parent_id) = reply.parent_id {
+            children.entry(parent_id).or_default().push(reply);
+        }
+    }
+
+    roots.into_iter()
+        .map(|root| attach_children(root, &mut children))
+        .collect()
+}
+
+fn attach_children(mut node: QCommentDto, children: &mut HashMap<i64, Vec<QCommentDto>>) -> QCommentDto {
+    if let Some(nodes) = children.remove(&node.id) {
+        node.children = nodes.into_iter()
+            .map(|child| attach_children(child, children))
+            .collect();
+    }
+    node
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use sea_orm::{DatabaseBackend, MockDatabase};
+    use uuid::Uuid;
+
+    use super::{build_comment_tree, CommentQueryUsecaseImpl};
+    use crate::application::board::{CommentQueryUsecase, QCommentDto, WriterVo};
+    use crate::common::database::DbConnProviderImpl;
+    use crate::common::{LoginMember, SecurityRole};
+    use crate::domain::board::repository::{SeaOrmLoadCommentAdapter, SeaOrmLoadPostAdapter, SeaOrmLoadReactionAdapter};
+    use crate::domain::board::schema::post::Model as PostModel;
+
+    fn comment(id: i64, parent_id: Option<i64>) -> QCommentDto {
+        QCommentDto {
+            id,
+            parent_id,
+            writer: WriterVo { id: uuid::Uuid::nil(), name: "writer".to_owned() },
+            contents: Some(format!("comment-{}", id)),
+            reactions: Vec::new(),
+            is_deleted: false,
+            created_at: chrono::Utc::now().naive_utc(),
+            updated_at: None,
+            children: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn build_comment_tree_test() {
+        let roots = vec![comment(1, None), comment(2, None)];
+        let replies = vec![comment(3, Some(1)), comment(4, Some(3)), comment(5, Some(1)), comment(6, Some(2))];
+
+        let tree = build_comment_tree(roots, replies);
+
+        assert_eq!(tree.len(), 2);
+        assert_eq!(tree[0].children.iter().map(|c| c.id).collect::<Vec<i64>>(), vec![3, 5]);
+        assert_eq!(tree[0].children[0].children[0].id, 4);
+        assert_eq!(tree[1].children[0].id, 6);
+    }
+
+    #[tokio::test]
+    async fn hide_comments_of_draft_test() {
+        let author_id = Uuid::new_v4();
+        let draft = PostModel {
+            id: Uuid::new_v4(),
+            member_id: author_id,
+            category_id: 1,
+            title: "title".to_owned(),
+            contents: "contents".to_owned(),
+            excerpt_source: "contents".to_owned(),
+            contents_length: 8,
+            cover_image: None,
+            view_count: 0,
+            status: "DRAFT".to_owned(),
+            published_at: None,
+            created_at: chrono::Utc::now().naive_utc(),
+            updated_at: None,
+        };
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([vec![draft.clone()]])
+            .append_query_results([vec![draft.clone()]])
+            .into_connection();
+        let usecase = CommentQueryUsecaseImpl {
+            db: Arc::new(DbConnProviderImpl { db }),
+            load_post_port: Arc::new(SeaOrmLoadPostAdapter {}),
+            load_comment_port: Arc::new(SeaOrmLoadCommentAdapter {}),
+            load_reaction_port: Arc::new(SeaOrmLoadReactionAdapter {}),
+        };
+        let other = LoginMember {
+            id: Uuid::new_v4(),
+            nickname: "other".to_owned(),
+            email: "other@dohoon-kim.kr".to_owned(),
+            roles: vec![SecurityRole::Member],
+            is_activated: true,
+            session_id: None,
+            mfa_authenticated: false,
+            token_scopes: None,
+        };
+
+        for viewer in [None, Some(other)] {
+            let err = usecase.get_comments(viewer, draft.id, None, 20).await.err().unwrap();
+            assert_eq!(err.status, 404);
+            assert_eq!(err.message, "게시글을 찾을 수 없습니다.");
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use shaku::Component;
+
+use crate::common::error_code::ErrorCode;
+use crate::common::markdown::MarkdownRenderer;
+use crate::common::{AppError, DbConnProvider};
+use crate::config::ConfigProvider;
+use crate::domain::board::entity::command::tag_entity::TagEntity;
+use crate::domain::board::repository::{LoadBoardPort, LoadPostPort, LoadTagPort, PostListOrder, PostListScope};
+
+use super::post_service::post_excerpt;
+use super::tag_service::find_post_tags;
+use super::{FeedDto, FeedItemDto, FeedQueryUsecase};
+
+/// 피드 요약의 최대 글자 수
+const FEED_SUMMARY_LENGTH: usize = 300;
+
+#[derive(Component)]
+#[shaku( interface = FeedQueryUsecase )]
+pub struct FeedQueryUsecaseImpl{
+    #[shaku(inject)]
+    db: Arc<dyn DbConnProvider>,
+    #[shaku(inject)]
+    config: Arc<dyn ConfigProvider>,
+    #[shaku(inject)]
+    load_board_port: Arc<dyn LoadBoardPort>,
+    #[shaku(inject)]
+    load_post_port: Arc<dyn LoadPostPort>,
+    #[shaku(inject)]
+    load_tag_port: Arc<dyn LoadTagPort>,
+    #[shaku(inject)]
+    markdown_renderer: Arc<dyn MarkdownRenderer>,
+}
+
+#[async_trait::async_trait]
+impl FeedQueryUsecase for FeedQueryUsecaseImpl {
+    async fn get_feed(&self, category_id: Option<i64>, tag: Option<String>) -> Result<FeedDto, AppError> {
+        let config = self.config.get();
+        let tag = tag.map(|tag| TagEntity::normalize_name(&tag));
+
+        let txn = self.db.ro_txn().await?;
+        let scope_name = match (category_id, &tag) {
+            (Some(category_id), _) => {
+                let board = self.load_board_port.load_entity_by_id(&txn, category_id)
+                    .await
+                    .ok_or_else(|| {
+                        AppError::with_message(ErrorCode::NotFound, "존재하지 않는 게시판입니다.")
+                    })?;
+                Some(board.get_name())
+            }
+            (None, Some(tag)) => Some(tag.to_owned()),
+            (None, None) => None,
+        };
+        let posts = self.load_post_port
+            .find_posts(&txn, category_id, tag, PostListScope::Public, PostListOrder::Latest { cursor: None }, config.feed_size)
+            .await
+            .unwrap_or_default();
+        let mut tags = find_post_tags(
+            self.load_tag_port.as_ref(),
+            &txn,
+            posts.iter().map(|post| post.id).collect(),
+        ).await?;
+        // 목록 조회는 본문을 읽지 않으므로 본문 전체를 담을 때만 따로 가져온다.
+        let mut contents = if config.feed_full_content {
+            self.load_post_port.find_contents(&txn, posts.iter().map(|post| post.id).collect()).await?
+        } else {
+            Default::default()
+        };
+        txn.commit().await?;
+
+        let items = posts.into_iter()
+            .map(|post| {
+                let summary = post_excerpt(&post, FEED_SUMMARY_LENGTH);
+                // 원문을 그대로 담으면 피드 리더에서 스크립트가 실행될 수 있으므로 정리한 HTML 만 담는다.
+                let content = contents.remove(&post.id)
+                    .map(|contents| {
+                        self.markdown_renderer
+                            .render_post(post.id, post.updated_at.unwrap_or(post.created_at), &contents)
+                            .html
+                    });
+                let published_at = post.published_at.unwrap_or(post.created_at);
+                FeedItemDto {
+                    id: post.id,
+                    title: post.title,
+                    writer: post.writer,
+                    category: post.category,
+                    tags: tags.remove(&post.id).unwrap_or_default(),
+                    summary,
+                    content,
+                    published_at,
+                    updated_at: post.updated_at.map_or(published_at, |updated_at| updated_at.max(published_at)),
+                }
+            })
+            .collect();
+
+        Ok(FeedDto { scope_name, items })
+    }
+}
@@ -1,7 +1,21 @@
 pub mod board_service;
+pub mod comment_service;
+pub mod feed_service;
 pub mod usecases;
 pub mod post_service;
+pub mod post_revision_service;
+pub mod reaction_service;
+pub mod search_service;
+pub mod sitemap_service;
+pub mod tag_service;
 
 pub use board_service::*;
+pub use comment_service::*;
+pub use feed_service::*;
 pub use usecases::*;
 pub use post_service::*;
+pub use post_revision_service::*;
+pub use reaction_service::*;
+pub use search_service::*;
+pub use sitemap_service::*;
+pub use tag_service::*;
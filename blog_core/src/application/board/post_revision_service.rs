@@ -0,0 +1,200 @@
+use std::sync::Arc;
+
+use sea_orm::DatabaseTransaction;
+use shaku::Component;
+use uuid::Uuid;
+
+use crate::common::error_code::ErrorCode;
+use crate::common::{AppError, DbConnProvider, LoginMember};
+use crate::domain::board::entity::command::post_entity::PostEntity;
+use crate::domain::board::entity::command::post_revision_entity::PostRevisionEntity;
+use crate::domain::board::repository::{LoadBoardPort, LoadPostPort, LoadPostRevisionPort, LoadTagPort, SavePostPort, SavePostRevisionPort, SearchIndexPort};
+
+use super::search_service::indexed_document;
+use super::tag_service::find_post_tags;
+use super::{PostDto, PostRevisionDiffDto, PostRevisionDto, PostRevisionQueryUsecase, PostRevisionRestoreUsecase, QPostRevisionDto};
+
+/// 저장된 게시글의 현재 내용을 다음 번호의 리비전으로 남긴다.
+pub(crate) async fn append_revision(
+    load_post_revision_port: &dyn LoadPostRevisionPort,
+    save_post_revision_port: &dyn SavePostRevisionPort,
+    txn: &DatabaseTransaction,
+    post: &PostEntity,
+    member_id: Uuid,
+    restored_from: Option<i32>,
+) -> Result<PostRevisionEntity, AppError> {
+    let post_id = post.get_id().expect("게시글 ID가 없습니다.");
+    let revision_no = load_post_revision_port
+        .lock_latest_revision_no(txn, post_id)
+        .await?
+        .unwrap_or(0) + 1;
+    let revision = PostRevisionEntity::from_post(
+        post,
+        revision_no,
+        member_id,
+        restored_from,
+        chrono::Utc::now().naive_utc(),
+    );
+
+    Ok(save_post_revision_port.save(txn, revision).await?)
+}
+
+#[derive(Component)]
+#[shaku( interface = PostRevisionQueryUsecase )]
+pub struct PostRevisionQueryUsecaseImpl{
+    #[shaku(inject)]
+    db: Arc<dyn DbConnProvider>,
+    #[shaku(inject)]
+    load_post_port: Arc<dyn LoadPostPort>,
+    #[shaku(inject)]
+    load_post_revision_port: Arc<dyn LoadPostRevisionPort>,
+}
+
+#[derive(Component)]
+#[shaku( interface = PostRevisionRestoreUsecase )]
+pub struct PostRevisionRestoreUsecaseImpl{
+    #[shaku(inject)]
+    db: Arc<dyn DbConnProvider>,
+    #[shaku(inject)]
+    load_board_port: Arc<dyn LoadBoardPort>,
+    #[shaku(inject)]
+    load_post_port: Arc<dyn LoadPostPort>,
+    #[shaku(inject)]
+    save_post_port: Arc<dyn SavePostPort>,
+    #[shaku(inject)]
+    load_post_revision_port: Arc<dyn LoadPostRevisionPort>,
+    #[shaku(inject)]
+    save_post_revision_port: Arc<dyn SavePostRevisionPort>,
+    #[shaku(inject)]
+    load_tag_port: Arc<dyn LoadTagPort>,
+    #[shaku(inject)]
+    search_index_port: Arc<dyn SearchIndexPort>,
+}
+
+/// 게시글을 불러와 작성자인지 확인한다.
+async fn load_owned_post(
+    load_post_port: &dyn LoadPostPort,
+    txn: &DatabaseTransaction,
+    login_member: &LoginMember,
+    post_id: Uuid,
+) -> Result<PostEntity, AppError> {
+    let post = load_post_port.load_by_id(txn, post_id)
+        .await
+        .ok_or_else(|| {
+            AppError::with_message(ErrorCode::NotFound, "게시글을 찾을 수 없습니다.")
+        })?;
+    post.check_ownership(login_member.id)?;
+    Ok(post)
+}
+
+fn revision_not_found() -> AppError {
+    AppError::with_message(ErrorCode::NotFound, "리비전을 찾을 수 없습니다.")
+}
+
+#[async_trait::async_trait]
+impl PostRevisionQueryUsecase for PostRevisionQueryUsecaseImpl {
+    async fn get_revisions(
+        &self,
+        login_member: LoginMember,
+        post_id: Uuid,
+    ) -> Result<Vec<QPostRevisionDto>, AppError> {
+        let txn = self.db.ro_txn().await?;
+        load_owned_post(self.load_post_port.as_ref(), &txn, &login_member, post_id).await?;
+        let revisions = self.load_post_revision_port.find_by_post_id(&txn, post_id)
+            .await
+            .unwrap_or(Vec::new());
+        txn.commit().await?;
+
+        Ok(revisions.into_iter().map(QPostRevisionDto::from).collect())
+    }
+
+    async fn get_revision(
+        &self,
+        login_member: LoginMember,
+        post_id: Uuid,
+        revision_no: i32,
+    ) -> Result<PostRevisionDto, AppError> {
+        let txn = self.db.ro_txn().await?;
+        load_owned_post(self.load_post_port.as_ref(), &txn, &login_member, post_id).await?;
+        let revision = self.load_post_revision_port.load_by_revision_no(&txn, post_id, revision_no)
+            .await
+            .ok_or_else(revision_not_found)?;
+        txn.commit().await?;
+
+        Ok(PostRevisionDto::from(revision))
+    }
+
+    async fn diff(
+        &self,
+        login_member: LoginMember,
+        post_id: Uuid,
+        from_revision_no: i32,
+        to_revision_no: i32,
+    ) -> Result<PostRevisionDiffDto, AppError> {
+        let txn = self.db.ro_txn().await?;
+        load_owned_post(self.load_post_port.as_ref(), &txn, &login_member, post_id).await?;
+        let from = self.load_post_revision_port.load_by_revision_no(&txn, post_id, from_revision_no)
+            .await
+            .ok_or_else(revision_not_found)?;
+        let to = self.load_post_revision_port.load_by_revision_no(&txn, post_id, to_revision_no)
+            .await
+            .ok_or_else(revision_not_found)?;
+        txn.commit().await?;
+
+        Ok(PostRevisionDiffDto {
+            from_revision_no: from.get_revision_no(),
+            to_revision_no: to.get_revision_no(),
+            from_title: from.get_title(),
+            to_title: to.get_title(),
+            from_category_id: from.get_category_id(),
+            to_category_id: to.get_category_id(),
+            diff: from.diff(&to),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl PostRevisionRestoreUsecase for PostRevisionRestoreUsecaseImpl {
+    async fn restore(
+        &self,
+        login_member: LoginMember,
+        post_id: Uuid,
+        revision_no: i32,
+    ) -> Result<PostDto, AppError> {
+        let txn = self.db.rw_txn().await?;
+        let mut post = load_owned_post(self.load_post_port.as_ref(), &txn, &login_member, post_id).await?;
+        let revision = self.load_post_revision_port.load_by_revision_no(&txn, post_id, revision_no)
+            .await
+            .ok_or_else(revision_not_found)?;
+
+        self.load_board_port.load_entity_by_id(&txn, revision.get_category_id())
+            .await
+            .ok_or_else(|| {
+                AppError::with_message(ErrorCode::NotFound, "리비전의 게시판이 더 이상 존재하지 않습니다.")
+            })?;
+
+        post.change_title(revision.get_title());
+        post.change_contents(revision.get_contents());
+        post.change_category(revision.get_category_id());
+
+        let saved_post = self.save_post_port.update(&txn, post).await?;
+        append_revision(
+            self.load_post_revision_port.as_ref(),
+            self.save_post_revision_port.as_ref(),
+            &txn,
+            &saved_post,
+            login_member.id,
+            Some(revision_no),
+        ).await?;
+        let tags = find_post_tags(self.load_tag_port.as_ref(), &txn, vec![post_id])
+            .await?
+            .remove(&post_id)
+            .unwrap_or_default();
+        txn.commit().await?;
+        self.search_index_port.index(indexed_document(&saved_post)).await;
+
+        let mut post = PostDto::from(saved_post);
+        post.tags = tags;
+        Ok(post)
+    }
+}
@@ -3,11 +3,41 @@ use std::sync::Arc;
 use shaku::Component;
 use uuid::Uuid;
 use crate::common::error_code::ErrorCode;
-use crate::common::{AppError, DbConnProvider, LoginMember};
-use crate::domain::board::entity::command::post_entity::PostEntityBuilder;
-use crate::domain::board::repository::{LoadBoardPort, LoadPostPort, SavePostPort};
+use crate::common::markdown::{to_plain_text, MarkdownRenderer};
+use crate::common::view_counter::ViewCounter;
+use crate::common::{AppError, DbConnProvider, LoginMember, Permission};
+use crate::config::ConfigProvider;
+use crate::domain::board::entity::command::post_entity::{excerpt, PostEntityBuilder, PostStatus, EXCERPT_SOURCE_CHARS};
+use crate::domain::board::entity::command::tag_entity::TagEntity;
+use crate::domain::board::entity::query::QPostEntity;
+use crate::domain::board::repository::{LoadBoardPort, LoadPostPort, LoadPostRevisionPort, LoadReactionPort, LoadTagPort, PostListOrder, PostListScope, SavePostPort, SavePostRevisionPort, SaveTagPort, SearchIndexPort};
+
+use super::post_revision_service::append_revision;
+use super::reaction_service::find_post_reactions;
+use super::search_service::indexed_document;
+use super::tag_service::{assign_tags, find_post_tags};
+use super::{CreatePostCommand, ModifyPostCommand, PostCreateUsecase, PostDeleteUsecase, PostDto, PostModifyUsecase, PostPublishUsecase, PostQueryUsecase, PostViewFlushUsecase, QPostDto};
+
+/// 본문 앞부분에서 Markdown 을 걷어 내고 max_chars 글자까지 자른 요약
+pub(crate) fn post_excerpt(post: &QPostEntity, max_chars: usize) -> String {
+    let plain_text = to_plain_text(post.excerpt_source.as_deref().unwrap_or_default());
+    let mut summary = excerpt(&plain_text, max_chars);
+    // 본문 앞부분만 읽었으므로 요약이 짧아도 뒤에 글이 더 있을 수 있다.
+    let source_truncated = post.contents_length.is_some_and(|length| length > EXCERPT_SOURCE_CHARS);
+    if source_truncated && !summary.ends_with('…') {
+        summary.push('…');
+    }
+    summary
+}
 
-use super::{CreatePostCommand, ModifyPostCommand, PostCreateUsecase, PostDeleteUsecase, PostDto, PostModifyUsecase, PostQueryUsecase, QPostDto};
+/// 본문 Markdown 을 렌더링해 HTML 과 목차를 채운다.
+fn render_contents(markdown_renderer: &dyn MarkdownRenderer, post: &mut QPostDto) {
+    if let Some(contents) = &post.contents {
+        let rendered = markdown_renderer.render_post(post.id, post.updated_at.unwrap_or(post.created_at), contents);
+        post.contents_html = Some(rendered.html);
+        post.toc = rendered.toc;
+    }
+}
 
 
 
@@ -20,6 +50,14 @@ pub struct PostCreateUsecaseImpl{
     load_board_port: Arc<dyn LoadBoardPort>,
     #[shaku(inject)]
     save_post_port: Arc<dyn SavePostPort>,
+    #[shaku(inject)]
+    load_post_revision_port: Arc<dyn LoadPostRevisionPort>,
+    #[shaku(inject)]
+    save_post_revision_port: Arc<dyn SavePostRevisionPort>,
+    #[shaku(inject)]
+    save_tag_port: Arc<dyn SaveTagPort>,
+    #[shaku(inject)]
+    search_index_port: Arc<dyn SearchIndexPort>,
 }
 
 #[derive(Component)]
@@ -33,6 +71,16 @@ pub struct PostModifyUsecaseImpl{
     load_post_port: Arc<dyn LoadPostPort>,
     #[shaku(inject)]
     save_post_port: Arc<dyn SavePostPort>,
+    #[shaku(inject)]
+    load_post_revision_port: Arc<dyn LoadPostRevisionPort>,
+    #[shaku(inject)]
+    save_post_revision_port: Arc<dyn SavePostRevisionPort>,
+    #[shaku(inject)]
+    load_tag_port: Arc<dyn LoadTagPort>,
+    #[shaku(inject)]
+    save_tag_port: Arc<dyn SaveTagPort>,
+    #[shaku(inject)]
+    search_index_port: Arc<dyn SearchIndexPort>,
 }
 
 #[derive(Component)]
@@ -44,6 +92,8 @@ pub struct PostDeleteUsecaseImpl{
     load_post_port: Arc<dyn LoadPostPort>,
     #[shaku(inject)]
     save_post_port: Arc<dyn SavePostPort>,
+    #[shaku(inject)]
+    search_index_port: Arc<dyn SearchIndexPort>,
 }
 
 #[derive(Component)]
@@ -52,7 +102,37 @@ pub struct PostQueryUsecaseImpl{
     #[shaku(inject)]
     db: Arc<dyn DbConnProvider>,
     #[shaku(inject)]
+    config: Arc<dyn ConfigProvider>,
+    #[shaku(inject)]
     load_post_port: Arc<dyn LoadPostPort>,
+    #[shaku(inject)]
+    load_tag_port: Arc<dyn LoadTagPort>,
+    #[shaku(inject)]
+    load_reaction_port: Arc<dyn LoadReactionPort>,
+    #[shaku(inject)]
+    view_counter: Arc<dyn ViewCounter>,
+    #[shaku(inject)]
+    markdown_renderer: Arc<dyn MarkdownRenderer>,
+}
+
+#[derive(Component)]
+#[shaku( interface = PostPublishUsecase )]
+pub struct PostPublishUsecaseImpl{
+    #[shaku(inject)]
+    db: Arc<dyn DbConnProvider>,
+    #[shaku(inject)]
+    save_post_port: Arc<dyn SavePostPort>,
+}
+
+#[derive(Component)]
+#[shaku( interface = PostViewFlushUsecase )]
+pub struct PostViewFlushUsecaseImpl{
+    #[shaku(inject)]
+    db: Arc<dyn DbConnProvider>,
+    #[shaku(inject)]
+    save_post_port: Arc<dyn SavePostPort>,
+    #[shaku(inject)]
+    view_counter: Arc<dyn ViewCounter>,
 }
 
 #[async_trait::async_trait]
@@ -63,17 +143,20 @@ impl PostCreateUsecase for PostCreateUsecaseImpl {
         command: CreatePostCommand,
     ) -> Result<PostDto, AppError> {
 
-        if !login_member.is_admin() {
-            return Err(AppError::from(ErrorCode::Forbidden));
+        if !login_member.has_permission(Permission::PostWrite) {
+            return Err(AppError::from(ErrorCode::NotEnoughPermission));
         }
 
-        let new_post = PostEntityBuilder::default()
+        let now = chrono::Utc::now().naive_utc();
+        let mut new_post = PostEntityBuilder::default()
             .id(None)
             .member_id(login_member.id)
             .category_id(command.category_id)
             .title(command.title)
             .contents(command.contents)
-            .created_at(chrono::Utc::now().naive_utc())
+            .status(PostStatus::Draft)
+            .published_at(None)
+            .created_at(now)
             .view_count(0)
             .updated_at(None)
             .build()
@@ -84,6 +167,7 @@ impl PostCreateUsecase for PostCreateUsecaseImpl {
                     "Invalid post entity",
                 )
             })?;
+        new_post.change_status(command.status.unwrap_or(PostStatus::Published), command.publish_at, now)?;
 
         let txn = self.db.rw_txn().await?;
 
@@ -97,19 +181,30 @@ impl PostCreateUsecase for PostCreateUsecaseImpl {
             }
         };
 
-        let post = self.save_post_port.save(&txn, new_post).await;
-        txn.commit().await?;
-
-        match post {
-            Ok(post) => Ok(PostDto::from(post)),
-            Err(err) => {
+        let post = self.save_post_port.save(&txn, new_post)
+            .await
+            .map_err(|err| {
                 tracing::error!("Failed to save post: {}", err);
-                Err(AppError::with_message(
+                AppError::with_message(
                     ErrorCode::InternalServerError,
                     "Failed to save post",
-                ))
-            }
-        }
+                )
+            })?;
+        append_revision(
+            self.load_post_revision_port.as_ref(),
+            self.save_post_revision_port.as_ref(),
+            &txn,
+            &post,
+            login_member.id,
+            None,
+        ).await?;
+        let tags = assign_tags(self.save_tag_port.as_ref(), &txn, post.get_id().expect("게시글 ID가 없습니다."), command.tags).await?;
+        txn.commit().await?;
+        self.search_index_port.index(indexed_document(&post)).await;
+
+        let mut post = PostDto::from(post);
+        post.tags = tags;
+        Ok(post)
     }
 }
 
@@ -140,12 +235,33 @@ impl PostModifyUsecase for PostModifyUsecaseImpl {
         post.change_title(command.title);
         post.change_contents(command.contents);
         post.change_category(new_board.get_id().expect("게시판 ID가 없습니다."));
+        if let Some(status) = command.status {
+            post.change_status(status, command.publish_at, chrono::Utc::now().naive_utc())?;
+        }
 
         let saved_post = self.save_post_port.update(&txn, post)
             .await?;
+        append_revision(
+            self.load_post_revision_port.as_ref(),
+            self.save_post_revision_port.as_ref(),
+            &txn,
+            &saved_post,
+            login_member.id,
+            None,
+        ).await?;
+        let tags = match command.tags {
+            Some(tags) => assign_tags(self.save_tag_port.as_ref(), &txn, id, tags).await?,
+            None => find_post_tags(self.load_tag_port.as_ref(), &txn, vec![id])
+                .await?
+                .remove(&id)
+                .unwrap_or_default(),
+        };
         txn.commit().await?;
+        self.search_index_port.index(indexed_document(&saved_post)).await;
 
-        Ok(PostDto::from(saved_post))
+        let mut post = PostDto::from(saved_post);
+        post.tags = tags;
+        Ok(post)
     }
 }
 
@@ -169,6 +285,7 @@ impl PostDeleteUsecase for PostDeleteUsecaseImpl {
         self.save_post_port.delete(&txn, id)
             .await?;
         txn.commit().await?;
+        self.search_index_port.remove(id).await;
         Ok(())
     }
 } 
@@ -177,38 +294,135 @@ impl PostDeleteUsecase for PostDeleteUsecaseImpl {
 impl PostQueryUsecase for PostQueryUsecaseImpl {
     async fn get_posts(
         &self,
+        viewer: Option<LoginMember>,
         category_id: Option<i64>,
-        cursor: Option<chrono::NaiveDateTime>,
+        tag: Option<String>,
+        status: Option<PostStatus>,
+        order: PostListOrder,
         size: u64,
     ) -> Result<Vec<QPostDto>, AppError> {
+        let viewer_id = viewer.as_ref().map(|viewer| viewer.id);
+        let scope = match status {
+            None | Some(PostStatus::Published) => PostListScope::Public,
+            Some(status) => {
+                let viewer = viewer.ok_or_else(|| {
+                    AppError::with_message(ErrorCode::Unauthorized, "발행되지 않은 게시글은 로그인한 작성자만 조회할 수 있습니다.")
+                })?;
+                PostListScope::Author { member_id: viewer.id, status }
+            }
+        };
+
+        let tag = tag.map(|tag| TagEntity::normalize_name(&tag));
+
         let txn = self.db.ro_txn().await?;
-        let posts = self.load_post_port.find_posts(&txn, category_id, cursor, size+1)
+        let posts = self.load_post_port.find_posts(&txn, category_id, tag, scope, order, size+1)
             .await
             .unwrap_or(Vec::new());
+        let mut tags = find_post_tags(
+            self.load_tag_port.as_ref(),
+            &txn,
+            posts.iter().map(|post| post.id).collect(),
+        ).await?;
+        let mut reactions = find_post_reactions(
+            self.load_reaction_port.as_ref(),
+            &txn,
+            posts.iter().map(|post| post.id).collect(),
+            viewer_id,
+        ).await?;
         txn.commit().await?;
 
+        let excerpt_length = self.config.get().excerpt_length;
         let posts = posts.into_iter()
             .map(|post| {
-                QPostDto::from(post)
+                let post_tags = tags.remove(&post.id).unwrap_or_default();
+                let post_reactions = reactions.remove(&post.id).unwrap_or_default();
+                let post_excerpt = post_excerpt(&post, excerpt_length);
+                let mut post = QPostDto::from(post);
+                post.tags = post_tags;
+                post.reactions = post_reactions;
+                post.excerpt = post_excerpt;
+                post
             })
             .collect();
 
         Ok(posts)
     }
 
-    async fn get_post(&self, id: Uuid) -> Result<QPostDto, AppError> {
+    async fn get_post(&self, viewer: Option<LoginMember>, visitor: Option<String>, id: Uuid) -> Result<QPostDto, AppError> {
         let txn = self.db.ro_txn().await?;
         let post = self.load_post_port.find_by_id(&txn, id)
             .await;
+        let tags = find_post_tags(self.load_tag_port.as_ref(), &txn, vec![id])
+            .await?
+            .remove(&id)
+            .unwrap_or_default();
+        let reactions = find_post_reactions(self.load_reaction_port.as_ref(), &txn, vec![id], viewer.as_ref().map(|viewer| viewer.id))
+            .await?
+            .remove(&id)
+            .unwrap_or_default();
         txn.commit().await?;
 
-        if post.is_none() {
-            return Err(AppError::with_message(
+        let now = chrono::Utc::now().naive_utc();
+        let post = post.and_then(|post| {
+            let is_public = PostStatus::from_str(&post.status)
+                .is_some_and(|status| status.is_public(post.published_at, now));
+            let is_writer = viewer.as_ref().is_some_and(|viewer| viewer.id == post.writer.id);
+            (is_public || is_writer).then_some((post, is_public && !is_writer))
+        });
+
+        match post {
+            Some((post, countable)) => {
+                if countable {
+                    self.view_counter.record(id, visitor.as_deref());
+                }
+                let post_excerpt = post_excerpt(&post, self.config.get().excerpt_length);
+                let mut post = QPostDto::from(post);
+                post.tags = tags;
+                post.reactions = reactions;
+                post.excerpt = post_excerpt;
+                post.view_count += self.view_counter.pending(id);
+                render_contents(self.markdown_renderer.as_ref(), &mut post);
+                Ok(post)
+            }
+            None => Err(AppError::with_message(
                 ErrorCode::NotFound,
                 "게시글을 찾을 수 없습니다.",
-            ));
+            )),
         }
-        Ok(QPostDto::from(post.unwrap()))
     }
 }
 
+#[async_trait::async_trait]
+impl PostPublishUsecase for PostPublishUsecaseImpl {
+    async fn publish_due_posts(&self) -> Result<u64, AppError> {
+        let txn = self.db.rw_txn().await?;
+        let count = self.save_post_port.publish_due(&txn, chrono::Utc::now().naive_utc()).await?;
+        txn.commit().await?;
+
+        Ok(count)
+    }
+}
+
+#[async_trait::async_trait]
+impl PostViewFlushUsecase for PostViewFlushUsecaseImpl {
+    async fn flush_view_counts(&self) -> Result<u64, AppError> {
+        self.view_counter.prune_visits();
+        let pending = self.view_counter.take_pending();
+        if pending.is_empty() {
+            return Ok(0);
+        }
+
+        let result = async {
+            let txn = self.db.rw_txn().await?;
+            let count = self.save_post_port.increase_view_counts(&txn, &pending).await?;
+            txn.commit().await?;
+            Ok::<u64, AppError>(count)
+        }.await;
+
+        if result.is_err() {
+            // 반영하지 못한 조회수는 다음 주기에 다시 반영한다.
+            self.view_counter.restore_pending(pending);
+        }
+        result
+    }
+}
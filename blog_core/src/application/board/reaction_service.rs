@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use sea_orm::DatabaseTransaction;
+use shaku::Component;
+use uuid::Uuid;
+
+use crate::common::error_code::ErrorCode;
+use crate::common::{AppError, DbConnProvider, LoginMember};
+use crate::domain::board::entity::command::reaction_entity::{ReactionEntity, ReactionKind, ReactionTarget};
+use crate::domain::board::entity::query::QReactionCountEntity;
+use crate::domain::board::repository::{LoadCommentPort, LoadPostPort, LoadReactionPort, SaveReactionPort};
+
+use super::{ReactionCountVo, ReactionToggleUsecase};
+
+impl From<QReactionCountEntity> for ReactionCountVo {
+    fn from(entity: QReactionCountEntity) -> Self {
+        ReactionCountVo {
+            kind: entity.kind,
+            count: entity.count,
+            reacted_by_me: entity.reacted,
+        }
+    }
+}
+
+/// 여러 게시글의 반응 수를 게시글 ID 별로 모은다.
+pub(crate) async fn find_post_reactions(
+    load_reaction_port: &dyn LoadReactionPort,
+    txn: &DatabaseTransaction,
+    post_ids: Vec<Uuid>,
+    viewer: Option<Uuid>,
+) -> Result<HashMap<Uuid, Vec<ReactionCountVo>>, AppError> {
+    let mut reactions: HashMap<Uuid, Vec<ReactionCountVo>> = HashMap::new();
+    for reaction in load_reaction_port.find_post_reactions(txn, post_ids, viewer).await? {
+        reactions.entry(reaction.post_id).or_default().push(ReactionCountVo::from(reaction));
+    }
+    Ok(reactions)
+}
+
+/// 여러 댓글의 반응 수를 댓글 ID 별로 모은다.
+pub(crate) async fn find_comment_reactions(
+    load_reaction_port: &dyn LoadReactionPort,
+    txn: &DatabaseTransaction,
+    comment_ids: Vec<i64>,
+    viewer: Option<Uuid>,
+) -> Result<HashMap<i64, Vec<ReactionCountVo>>, AppError> {
+    let mut reactions: HashMap<i64, Vec<ReactionCountVo>> = HashMap::new();
+    for reaction in load_reaction_port.find_comment_reactions(txn, comment_ids, viewer).await? {
+        if let Some(comment_id) = reaction.comment_id {
+            reactions.entry(comment_id).or_default().push(ReactionCountVo::from(reaction));
+        }
+    }
+    Ok(reactions)
+}
+
+#[derive(Component)]
+#[shaku( interface = ReactionToggleUsecase )]
+pub struct ReactionToggleUsecaseImpl{
+    #[shaku(inject)]
+    db: Arc<dyn DbConnProvider>,
+    #[shaku(inject)]
+    load_post_port: Arc<dyn LoadPostPort>,
+    #[shaku(inject)]
+    load_comment_port: Arc<dyn LoadCommentPort>,
+    #[shaku(inject)]
+    load_reaction_port: Arc<dyn LoadReactionPort>,
+    #[shaku(inject)]
+    save_reaction_port: Arc<dyn SaveReactionPort>,
+}
+
+impl ReactionToggleUsecaseImpl {
+    /// 반응 대상 게시글은 로그인한 회원이 볼 수 있는 글이어야 한다.
+    async fn check_post_visible(&self, txn: &DatabaseTransaction, login_member: &LoginMember, post_id: Uuid) -> Result<(), AppError> {
+        self.load_post_port.load_by_id(txn, post_id)
+            .await
+            .filter(|post| post.is_visible_to(Some(login_member.id), chrono::Utc::now().naive_utc()))
+            .ok_or_else(|| {
+                AppError::with_message(ErrorCode::NotFound, "게시글을 찾을 수 없습니다.")
+            })?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl ReactionToggleUsecase for ReactionToggleUsecaseImpl {
+    async fn toggle_post_reaction(
+        &self,
+        login_member: LoginMember,
+        post_id: Uuid,
+        kind: ReactionKind,
+    ) -> Result<Vec<ReactionCountVo>, AppError> {
+        let txn = self.db.rw_txn().await?;
+        self.check_post_visible(&txn, &login_member, post_id).await?;
+
+        self.save_reaction_port
+            .toggle(&txn, ReactionEntity::new(None, login_member.id, ReactionTarget::Post(post_id), kind, None))
+            .await?;
+        let reactions = find_post_reactions(self.load_reaction_port.as_ref(), &txn, vec![post_id], Some(login_member.id))
+            .await?
+            .remove(&post_id)
+            .unwrap_or_default();
+        txn.commit().await?;
+
+        Ok(reactions)
+    }
+
+    async fn toggle_comment_reaction(
+        &self,
+        login_member: LoginMember,
+        post_id: Uuid,
+        comment_id: i64,
+        kind: ReactionKind,
+    ) -> Result<Vec<ReactionCountVo>, AppError> {
+        let txn = self.db.rw_txn().await?;
+        self.check_post_visible(&txn, &login_member, post_id).await?;
+
+        let comment = self.load_comment_port.load_by_id(&txn, comment_id)
+            .await
+            .ok_or_else(|| {
+                AppError::with_message(ErrorCode::NotFound, "댓글을 찾을 수 없습니다.")
+            })?;
+        comment.check_post(post_id)?;
+        if comment.is_deleted() {
+            return Err(AppError::with_message(ErrorCode::BadRequest, "삭제된 댓글에는 반응할 수 없습니다."));
+        }
+
+        self.save_reaction_port
+            .toggle(&txn, ReactionEntity::new(None, login_member.id, ReactionTarget::Comment { post_id, comment_id }, kind, None))
+            .await?;
+        let reactions = find_comment_reactions(self.load_reaction_port.as_ref(), &txn, vec![comment_id], Some(login_member.id))
+            .await?
+            .remove(&comment_id)
+            .unwrap_or_default();
+        txn.commit().await?;
+
+        Ok(reactions)
+    }
+}
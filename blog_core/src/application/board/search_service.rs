@@ -0,0 +1,141 @@
+use std::sync::Arc;
+
+use shaku::Component;
+
+use crate::common::error_code::ErrorCode;
+use crate::common::ngram_index::IndexedDocument;
+use crate::common::{AppError, DbConnProvider};
+use crate::domain::board::entity::command::post_entity::PostEntity;
+use crate::domain::board::entity::command::tag_entity::TagEntity;
+use crate::domain::board::entity::query::QPostSearchEntity;
+use crate::domain::board::repository::{PostSearchQuery, SearchIndexPort, HIGHLIGHT_START, HIGHLIGHT_STOP};
+
+use super::{PostSearchUsecase, QPostSearchDto, SearchIndexFlushUsecase, SearchIndexRebuildUsecase, SearchPostCommand};
+
+/// 검색어 최대 길이(글자 수)
+const MAX_KEYWORD_LENGTH: usize = 100;
+
+/// 원문을 HTML 이스케이프하고, 하이라이트 구분 문자를 `<mark>` 태그로 바꾼다.
+fn highlight_html(text: &str) -> String {
+    let mut html = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            HIGHLIGHT_START => html.push_str("<mark>"),
+            HIGHLIGHT_STOP => html.push_str("</mark>"),
+            '&' => html.push_str("&amp;"),
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            '"' => html.push_str("&quot;"),
+            '\'' => html.push_str("&#39;"),
+            c => html.push(c),
+        }
+    }
+    html
+}
+
+/// 저장된 게시글을 검색 색인에 넣을 문서로 바꾼다.
+pub(crate) fn indexed_document(post: &PostEntity) -> IndexedDocument {
+    IndexedDocument {
+        id: post.get_id().expect("게시글 ID가 없습니다."),
+        title: post.get_title(),
+        contents: post.get_contents(),
+    }
+}
+
+impl From<QPostSearchEntity> for QPostSearchDto {
+    fn from(entity: QPostSearchEntity) -> Self {
+        QPostSearchDto {
+            id: entity.id,
+            writer: entity.writer,
+            category: entity.category,
+            title: entity.title,
+            title_highlight: highlight_html(&entity.title_highlight),
+            snippet: highlight_html(&entity.snippet),
+            rank: entity.rank,
+            published_at: entity.published_at,
+        }
+    }
+}
+
+#[derive(Component)]
+#[shaku( interface = PostSearchUsecase )]
+pub struct PostSearchUsecaseImpl{
+    #[shaku(inject)]
+    db: Arc<dyn DbConnProvider>,
+    #[shaku(inject)]
+    search_index_port: Arc<dyn SearchIndexPort>,
+}
+
+#[async_trait::async_trait]
+impl PostSearchUsecase for PostSearchUsecaseImpl {
+    async fn search(&self, command: SearchPostCommand) -> Result<Vec<QPostSearchDto>, AppError> {
+        let keyword = command.keyword.trim().to_owned();
+        if keyword.is_empty() || keyword.chars().count() > MAX_KEYWORD_LENGTH {
+            return Err(AppError::with_message(
+                ErrorCode::BadRequest,
+                "검색어는 1자 이상 100자 이하로 입력해야 합니다.",
+            ));
+        }
+
+        let txn = self.db.ro_txn().await?;
+        let posts = self.search_index_port
+            .search(&txn, PostSearchQuery {
+                keyword,
+                category_id: command.category_id,
+                tag: command.tag.map(|tag| TagEntity::normalize_name(&tag)),
+                author_id: command.author_id,
+                offset: command.offset,
+                size: command.size,
+            })
+            .await?;
+        txn.commit().await?;
+
+        Ok(posts.into_iter().map(QPostSearchDto::from).collect())
+    }
+}
+
+#[derive(Component)]
+#[shaku( interface = SearchIndexRebuildUsecase )]
+pub struct SearchIndexRebuildUsecaseImpl{
+    #[shaku(inject)]
+    db: Arc<dyn DbConnProvider>,
+    #[shaku(inject)]
+    search_index_port: Arc<dyn SearchIndexPort>,
+}
+
+#[async_trait::async_trait]
+impl SearchIndexRebuildUsecase for SearchIndexRebuildUsecaseImpl {
+    async fn rebuild(&self) -> Result<u64, AppError> {
+        let txn = self.db.ro_txn().await?;
+        let count = self.search_index_port.rebuild(&txn).await?;
+        txn.commit().await?;
+
+        Ok(count)
+    }
+}
+
+#[derive(Component)]
+#[shaku( interface = SearchIndexFlushUsecase )]
+pub struct SearchIndexFlushUsecaseImpl{
+    #[shaku(inject)]
+    search_index_port: Arc<dyn SearchIndexPort>,
+}
+
+#[async_trait::async_trait]
+impl SearchIndexFlushUsecase for SearchIndexFlushUsecaseImpl {
+    async fn flush_search_index(&self) -> Result<bool, AppError> {
+        Ok(self.search_index_port.flush().await?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::highlight_html;
+    use crate::domain::board::repository::{HIGHLIGHT_START, HIGHLIGHT_STOP};
+
+    #[test]
+    fn highlight_html_test() {
+        let text = format!("<b>{}러스트{}</b> & co", HIGHLIGHT_START, HIGHLIGHT_STOP);
+        assert_eq!(highlight_html(&text), "&lt;b&gt;<mark>러스트</mark>&lt;/b&gt; &amp; co");
+    }
+}
@@ -0,0 +1,131 @@
+use std::sync::Arc;
+
+use shaku::Component;
+
+use crate::common::error_code::ErrorCode;
+use crate::common::{AppError, DbConnProvider};
+use crate::domain::board::entity::query::{QBoardSitemapEntity, QPostSitemapEntity};
+use crate::domain::board::repository::{LoadBoardPort, LoadPostPort};
+
+use super::{SitemapDto, SitemapEntryDto, SitemapQueryUsecase};
+
+/// 사이트맵 하나에 실을 수 있는 URL 수 (sitemaps.org 프로토콜 제한)
+pub const SITEMAP_MAX_URLS: u64 = 50_000;
+
+impl From<QBoardSitemapEntity> for SitemapEntryDto {
+    fn from(board: QBoardSitemapEntity) -> Self {
+        SitemapEntryDto {
+            path: format!("/categories/{}", board.id),
+            last_modified: board.last_modified,
+        }
+    }
+}
+
+impl From<QPostSitemapEntity> for SitemapEntryDto {
+    fn from(post: QPostSitemapEntity) -> Self {
+        SitemapEntryDto {
+            path: format!("/posts/{}", post.id),
+            last_modified: post.last_modified,
+        }
+    }
+}
+
+/// 게시글 사이트맵 수. 사이트 첫 화면, 게시판, 게시글이 사이트맵 하나에 다 들어가면 None 이다.
+fn post_sitemap_pages(board_count: u64, post_count: u64) -> Option<u64> {
+    if 1 + board_count + post_count <= SITEMAP_MAX_URLS {
+        None
+    } else {
+        Some(post_count.div_ceil(SITEMAP_MAX_URLS))
+    }
+}
+
+#[derive(Component)]
+#[shaku( interface = SitemapQueryUsecase )]
+pub struct SitemapQueryUsecaseImpl{
+    #[shaku(inject)]
+    db: Arc<dyn DbConnProvider>,
+    #[shaku(inject)]
+    load_board_port: Arc<dyn LoadBoardPort>,
+    #[shaku(inject)]
+    load_post_port: Arc<dyn LoadPostPort>,
+}
+
+#[async_trait::async_trait]
+impl SitemapQueryUsecase for SitemapQueryUsecaseImpl {
+    async fn get_sitemap(&self) -> Result<SitemapDto, AppError> {
+        let txn = self.db.ro_txn().await?;
+        let boards: Vec<SitemapEntryDto> = self.load_board_port.find_sitemap_boards(&txn)
+            .await?
+            .into_iter()
+            .map(SitemapEntryDto::from)
+            .collect();
+        let post_count = self.load_post_port.count_public_posts(&txn).await?;
+        let last_modified = boards.iter().filter_map(|board| board.last_modified).max();
+
+        let sitemap = match post_sitemap_pages(boards.len() as u64, post_count) {
+            None => {
+                let posts = self.load_post_port.find_sitemap_posts(&txn, 0, SITEMAP_MAX_URLS).await?;
+                let mut entries = vec![SitemapEntryDto { path: "/".to_owned(), last_modified }];
+                entries.extend(boards);
+                entries.extend(posts.into_iter().map(SitemapEntryDto::from));
+                SitemapDto::UrlSet(entries)
+            }
+            Some(pages) => {
+                let mut sitemaps = vec![SitemapEntryDto { path: "/sitemaps/boards.xml".to_owned(), last_modified }];
+                sitemaps.extend((1..=pages).map(|page| SitemapEntryDto {
+                    path: format!("/sitemaps/posts/{}.xml", page),
+                    last_modified: None,
+                }));
+                SitemapDto::Index(sitemaps)
+            }
+        };
+        txn.commit().await?;
+
+        Ok(sitemap)
+    }
+
+    async fn get_board_sitemap(&self) -> Result<Vec<SitemapEntryDto>, AppError> {
+        let txn = self.db.ro_txn().await?;
+        let boards: Vec<SitemapEntryDto> = self.load_board_port.find_sitemap_boards(&txn)
+            .await?
+            .into_iter()
+            .map(SitemapEntryDto::from)
+            .collect();
+        txn.commit().await?;
+
+        let last_modified = boards.iter().filter_map(|board| board.last_modified).max();
+        let mut entries = vec![SitemapEntryDto { path: "/".to_owned(), last_modified }];
+        entries.extend(boards);
+        Ok(entries)
+    }
+
+    async fn get_post_sitemap(&self, page: u64) -> Result<Vec<SitemapEntryDto>, AppError> {
+        if page == 0 {
+            return Err(AppError::with_message(ErrorCode::NotFound, "존재하지 않는 사이트맵입니다."));
+        }
+
+        let txn = self.db.ro_txn().await?;
+        let posts = self.load_post_port
+            .find_sitemap_posts(&txn, (page - 1) * SITEMAP_MAX_URLS, SITEMAP_MAX_URLS)
+            .await?;
+        txn.commit().await?;
+
+        if posts.is_empty() && page > 1 {
+            return Err(AppError::with_message(ErrorCode::NotFound, "존재하지 않는 사이트맵입니다."));
+        }
+        Ok(posts.into_iter().map(SitemapEntryDto::from).collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{post_sitemap_pages, SITEMAP_MAX_URLS};
+
+    #[test]
+    fn post_sitemap_pages_test() {
+        // 첫 화면과 게시판까지 한 사이트맵에 들어가면 나누지 않는다.
+        assert_eq!(post_sitemap_pages(9, SITEMAP_MAX_URLS - 10), None);
+        assert_eq!(post_sitemap_pages(10, SITEMAP_MAX_URLS - 10), Some(1));
+        assert_eq!(post_sitemap_pages(3, SITEMAP_MAX_URLS * 2 + 1), Some(3));
+    }
+}
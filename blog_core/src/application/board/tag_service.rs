@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use sea_orm::DatabaseTransaction;
+use shaku::Component;
+use uuid::Uuid;
+
+use crate::common::{AppError, DbConnProvider};
+use crate::domain::board::entity::command::tag_entity::TagEntity;
+use crate::domain::board::repository::{LoadTagPort, SaveTagPort};
+
+use super::{QTagDto, TagQueryUsecase};
+
+/// 게시글의 태그를 주어진 이름들로 바꾸고, 저장된 태그 이름을 이름순으로 돌려준다.
+pub(crate) async fn assign_tags(
+    save_tag_port: &dyn SaveTagPort,
+    txn: &DatabaseTransaction,
+    post_id: Uuid,
+    names: Vec<String>,
+) -> Result<Vec<String>, AppError> {
+    let names = TagEntity::normalize_names(names)?;
+    let tags = save_tag_port.save_all(txn, names).await?;
+    save_tag_port
+        .replace_post_tags(txn, post_id, tags.iter().filter_map(|tag| tag.get_id()).collect())
+        .await?;
+
+    let mut names: Vec<String> = tags.iter().map(|tag| tag.get_name()).collect();
+    names.sort();
+    Ok(names)
+}
+
+/// 여러 게시글의 태그 이름을 게시글 ID 별로 모은다.
+pub(crate) async fn find_post_tags(
+    load_tag_port: &dyn LoadTagPort,
+    txn: &DatabaseTransaction,
+    post_ids: Vec<Uuid>,
+) -> Result<HashMap<Uuid, Vec<String>>, AppError> {
+    let mut tags: HashMap<Uuid, Vec<String>> = HashMap::new();
+    for post_tag in load_tag_port.find_by_post_ids(txn, post_ids).await? {
+        tags.entry(post_tag.post_id).or_default().push(post_tag.name);
+    }
+    Ok(tags)
+}
+
+#[derive(Component)]
+#[shaku( interface = TagQueryUsecase )]
+pub struct TagQueryUsecaseImpl{
+    #[shaku(inject)]
+    db: Arc<dyn DbConnProvider>,
+    #[shaku(inject)]
+    load_tag_port: Arc<dyn LoadTagPort>,
+}
+
+#[async_trait::async_trait]
+impl TagQueryUsecase for TagQueryUsecaseImpl {
+    async fn get_all(&self) -> Result<Vec<QTagDto>, AppError> {
+        let txn = self.db.ro_txn().await?;
+        let tags = self.load_tag_port.find_all(&txn).await?;
+        txn.commit().await?;
+
+        Ok(tags.into_iter().map(QTagDto::from).collect())
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
-    common::{AppError, LoginMember},
-    domain::board::entity::{command::{board_entity::BoardEntity, post_entity::PostEntity}, query::{QBoardEntity, QPostEntity}},
+    common::{markdown::TocEntry, AppError, LoginMember},
+    domain::board::{entity::{command::{board_entity::BoardEntity, comment_entity::CommentEntity, post_entity::{cover_image_url, reading_time_minutes, PostEntity, PostStatus}, post_revision_entity::PostRevisionEntity, reaction_entity::ReactionKind}, query::{QBoardEntity, QCommentEntity, QPostEntity, QPostRevisionEntity, QTagEntity}}, repository::PostListOrder},
 };
 use chrono::NaiveDateTime;
 use sea_orm::FromQueryResult;
@@ -34,16 +34,24 @@ pub struct QBoardDto {
     pub count: i64 
 }
 
+/// status 를 주지 않으면 바로 발행한다. 예약 발행(`Scheduled`)은 publish_at 이 필요하다.
 pub struct CreatePostCommand {
     pub title: String,
     pub contents: String,
     pub category_id: i64,
+    pub status: Option<PostStatus>,
+    pub publish_at: Option<NaiveDateTime>,
+    pub tags: Vec<String>,
 }
 
+/// status, tags 를 주지 않으면 발행 상태와 태그를 바꾸지 않는다.
 pub struct ModifyPostCommand {
     pub title: String,
     pub contents: String,
     pub category_id: i64,
+    pub status: Option<PostStatus>,
+    pub publish_at: Option<NaiveDateTime>,
+    pub tags: Option<Vec<String>>,
 }
 
 pub struct PostDto {
@@ -52,10 +60,163 @@ pub struct PostDto {
     pub title: String,
     pub contents: String,
     pub category_id: i64,
+    pub status: String,
+    pub published_at: Option<NaiveDateTime>,
+    pub tags: Vec<String>,
     pub created_at: NaiveDateTime,
     pub updated_at: Option<NaiveDateTime>,
 }
 
+/// 공개된 게시글 검색 조건. offset 번째 결과부터 size 개를 찾는다.
+pub struct SearchPostCommand {
+    pub keyword: String,
+    pub category_id: Option<i64>,
+    pub tag: Option<String>,
+    pub author_id: Option<uuid::Uuid>,
+    pub offset: u64,
+    pub size: u64,
+}
+
+/// 검색 결과 DTO. title_highlight, snippet 은 HTML 이스케이프 후 일치한 부분을 `<mark>` 로 감싼 HTML 조각이다.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct QPostSearchDto {
+    pub id: uuid::Uuid,
+    pub writer: WriterVo,
+    pub category: CategoryVo,
+    pub title: String,
+    pub title_highlight: String,
+    pub snippet: String,
+    pub rank: f32,
+    pub published_at: Option<NaiveDateTime>,
+}
+
+/// 대상에 남은 종류별 반응 수
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ReactionCountVo {
+    pub kind: String,
+    pub count: i64,
+    /// 로그인한 회원이 남긴 반응인지
+    pub reacted_by_me: bool,
+}
+
+/// 피드에 담는 글. summary 는 본문 요약이고, 본문 전체를 담도록 설정하면 content 에 렌더링한 HTML 을 채운다.
+/// content 는 피드에 HTML 로 그대로 실리므로 반드시 `MarkdownRenderer` 로 정리한 값이어야 한다.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FeedItemDto {
+    pub id: Uuid,
+    pub title: String,
+    pub writer: WriterVo,
+    pub category: CategoryVo,
+    pub tags: Vec<String>,
+    pub summary: String,
+    pub content: Option<String>,
+    pub published_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// 사이트 전체, 게시판, 태그별 피드. scope_name 은 게시판 이름이나 태그이고 사이트 전체 피드면 없다.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FeedDto {
+    pub scope_name: Option<String>,
+    pub items: Vec<FeedItemDto>,
+}
+
+impl FeedDto {
+    /// 가장 최근에 바뀐 글의 시각. 글이 없으면 None
+    pub fn last_modified(&self) -> Option<NaiveDateTime> {
+        self.items.iter().map(|item| item.updated_at).max()
+    }
+}
+
+/// 사이트맵 항목. path 는 사이트 루트 기준 경로다.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SitemapEntryDto {
+    pub path: String,
+    pub last_modified: Option<NaiveDateTime>,
+}
+
+/// `/sitemap.xml`. URL 이 사이트맵 하나에 다 들어가지 않으면 하위 사이트맵 목록을 준다.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub enum SitemapDto {
+    UrlSet(Vec<SitemapEntryDto>),
+    Index(Vec<SitemapEntryDto>),
+}
+
+/// 태그 구름 조회용 DTO
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct QTagDto {
+    pub id: i64,
+    pub name: String,
+    /// 태그가 달린 공개 게시글 수
+    pub count: i64,
+}
+
+/// 리비전 목록 조회용 DTO. 본문은 포함하지 않는다.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct QPostRevisionDto {
+    pub revision_no: i32,
+    pub writer: WriterVo,
+    pub category_id: i64,
+    pub title: String,
+    pub restored_from: Option<i32>,
+    pub created_at: NaiveDateTime,
+}
+
+pub struct PostRevisionDto {
+    pub post_id: uuid::Uuid,
+    pub revision_no: i32,
+    pub writer_id: uuid::Uuid,
+    pub category_id: i64,
+    pub title: String,
+    pub contents: String,
+    pub restored_from: Option<i32>,
+    pub created_at: NaiveDateTime,
+}
+
+/// 두 리비전의 차이. 본문은 unified diff 로, 제목과 게시판은 양쪽 값을 그대로 준다.
+pub struct PostRevisionDiffDto {
+    pub from_revision_no: i32,
+    pub to_revision_no: i32,
+    pub from_title: String,
+    pub to_title: String,
+    pub from_category_id: i64,
+    pub to_category_id: i64,
+    pub diff: String,
+}
+
+pub struct CreateCommentCommand {
+    pub parent_id: Option<i64>,
+    pub contents: String,
+}
+
+pub struct ModifyCommentCommand {
+    pub contents: String,
+}
+
+pub struct CommentDto {
+    pub id: i64,
+    pub post_id: uuid::Uuid,
+    pub parent_id: Option<i64>,
+    pub writer_id: uuid::Uuid,
+    pub contents: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+/// 댓글 트리 조회용 DTO. 삭제된 댓글은 답글 스레드를 유지하기 위해 내용 없이 남는다.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct QCommentDto {
+    pub id: i64,
+    pub parent_id: Option<i64>,
+    pub writer: WriterVo,
+    pub contents: Option<String>,
+    pub is_deleted: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: Option<NaiveDateTime>,
+    pub reactions: Vec<ReactionCountVo>,
+    pub children: Vec<QCommentDto>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, FromQueryResult)]
 pub struct WriterVo {
     #[sea_orm(from_alias = "writer_id")]
@@ -78,7 +239,20 @@ pub struct QPostDto {
     pub writer: WriterVo,
     pub category: CategoryVo,
     pub title: String,
+    /// Markdown 원문. 목록 조회에서는 비어 있다.
     pub contents: Option<String>,
+    /// 원문을 렌더링하고 정리한 HTML
+    pub contents_html: Option<String>,
+    pub toc: Vec<TocEntry>,
+    /// Markdown 을 걷어 낸 본문 요약
+    pub excerpt: String,
+    pub reading_time_minutes: i64,
+    pub cover_image: Option<String>,
+    pub status: String,
+    pub published_at: Option<NaiveDateTime>,
+    pub tags: Vec<String>,
+    pub view_count: i64,
+    pub reactions: Vec<ReactionCountVo>,
     pub created_at: NaiveDateTime,
     pub updated_at: Option<NaiveDateTime>,
 }
@@ -91,6 +265,16 @@ impl From<QPostEntity> for QPostDto {
             category: entity.category,
             title: entity.title,
             contents: entity.contents,
+            contents_html: None,
+            toc: Vec::new(),
+            excerpt: String::new(),
+            reading_time_minutes: reading_time_minutes(entity.contents_length.unwrap_or(0) as usize),
+            cover_image: entity.cover_image.as_deref().and_then(cover_image_url),
+            status: entity.status,
+            published_at: entity.published_at,
+            tags: Vec::new(),
+            view_count: entity.view_count,
+            reactions: Vec::new(),
             created_at: entity.created_at,
             updated_at: entity.updated_at
         }
@@ -105,12 +289,83 @@ impl From<PostEntity> for PostDto {
             title: entity.get_title(),
             contents: entity.get_contents(),
             category_id: entity.get_category_id(),
+            status: entity.get_status().as_str().to_owned(),
+            published_at: entity.get_published_at(),
+            tags: Vec::new(),
             created_at: entity.get_created_at(),
             updated_at: entity.get_updated_at()
         }
     }
 }
 
+impl From<QTagEntity> for QTagDto {
+    fn from(entity: QTagEntity) -> Self {
+        QTagDto {
+            id: entity.id,
+            name: entity.name,
+            count: entity.count,
+        }
+    }
+}
+
+impl From<QPostRevisionEntity> for QPostRevisionDto {
+    fn from(entity: QPostRevisionEntity) -> Self {
+        QPostRevisionDto {
+            revision_no: entity.revision_no,
+            writer: entity.writer,
+            category_id: entity.category_id,
+            title: entity.title,
+            restored_from: entity.restored_from,
+            created_at: entity.created_at,
+        }
+    }
+}
+
+impl From<PostRevisionEntity> for PostRevisionDto {
+    fn from(entity: PostRevisionEntity) -> Self {
+        PostRevisionDto {
+            post_id: entity.get_post_id(),
+            revision_no: entity.get_revision_no(),
+            writer_id: entity.get_member_id(),
+            category_id: entity.get_category_id(),
+            title: entity.get_title(),
+            contents: entity.get_contents(),
+            restored_from: entity.get_restored_from(),
+            created_at: entity.get_created_at(),
+        }
+    }
+}
+
+impl From<QCommentEntity> for QCommentDto {
+    fn from(entity: QCommentEntity) -> Self {
+        QCommentDto {
+            id: entity.id,
+            parent_id: entity.parent_id,
+            writer: entity.writer,
+            contents: if entity.is_deleted { None } else { Some(entity.contents) },
+            is_deleted: entity.is_deleted,
+            created_at: entity.created_at,
+            updated_at: entity.updated_at,
+            reactions: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+}
+
+impl From<CommentEntity> for CommentDto {
+    fn from(entity: CommentEntity) -> Self {
+        CommentDto {
+            id: entity.get_id().expect("Id field is required"),
+            post_id: entity.get_post_id(),
+            parent_id: entity.get_parent_id(),
+            writer_id: entity.get_member_id(),
+            contents: entity.get_contents(),
+            created_at: entity.get_created_at(),
+            updated_at: entity.get_updated_at(),
+        }
+    }
+}
+
 impl From<QBoardEntity> for QBoardDto {
     fn from(entity: QBoardEntity) -> Self {
         QBoardDto {
@@ -194,12 +449,169 @@ pub trait PostDeleteUsecase: Interface {
 #[async_trait::async_trait]
 pub trait PostQueryUsecase: Interface {
 
+    /// status 가 없거나 `Published` 면 공개된 글을, 그 밖의 상태는 viewer 본인의 글만 조회한다.
     async fn get_posts(
         &self,
+        viewer: Option<LoginMember>,
         category_id: Option<i64>,
-        cursor: Option<NaiveDateTime>,
+        tag: Option<String>,
+        status: Option<PostStatus>,
+        order: PostListOrder,
         size: u64,
     ) -> Result<Vec<QPostDto>, AppError>;
 
-    async fn get_post(&self, id: Uuid) -> Result<QPostDto, AppError>;
+    /// 공개되지 않은 글은 작성자가 아니면 찾을 수 없는 것으로 응답한다.
+    /// 공개된 글을 작성자가 아닌 방문자가 보면 조회수를 센다. visitor 는 중복 조회를 가리는 방문자 식별값이며,
+    /// 알 수 없으면 None 이다.
+    async fn get_post(&self, viewer: Option<LoginMember>, visitor: Option<String>, id: Uuid) -> Result<QPostDto, AppError>;
+}
+
+#[async_trait::async_trait]
+pub trait PostPublishUsecase: Interface {
+    /// 예약 시각이 지난 글을 발행하고 발행한 글 수를 돌려준다.
+    async fn publish_due_posts(&self) -> Result<u64, AppError>;
+}
+
+#[async_trait::async_trait]
+pub trait PostViewFlushUsecase: Interface {
+    /// 모아 둔 조회수를 반영하고 조회수가 바뀐 글 수를 돌려준다.
+    async fn flush_view_counts(&self) -> Result<u64, AppError>;
+}
+
+#[async_trait::async_trait]
+pub trait PostSearchUsecase: Interface {
+    async fn search(&self, command: SearchPostCommand) -> Result<Vec<QPostSearchDto>, AppError>;
+}
+
+#[async_trait::async_trait]
+pub trait SearchIndexRebuildUsecase: Interface {
+    /// 모든 게시글로 검색 색인을 다시 만들고 색인한 게시글 수를 돌려준다.
+    async fn rebuild(&self) -> Result<u64, AppError>;
+}
+
+#[async_trait::async_trait]
+pub trait SearchIndexFlushUsecase: Interface {
+    /// 임베디드 색인의 바뀐 내용을 파일에 쓰고, 쓴 내용이 있으면 true 를 돌려준다.
+    async fn flush_search_index(&self) -> Result<bool, AppError>;
+}
+
+/// 반응은 누르면 남고 다시 누르면 취소된다. 바뀐 뒤의 종류별 반응 수를 돌려준다.
+#[async_trait::async_trait]
+pub trait ReactionToggleUsecase: Interface {
+    async fn toggle_post_reaction(
+        &self,
+        login_member: LoginMember,
+        post_id: Uuid,
+        kind: ReactionKind,
+    ) -> Result<Vec<ReactionCountVo>, AppError>;
+
+    async fn toggle_comment_reaction(
+        &self,
+        login_member: LoginMember,
+        post_id: Uuid,
+        comment_id: i64,
+        kind: ReactionKind,
+    ) -> Result<Vec<ReactionCountVo>, AppError>;
+}
+
+/// 공개된 글만 최신순으로 담는다.
+#[async_trait::async_trait]
+pub trait FeedQueryUsecase: Interface {
+    async fn get_feed(&self, category_id: Option<i64>, tag: Option<String>) -> Result<FeedDto, AppError>;
+}
+
+/// 사이트맵에는 게시판과 공개된 글만 싣는다.
+#[async_trait::async_trait]
+pub trait SitemapQueryUsecase: Interface {
+    async fn get_sitemap(&self) -> Result<SitemapDto, AppError>;
+
+    /// 사이트맵 목록으로 나눴을 때의 게시판 사이트맵
+    async fn get_board_sitemap(&self) -> Result<Vec<SitemapEntryDto>, AppError>;
+
+    /// 사이트맵 목록으로 나눴을 때의 page 번째(1부터) 게시글 사이트맵
+    async fn get_post_sitemap(&self, page: u64) -> Result<Vec<SitemapEntryDto>, AppError>;
+}
+
+#[async_trait::async_trait]
+pub trait TagQueryUsecase: Interface {
+    async fn get_all(&self) -> Result<Vec<QTagDto>, AppError>;
+}
+
+/// 리비전은 게시글 작성자만 볼 수 있다.
+#[async_trait::async_trait]
+pub trait PostRevisionQueryUsecase: Interface {
+    async fn get_revisions(
+        &self,
+        login_member: LoginMember,
+        post_id: Uuid,
+    ) -> Result<Vec<QPostRevisionDto>, AppError>;
+
+    async fn get_revision(
+        &self,
+        login_member: LoginMember,
+        post_id: Uuid,
+        revision_no: i32,
+    ) -> Result<PostRevisionDto, AppError>;
+
+    async fn diff(
+        &self,
+        login_member: LoginMember,
+        post_id: Uuid,
+        from_revision_no: i32,
+        to_revision_no: i32,
+    ) -> Result<PostRevisionDiffDto, AppError>;
+}
+
+#[async_trait::async_trait]
+pub trait PostRevisionRestoreUsecase: Interface {
+    /// 예전 리비전의 내용으로 게시글을 되돌리고, 그 내용으로 새 리비전을 남긴다.
+    async fn restore(
+        &self,
+        login_member: LoginMember,
+        post_id: Uuid,
+        revision_no: i32,
+    ) -> Result<PostDto, AppError>;
+}
+
+#[async_trait::async_trait]
+pub trait CommentCreateUsecase: Interface {
+    async fn create(
+        &self,
+        login_member: LoginMember,
+        post_id: Uuid,
+        command: CreateCommentCommand,
+    ) -> Result<CommentDto, AppError>;
+}
+
+#[async_trait::async_trait]
+pub trait CommentModifyUsecase: Interface {
+    async fn update(
+        &self,
+        login_member: LoginMember,
+        post_id: Uuid,
+        id: i64,
+        command: ModifyCommentCommand,
+    ) -> Result<CommentDto, AppError>;
+}
+
+#[async_trait::async_trait]
+pub trait CommentDeleteUsecase: Interface {
+    async fn delete(
+        &self,
+        login_member: LoginMember,
+        post_id: Uuid,
+        id: i64,
+    ) -> Result<(), AppError>;
+}
+
+#[async_trait::async_trait]
+pub trait CommentQueryUsecase: Interface {
+
+    async fn get_comments(
+        &self,
+        viewer: Option<LoginMember>,
+        post_id: Uuid,
+        cursor: Option<i64>,
+        size: u64,
+    ) -> Result<Vec<QCommentDto>, AppError>;
 }
@@ -2,9 +2,10 @@ use super::{
     MemberCreateCommand, MemberCreateUseCase, MemberDeleteUseCase, MemberDto, MemberUpdateCommand,
     MemberUpdateUseCase,
 };
+use crate::application::auth::EmailVerificationUseCase;
 use crate::common::database::*;
 use crate::common::error::error_code::ErrorCode;
-use crate::common::middleware::security::LoginMember;
+use crate::common::middleware::security::{LoginMember, Permission};
 use crate::domain::member::entity::MemberEntity;
 use crate::domain::member::repository::{LoadMemberPort, SaveMemberPort};
 use shaku::Component;
@@ -31,6 +32,8 @@ pub struct MemberUpdateUseCaseImpl {
     load_member_port: Arc<dyn LoadMemberPort>,
     #[shaku(inject)]
     save_member_port: Arc<dyn SaveMemberPort>,
+    #[shaku(inject)]
+    email_verification_usecase: Arc<dyn EmailVerificationUseCase>,
 }
 
 #[derive(Component)]
@@ -66,7 +69,9 @@ impl MemberCreateUseCase for MemberCreateUseCaseImpl {
             role: "MEMBER".to_string(),
             created_at: chrono::Utc::now().naive_utc(),
             updated_at: None, //chrono::Utc::now().naive_utc(),
-            is_activated: true,
+            // 이메일 인증을 마치면 활성화된다.
+            is_activated: false,
+            has_password: true,
         };
 
         let member_entity = self.save_member_port.save(&txn, member_entity).await?;
@@ -100,14 +105,27 @@ impl MemberUpdateUseCase for MemberUpdateUseCaseImpl {
         }
 
         let mut member_entity = member.unwrap();
+        // 이메일이 바뀌면 새 주소로 다시 인증받아야 한다.
+        let email_changed = member_entity.email != command.email;
+        if email_changed {
+            member_entity.is_activated = false;
+        }
         member_entity.nickname = command.nickname;
         member_entity.email = command.email;
         member_entity.password = bcrypt::hash(command.password, 10).unwrap();
+        member_entity.has_password = true;
         // member_entity.updated_at = chrono::Utc::now().naive_utc();
 
         let modified_entity = self.save_member_port.update(&txn, member_entity).await?;
         txn.commit().await?;
 
+        // 메일은 응답과 별도로 보낸다. 변경은 이미 반영되었으므로 실패해도 인증 메일 재요청으로 다시 받을 수 있다.
+        if email_changed {
+            if let Err(e) = self.email_verification_usecase.request_verification(login_member.id).await {
+                tracing::error!("Failed to send verification mail for changed email: {}", e.message);
+            }
+        }
+
         Ok(MemberDto::from(modified_entity))
     }
 }
@@ -128,7 +146,7 @@ impl MemberDeleteUseCase for MemberDeleteUseCaseImpl {
 
         //let member_entity = member.unwrap();
 
-        if target_id != login_member.id {
+        if target_id != login_member.id && !login_member.has_permission(Permission::MemberAdmin) {
             return Err(ErrorCode::Forbidden);
         }
 
@@ -1,2 +0,0 @@
-pub mod github_oauth2;
-pub use github_oauth2::GithubOAuth2UsecaseImpl;
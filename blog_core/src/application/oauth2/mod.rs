@@ -1,14 +1,22 @@
 pub mod usecases;
-pub mod github;
+pub mod oauth2_service;
+pub mod oidc;
 
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
 use rand::distributions::Alphanumeric;
 use rand::Rng;
+use sha2::{Digest, Sha256};
 pub use usecases::*;
+pub use oauth2_service::OAuth2UsecaseImpl;
+pub use oidc::OidcDiscoveryImpl;
 
 pub const OAUTH2_AUTHORIZATION_REQUEST_COOKIE_NAME : &str = "oauth2-auth-request";
 pub const OAUTH2_REDIRECT_URI_COOKIE_NAME : &str = "redirect-uri";
 pub const OAUTH2_MODE_COOKIE_NAME : &str = "mode";
 pub const OAUTH2_COOKIE_EXPIRE_SECONDS: u16 = 180;
+/// RFC 7636 은 43~128자의 code_verifier 를 요구한다.
+pub const PKCE_CODE_VERIFIER_LENGTH: usize = 64;
 
 pub fn generate_rand(length: usize) -> String {
     let mut rng = rand::thread_rng();
@@ -18,4 +26,7 @@ pub fn generate_rand(length: usize) -> String {
     rand_string
 }
 
-
+/// PKCE S256 방식의 code_challenge (BASE64URL(SHA256(code_verifier)))
+pub fn pkce_code_challenge(code_verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(code_verifier.as_bytes()))
+}
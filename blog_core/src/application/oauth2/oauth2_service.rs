@@ -0,0 +1,315 @@
+use axum_extra::extract::CookieJar;
+use serde_json::Value;
+
+use crate::application::oauth2::oidc::{validate_id_token, OidcDiscovery};
+use crate::application::oauth2::usecases::OAuth2Usecase;
+use crate::application::oauth2::{generate_rand, OAuth2Mode, OAuth2Request, PKCE_CODE_VERIFIER_LENGTH, OAuth2RequestBuilder, OAuth2UserProfile, OAUTH2_AUTHORIZATION_REQUEST_COOKIE_NAME, OAUTH2_COOKIE_EXPIRE_SECONDS, OAUTH2_MODE_COOKIE_NAME, OAUTH2_REDIRECT_URI_COOKIE_NAME};
+use crate::common::error_code::ErrorCode;
+use crate::common::{AppError, CookieCipher, CookieMaker};
+use crate::config::OAuth2ProviderConfig;
+
+#[derive(serde::Deserialize)]
+#[allow(dead_code)]
+pub struct OAuth2TokenResponse {
+    pub access_token: String,
+    pub token_type: Option<String>,
+    pub scope: Option<String>,
+    pub id_token: Option<String>,
+}
+
+struct OAuth2Endpoints {
+    authorization_uri: String,
+    token_uri: String,
+    userinfo_uri: Option<String>,
+    jwks_uri: Option<String>,
+}
+
+/// `kakao_account.email` 처럼 점으로 구분된 경로의 claim 값을 문자열로 꺼낸다.
+fn claim_value(claims: &Value, path: &str) -> Option<String> {
+    let value = path
+        .split('.')
+        .try_fold(claims, |value, key| value.get(key))?;
+
+    match value {
+        Value::String(value) => Some(value.to_owned()),
+        Value::Number(value) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+fn map_user_profile(
+    config: &OAuth2ProviderConfig,
+    claims: &Value,
+    access_token: String,
+    mode: OAuth2Mode,
+) -> Result<OAuth2UserProfile, AppError> {
+    let user_id = claim_value(claims, &config.user_id_claim)
+        .ok_or_else(|| AppError::with_message(ErrorCode::FailedToDeserializeUserProfile, "OAuth2 사용자 식별자를 찾을 수 없습니다."))?;
+
+    // 제공자가 검증되지 않은 이메일이라고 알려주면 회원 병합에 사용하지 않는다.
+    let email_verified = ["email_verified", "kakao_account.is_email_verified"]
+        .iter()
+        .filter_map(|path| path.split('.').try_fold(claims, |value, key| value.get(key)))
+        .all(|value| value.as_bool().unwrap_or(true));
+    let email = config
+        .email_claim
+        .as_ref()
+        .and_then(|path| claim_value(claims, path))
+        .filter(|_| email_verified);
+
+    Ok(OAuth2UserProfile {
+        provider: config.provider.as_str().to_string(),
+        user_id,
+        email,
+        access_token,
+        mode,
+    })
+}
+
+/// 제공자 설정만으로 동작하는 OAuth2 / OIDC 로그인 구현체
+pub struct OAuth2UsecaseImpl<'a> {
+    config: OAuth2ProviderConfig,
+    cookie_maker: &'a dyn CookieMaker,
+    cookie_cipher: &'a dyn CookieCipher,
+    oidc_discovery: &'a dyn OidcDiscovery,
+}
+
+impl <'a> OAuth2UsecaseImpl<'a> {
+    pub fn new(config: OAuth2ProviderConfig,
+        cookie_maker: &'a dyn CookieMaker,
+        cookie_cipher: &'a dyn CookieCipher,
+        oidc_discovery: &'a dyn OidcDiscovery) -> Self {
+        OAuth2UsecaseImpl {
+            config,
+            cookie_maker,
+            cookie_cipher,
+            oidc_discovery,
+        }
+    }
+
+    /// 직접 설정한 엔드포인트가 우선이고, 없으면 OIDC discovery 문서의 값을 사용한다.
+    async fn endpoints(&self) -> Result<OAuth2Endpoints, AppError> {
+        let metadata = match self.config.issuer {
+            Some(ref issuer) => Some(self.oidc_discovery.metadata(issuer).await?),
+            None => None,
+        };
+
+        let not_configured = || AppError::with_message(ErrorCode::InternalServerError, "OAuth2 제공자 엔드포인트가 설정되지 않았습니다.");
+        Ok(OAuth2Endpoints {
+            authorization_uri: self.config.authorization_uri.clone()
+                .or_else(|| metadata.as_ref().map(|m| m.authorization_endpoint.clone()))
+                .ok_or_else(not_configured)?,
+            token_uri: self.config.token_uri.clone()
+                .or_else(|| metadata.as_ref().map(|m| m.token_endpoint.clone()))
+                .ok_or_else(not_configured)?,
+            userinfo_uri: self.config.userinfo_uri.clone()
+                .or_else(|| metadata.as_ref().and_then(|m| m.userinfo_endpoint.clone())),
+            jwks_uri: metadata.map(|m| m.jwks_uri),
+        })
+    }
+
+    async fn get_user_profile(&self, userinfo_uri: &str, access_token: &str) ->
+        Result<Value, AppError> {
+        let response = reqwest::Client::new()
+            .get(userinfo_uri)
+            .bearer_auth(access_token)
+            .header("Accept", "application/json")
+            .header("User-Agent", "reqwest")
+            .send()
+            .await
+            .map_err(|_| AppError::from(ErrorCode::FailedToGetUserProfile))?;
+
+        if response.status() != reqwest::StatusCode::OK {
+            tracing::error!("userinfo response status: {}", response.status());
+            return Err(AppError::from(ErrorCode::FailedToGetUserProfile));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|_| AppError::from(ErrorCode::FailedToDeserializeUserProfile))
+    }
+
+    async fn request_access_token(&self, token_uri: &str, grant_code: String, code_verifier: Option<String>) -> Result<OAuth2TokenResponse, AppError> {
+        let response = reqwest::Client::new()
+            .post(token_uri)
+            .header("Accept", "application/json")
+            .header("User-Agent", "reqwest")
+            .form(&[
+                ("grant_type", "authorization_code"),
+                ("client_id", self.config.client_id.as_str()),
+                ("client_secret", self.config.client_secret.as_str()),
+                ("redirect_uri", self.config.redirect_uri.as_str()),
+                ("code", grant_code.as_str()),
+                ("code_verifier", code_verifier.as_deref().unwrap_or_default()),
+            ])
+            .send()
+            .await
+            .map_err(|_| AppError::from(ErrorCode::FailedToGetAccessToken))?;
+
+        response
+            .json()
+            .await
+            .map_err(|_| AppError::from(ErrorCode::FailedToDeserializeAccessToken))
+    }
+}
+
+#[async_trait::async_trait]
+impl <'a> OAuth2Usecase for OAuth2UsecaseImpl <'a>{
+
+    async fn redirect_to_login_page(&self, jar: CookieJar, mode: OAuth2Mode) -> Result<(CookieJar, String), AppError> {
+        let endpoints = self.endpoints().await?;
+
+        let mut request = OAuth2RequestBuilder::default()
+            .provider(self.config.provider.clone())
+            .mode(mode.clone())
+            .authorization_uri(endpoints.authorization_uri)
+            .redirect_uri(self.config.redirect_uri.clone())
+            .client_id(self.config.client_id.clone())
+            .scope(self.config.scopes.clone())
+            .response_type("code".to_owned())
+            .state(Some(generate_rand(32)))
+            .nonce(self.config.issuer.as_ref().map(|_| generate_rand(32)))
+            .code_verifier(Some(generate_rand(PKCE_CODE_VERIFIER_LENGTH)))
+            .prompt(self.config.prompt.clone())
+            .build()
+            .expect("Failed to build OAuth2Request");
+
+        let uri = request.to_redirect_uri()
+            .ok_or_else(|| AppError::with_message(ErrorCode::InternalServerError, "OAuth2 요청을 만들 수 없습니다."))?;
+        request.full_redirect_uri = Some(uri.clone());
+        let serialized_request = serde_json::to_string(&request).unwrap();
+        let encoded_request = self.cookie_cipher.encrypt(&serialized_request);
+
+        let jar = jar.remove(OAUTH2_AUTHORIZATION_REQUEST_COOKIE_NAME);
+        let jar = jar.remove(OAUTH2_REDIRECT_URI_COOKIE_NAME);
+        let jar = jar.remove(OAUTH2_MODE_COOKIE_NAME);
+
+        let mut request_cookie = self.cookie_maker
+            .create_cookie(
+                OAUTH2_AUTHORIZATION_REQUEST_COOKIE_NAME.to_string(),
+                encoded_request.to_string()
+            );
+
+        let mut redirect_uri_cookie = self.cookie_maker
+            .create_cookie(
+                OAUTH2_REDIRECT_URI_COOKIE_NAME.to_string(),
+                uri.clone()
+            );
+
+        let mut mode_cookie = self.cookie_maker
+            .create_cookie(
+                OAUTH2_MODE_COOKIE_NAME.to_string(),
+                mode.as_str().to_string()
+            );
+
+        request_cookie.set_max_age(time::Duration::seconds(OAUTH2_COOKIE_EXPIRE_SECONDS as i64));
+        redirect_uri_cookie.set_max_age(time::Duration::seconds(OAUTH2_COOKIE_EXPIRE_SECONDS as i64));
+        mode_cookie.set_max_age(time::Duration::seconds(OAUTH2_COOKIE_EXPIRE_SECONDS as i64));
+
+        let jar = jar.add(request_cookie);
+        let jar = jar.add(redirect_uri_cookie);
+        let jar = jar.add(mode_cookie);
+
+        Ok((jar, uri))
+    }
+
+    async fn get_userinfo(&self,
+        jar: CookieJar,
+        state: String,
+        grant_code: String
+    ) -> Result<(CookieJar, OAuth2UserProfile), AppError> {
+
+        let request_cookie = jar.get(OAUTH2_AUTHORIZATION_REQUEST_COOKIE_NAME)
+            .ok_or_else(|| AppError::from(ErrorCode::BadRequest))?;
+        let request_cookie = self.cookie_cipher.decrypt(request_cookie.value())
+            .ok_or_else(|| AppError::from(ErrorCode::BadRequest))?;
+        let request_cookie = serde_json::from_str::<OAuth2Request>(&request_cookie)
+            .map_err(|_| AppError::from(ErrorCode::BadRequest))?;
+
+        if request_cookie.state != Some(state) || request_cookie.provider != self.config.provider {
+            return Err(AppError::from(ErrorCode::StateMismatch));
+        }
+
+        let endpoints = self.endpoints().await?;
+        let token_response = self
+            .request_access_token(&endpoints.token_uri, grant_code, request_cookie.code_verifier.clone())
+            .await?;
+
+        // OIDC 제공자는 검증된 id_token 의 claim 을, 그 외에는 userinfo 응답을 사용한다.
+        let claims = match (&self.config.issuer, &token_response.id_token, &endpoints.jwks_uri) {
+            (Some(issuer), Some(id_token), Some(jwks_uri)) => {
+                let jwks = self.oidc_discovery.jwks(jwks_uri).await?;
+                validate_id_token(id_token, &jwks, issuer, &self.config.client_id, request_cookie.nonce.as_deref())?
+            }
+            (Some(_), _, _) => {
+                return Err(AppError::with_message(ErrorCode::FailedToGetAccessToken, "OIDC 제공자가 id_token 을 반환하지 않았습니다."));
+            }
+            (None, _, _) => {
+                let userinfo_uri = endpoints.userinfo_uri.as_deref()
+                    .ok_or_else(|| AppError::with_message(ErrorCode::InternalServerError, "OAuth2 제공자 엔드포인트가 설정되지 않았습니다."))?;
+                self.get_user_profile(userinfo_uri, &token_response.access_token).await?
+            }
+        };
+
+        let user_info = map_user_profile(&self.config, &claims, token_response.access_token, request_cookie.mode)?;
+        tracing::debug!("receive {} user profile : ${:?}", self.config.provider.as_str(), user_info);
+        let jar = jar.remove(OAUTH2_AUTHORIZATION_REQUEST_COOKIE_NAME);
+        let jar = jar.remove(OAUTH2_REDIRECT_URI_COOKIE_NAME);
+        let jar = jar.remove(OAUTH2_MODE_COOKIE_NAME);
+
+        Ok((jar, user_info))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::map_user_profile;
+    use crate::application::oauth2::{OAuth2Mode, OAuth2Provider};
+    use crate::config::OAuth2ProviderConfig;
+
+    fn create_config(provider: OAuth2Provider, user_id_claim: &str, email_claim: &str) -> OAuth2ProviderConfig {
+        OAuth2ProviderConfig {
+            provider,
+            client_id: "client".to_owned(),
+            client_secret: "secret".to_owned(),
+            redirect_uri: "http://localhost:8080/callback".to_owned(),
+            scopes: String::new(),
+            authorization_uri: None,
+            token_uri: None,
+            userinfo_uri: None,
+            issuer: None,
+            user_id_claim: user_id_claim.to_owned(),
+            email_claim: Some(email_claim.to_owned()),
+            prompt: None,
+        }
+    }
+
+    #[test]
+    fn map_nested_claims_test() {
+        let config = create_config(OAuth2Provider::Kakao, "id", "kakao_account.email");
+        let claims = serde_json::json!({
+            "id": 1234567,
+            "kakao_account": { "email": "member@kakao.com", "is_email_verified": true },
+        });
+
+        let profile = map_user_profile(&config, &claims, "token".to_owned(), OAuth2Mode::SignIn).ok().unwrap();
+        assert_eq!(profile.provider, "KAKAO");
+        assert_eq!(profile.user_id, "1234567");
+        assert_eq!(profile.email, Some("member@kakao.com".to_owned()));
+    }
+
+    #[test]
+    fn unverified_email_is_ignored_test() {
+        let config = create_config(OAuth2Provider::Google, "sub", "email");
+        let claims = serde_json::json!({
+            "sub": "abc",
+            "email": "member@gmail.com",
+            "email_verified": false,
+        });
+
+        let profile = map_user_profile(&config, &claims, "token".to_owned(), OAuth2Mode::SignIn).ok().unwrap();
+        assert_eq!(profile.email, None);
+        assert!(map_user_profile(&config, &serde_json::json!({}), "token".to_owned(), OAuth2Mode::SignIn).is_err());
+    }
+}
@@ -3,12 +3,15 @@ use std::collections::HashMap;
 use axum_extra::extract::CookieJar;
 use derive_builder::Builder;
 use serde::{Serialize, Deserialize};
+use uuid::Uuid;
 use crate::common::AppError;
 
+use super::pkce_code_challenge;
+
 #[async_trait::async_trait]
 pub trait OAuth2Usecase {
 
-    fn redirect_to_login_page(&self, jar: CookieJar, mode: String) -> (CookieJar, String);
+    async fn redirect_to_login_page(&self, jar: CookieJar, mode: OAuth2Mode) -> Result<(CookieJar, String), AppError>;
 
     async fn get_userinfo(&self, 
         jar: CookieJar,
@@ -16,16 +19,65 @@ pub trait OAuth2Usecase {
         grant_code: String) -> Result<(CookieJar, OAuth2UserProfile), AppError>;
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub enum OAuth2Provider {
     Github,
     Google,
+    Kakao,
+}
+
+impl OAuth2Provider {
+    /// oauth2_member.provider 컬럼과 환경변수 접두어에 사용하는 이름
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Github => "GITHUB",
+            Self::Google => "GOOGLE",
+            Self::Kakao => "KAKAO",
+        }
+    }
+
+    /// `/api/v1/oauth2/{provider}` 경로에 사용하는 이름
+    pub fn path(&self) -> &'static str {
+        match self {
+            Self::Github => "github",
+            Self::Google => "google",
+            Self::Kakao => "kakao",
+        }
+    }
+
+    pub fn from_path(path: &str) -> Option<Self> {
+        match path {
+            "github" => Some(Self::Github),
+            "google" => Some(Self::Google),
+            "kakao" => Some(Self::Kakao),
+            _ => None,
+        }
+    }
+}
+
+/// 콜백에서 로그인할지, 로그인한 회원에게 제공자 계정을 연결할지 구분한다.
+/// 연결 대상 회원은 암호화된 인가 요청 쿠키에만 담기므로 콜백에서 그대로 신뢰할 수 있다.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum OAuth2Mode {
+    SignIn,
+    Link(Uuid),
+}
+
+impl OAuth2Mode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::SignIn => "sign-in",
+            Self::Link(_) => "link",
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Builder)]
 pub struct OAuth2Request {
     #[builder(default="OAuth2Provider::Github")]
     pub provider: OAuth2Provider,
+    #[builder(default="OAuth2Mode::SignIn")]
+    pub mode: OAuth2Mode,
     pub authorization_uri: String,
     pub redirect_uri: String,
     pub client_id: String,
@@ -33,9 +85,14 @@ pub struct OAuth2Request {
     pub response_type: String,
     #[builder(default=None)]
     pub state: Option<String>,
-    // pub nounce: Option<String>,
-    #[builder(default="true")]
-    pub prompt: bool,
+    /// OIDC 제공자에게 보내고 id_token 의 nonce claim 과 비교한다.
+    #[builder(default=None)]
+    pub nonce: Option<String>,
+    /// PKCE code_verifier. 인가 요청에는 S256 code_challenge 만 보내고 원문은 암호화된 쿠키에만 남긴다.
+    #[builder(default=None)]
+    pub code_verifier: Option<String>,
+    #[builder(default=None)]
+    pub prompt: Option<String>,
     #[builder(default=None)]
     pub full_redirect_uri: Option<String>,
     #[builder(default=HashMap::new())]
@@ -53,21 +110,37 @@ impl OAuth2Request {
             return None;
         }
 
-        let mut uri = format!("{}?client_id={}&redirect_uri={}", request.authorization_uri, request.client_id, request.redirect_uri);
+        let mut uri = reqwest::Url::parse(&request.authorization_uri).ok()?;
+        {
+            let mut query = uri.query_pairs_mut();
+            query.append_pair("response_type", &request.response_type);
+            query.append_pair("client_id", &request.client_id);
+            query.append_pair("redirect_uri", &request.redirect_uri);
+            query.append_pair("scope", &request.scope);
 
-        uri.push_str(&format!("&scope={}", request.scope));
+            if let Some(ref state) = request.state {
+                query.append_pair("state", state);
+            }
 
-        if let Some(ref state) = request.state {
-            uri.push_str(&format!("&state={}", state));
-        }
+            if let Some(ref code_verifier) = request.code_verifier {
+                query.append_pair("code_challenge", &pkce_code_challenge(code_verifier));
+                query.append_pair("code_challenge_method", "S256");
+            }
+
+            if let Some(ref nonce) = request.nonce {
+                query.append_pair("nonce", nonce);
+            }
 
-        uri.push_str(&format!("&prompt={}", request.prompt));
+            if let Some(ref prompt) = request.prompt {
+                query.append_pair("prompt", prompt);
+            }
 
-        for (key, value) in request.additional_params.iter() {
-            uri.push_str(&format!("&{}={}", key, value));
+            for (key, value) in request.additional_params.iter() {
+                query.append_pair(key, value);
+            }
         }
 
-        Some(uri)
+        Some(uri.to_string())
     }
 }
 
@@ -77,4 +150,40 @@ pub struct OAuth2UserProfile {
     pub user_id: String,
     pub email: Option<String>,
     pub access_token: String,
+    pub mode: OAuth2Mode,
+}
+
+#[cfg(test)]
+mod test {
+    use super::OAuth2RequestBuilder;
+    use crate::application::oauth2::pkce_code_challenge;
+
+    #[test]
+    fn to_redirect_uri_test() {
+        let request = OAuth2RequestBuilder::default()
+            .authorization_uri("https://accounts.google.com/o/oauth2/v2/auth".to_owned())
+            .redirect_uri("http://localhost:8080/api/v1/oauth2/google/callback".to_owned())
+            .client_id("client".to_owned())
+            .scope("openid email".to_owned())
+            .response_type("code".to_owned())
+            .state(Some("state".to_owned()))
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            request.to_redirect_uri().unwrap(),
+            "https://accounts.google.com/o/oauth2/v2/auth?response_type=code&client_id=client\
+&redirect_uri=http%3A%2F%2Flocalhost%3A8080%2Fapi%2Fv1%2Foauth2%2Fgoogle%2Fcallback\
+&scope=openid+email&state=state"
+        );
+    }
+
+    #[test]
+    fn pkce_code_challenge_test() {
+        // RFC 7636 Appendix B
+        assert_eq!(
+            pkce_code_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+    }
 }
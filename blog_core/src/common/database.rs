@@ -21,7 +21,7 @@ pub trait DbConnProvider: Interface {
 #[derive(Component)]
 #[shaku(interface = DbConnProvider)]
 pub struct DbConnProviderImpl {
-    db: DbConn,
+    pub(crate) db: DbConn,
 }
 
 #[async_trait::async_trait]
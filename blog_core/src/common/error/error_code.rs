@@ -13,6 +13,7 @@ pub enum ErrorCode {
     DbError,
     NotImplemented,
     InvalidInput,
+    TooManyRequests,
     // 검증 에러
     ValidationError,
 
@@ -20,6 +21,12 @@ pub enum ErrorCode {
     EmailPasswordMismatch,
     JwtBuildClaimsException,
     InvalidJwtToken,
+    RefreshTokenReused,
+    RevokedSession,
+    InvalidVerificationToken,
+    InvalidMfaCode,
+    LoginLocked,
+    InvalidAccessToken,
 
     //인가 관련 에러
     NotEnoughPermission,
@@ -94,6 +101,12 @@ impl ErrorCode {
                 "잘못된 입력입니다.",
             ),
 
+            Self::TooManyRequests => (
+                StatusCode::TOO_MANY_REQUESTS,
+                "GE-010",
+                "요청이 너무 많습니다. 잠시 후 다시 시도해주세요.",
+            ),
+
             // 인증 관련 에러
             Self::EmailPasswordMismatch => (
                 StatusCode::UNAUTHORIZED,
@@ -111,6 +124,42 @@ impl ErrorCode {
                 "유효하지 않은 JWT 토큰입니다.",
             ),
 
+            Self::RefreshTokenReused => (
+                StatusCode::UNAUTHORIZED,
+                "AE-005",
+                "이미 사용된 refresh token 입니다. 보안을 위해 해당 로그인 세션이 만료되었습니다.",
+            ),
+
+            Self::RevokedSession => (
+                StatusCode::UNAUTHORIZED,
+                "AE-006",
+                "로그아웃된 세션입니다. 다시 로그인해주세요.",
+            ),
+
+            Self::InvalidVerificationToken => (
+                StatusCode::BAD_REQUEST,
+                "AE-007",
+                "유효하지 않거나 이미 사용된 인증 링크입니다.",
+            ),
+
+            Self::InvalidMfaCode => (
+                StatusCode::UNAUTHORIZED,
+                "AE-008",
+                "2단계 인증 코드가 올바르지 않습니다.",
+            ),
+
+            Self::LoginLocked => (
+                StatusCode::TOO_MANY_REQUESTS,
+                "AE-009",
+                "로그인 시도가 너무 많습니다. 잠시 후 다시 시도해주세요.",
+            ),
+
+            Self::InvalidAccessToken => (
+                StatusCode::UNAUTHORIZED,
+                "AE-010",
+                "유효하지 않거나 만료된 액세스 토큰입니다.",
+            ),
+
             // 인가 관련 에러
             Self::NotEnoughPermission => (StatusCode::FORBIDDEN, "AE-004", "권한이 없습니다."),
 
@@ -1,12 +1,16 @@
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 
 use super::error::error_code::ErrorCode;
+use super::jwt_key::JwtKeyRing;
 use crate::config::{AppConfig, ConfigProvider};
 use crate::domain::member::entity::MemberEntity;
+use chrono::NaiveDateTime;
+use jsonwebtoken::jwk::JwkSet;
 use jsonwebtoken::{encode, Header};
 use serde::{Deserialize, Serialize};
 use shaku::{Component, Interface};
 use tracing::error;
+use uuid::Uuid;
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct AccessTokenClaims {
@@ -19,6 +23,10 @@ pub struct AccessTokenClaims {
     pub nickname: String,
     pub is_activated: bool,
     pub roles: Vec<String>,
+    pub sid: String,
+    /// 2단계 인증(TOTP)을 거쳐 발급된 토큰인지 여부
+    #[serde(default)]
+    pub mfa: bool,
 }
 
 impl From<MemberEntity> for AccessTokenClaims {
@@ -33,6 +41,8 @@ impl From<MemberEntity> for AccessTokenClaims {
             nickname: member.nickname.clone(),
             is_activated: member.is_activated,
             roles: vec![format!("{}_{}", "ROLE", member.role.to_string())],
+            sid: "".to_string(),
+            mfa: false,
         }
     }
 }
@@ -49,6 +59,8 @@ impl Into<AccessTokenClaims> for String {
             nickname: "".to_string(),
             is_activated: false,
             roles: vec![],
+            sid: "".to_string(),
+            mfa: false,
         }
     }
 }
@@ -56,13 +68,26 @@ impl Into<AccessTokenClaims> for String {
 #[derive(Debug, Deserialize, Serialize)]
 pub struct RefreshTokenClaims {
     pub sub: String,
+    pub jti: String,
     pub exp: usize,
     pub iat: usize,
     pub iss: String,
     pub aud: String,
 }
 
-fn map_to_access_token_claims(cfg: Arc<AppConfig>, member: &MemberEntity) -> AccessTokenClaims {
+/// 비밀번호 재설정, 이메일 인증 메일에 담는 토큰. jti 로 서버에 저장된 토큰을 찾아 한 번만 사용하게 한다.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct VerificationTokenClaims {
+    pub sub: String,
+    pub jti: String,
+    pub purpose: String,
+    pub exp: usize,
+    pub iat: usize,
+    pub iss: String,
+    pub aud: String,
+}
+
+fn map_to_access_token_claims(cfg: Arc<AppConfig>, member: &MemberEntity, session_id: Uuid, mfa_authenticated: bool) -> AccessTokenClaims {
     AccessTokenClaims {
         sub: member.id.unwrap().to_string(),
         exp: (chrono::Utc::now()
@@ -75,12 +100,15 @@ fn map_to_access_token_claims(cfg: Arc<AppConfig>, member: &MemberEntity) -> Acc
         nickname: member.nickname.clone(),
         roles: vec![format!("{}_{}", "ROLE", member.role.to_string())],
         is_activated: member.is_activated,
+        sid: session_id.to_string(),
+        mfa: mfa_authenticated,
     }
 }
 
-fn map_to_refresh_token_claims(cfg: Arc<AppConfig>, member: &MemberEntity) -> RefreshTokenClaims {
+fn map_to_refresh_token_claims(cfg: Arc<AppConfig>, member: &MemberEntity, jti: Uuid) -> RefreshTokenClaims {
     RefreshTokenClaims {
         sub: member.id.unwrap().to_string(),
+        jti: jti.to_string(),
         exp: (chrono::Utc::now()
             + chrono::Duration::milliseconds(cfg.jwt_refresh_token_expire as i64))
         .timestamp() as usize,
@@ -91,34 +119,49 @@ fn map_to_refresh_token_claims(cfg: Arc<AppConfig>, member: &MemberEntity) -> Re
 }
 
 pub trait JwtService: Interface {
-    fn create_access_token(&self, member: &MemberEntity) -> Result<String, ErrorCode>;
-    fn create_refresh_token(&self, member: &MemberEntity) -> Result<String, ErrorCode>;
+    fn create_access_token(&self, member: &MemberEntity, session_id: Uuid, mfa_authenticated: bool) -> Result<String, ErrorCode>;
+    fn create_refresh_token(&self, member: &MemberEntity, jti: Uuid) -> Result<String, ErrorCode>;
     fn decode_access_token(&self, token: &str) -> Result<AccessTokenClaims, ErrorCode>;
     fn decode_refresh_token(&self, token: &str) -> Result<RefreshTokenClaims, ErrorCode>;
+    fn create_verification_token(&self, member_id: Uuid, jti: Uuid, purpose: &str, expires_at: NaiveDateTime) -> Result<String, ErrorCode>;
+    fn decode_verification_token(&self, token: &str) -> Result<VerificationTokenClaims, ErrorCode>;
+    fn jwks(&self) -> JwkSet;
 }
 
 #[derive(Component)]
 #[shaku(interface = JwtService)]
 pub struct JwtServiceImpl {
     #[shaku(inject)]
-    config: Arc<dyn ConfigProvider>,
+    pub(crate) config: Arc<dyn ConfigProvider>,
+    #[shaku(default)]
+    pub(crate) key_ring: OnceLock<JwtKeyRing>,
 }
 
+impl JwtServiceImpl {
+    fn key_ring(&self) -> &JwtKeyRing {
+        self.key_ring.get_or_init(|| {
+            JwtKeyRing::from_config(&self.config.get())
+                .unwrap_or_else(|e| panic!("JWT 서명 키를 불러오지 못했습니다: {}", e))
+        })
+    }
+}
+
+/// access token 은 외부 서비스에서도 검증할 수 있도록 kid 가 붙은 키로 서명한다.
+/// refresh token 은 이 서버에서만 검증하므로 HS256 secret 을 그대로 사용한다.
 impl JwtService for JwtServiceImpl {
-    fn create_access_token(&self, member: &MemberEntity) -> Result<String, ErrorCode> {
-        let claims = map_to_access_token_claims(self.config.get(), member);
-        let header = Header::new(jsonwebtoken::Algorithm::HS256);
-        let secret = &self.config.get().jwt_access_token_secret;
-        encode(
-            &header,
-            &claims,
-            &jsonwebtoken::EncodingKey::from_secret(secret.as_ref()),
-        )
-        .map_err(|_| ErrorCode::JwtBuildClaimsException)
+    fn create_access_token(&self, member: &MemberEntity, session_id: Uuid, mfa_authenticated: bool) -> Result<String, ErrorCode> {
+        let claims = map_to_access_token_claims(self.config.get(), member, session_id, mfa_authenticated);
+        let key = self.key_ring().signing_key();
+        let mut header = Header::new(key.get_algorithm());
+        header.kid = Some(key.get_kid().to_owned());
+        let encoding_key = key
+            .get_encoding_key()
+            .ok_or(ErrorCode::JwtBuildClaimsException)?;
+        encode(&header, &claims, encoding_key).map_err(|_| ErrorCode::JwtBuildClaimsException)
     }
 
-    fn create_refresh_token(&self, member: &MemberEntity) -> Result<String, ErrorCode> {
-        let claims = map_to_refresh_token_claims(self.config.get(), member);
+    fn create_refresh_token(&self, member: &MemberEntity, jti: Uuid) -> Result<String, ErrorCode> {
+        let claims = map_to_refresh_token_claims(self.config.get(), member, jti);
         let header = Header::new(jsonwebtoken::Algorithm::HS256);
         let secret = &self.config.get().jwt_refresh_token_secret;
         encode(
@@ -130,14 +173,24 @@ impl JwtService for JwtServiceImpl {
     }
 
     fn decode_access_token(&self, token: &str) -> Result<AccessTokenClaims, ErrorCode> {
-        let secret = &self.config.get().jwt_access_token_secret;
         tracing::info! {"token:{}", token}
-        let mut valid_options = jsonwebtoken::Validation::new(jsonwebtoken::Algorithm::HS256);
+        let header = jsonwebtoken::decode_header(token).map_err(|err| {
+            error!("Invalid JWT header: {}, error : {}", token, err.to_string());
+            ErrorCode::InvalidJwtToken
+        })?;
+        let key = self
+            .key_ring()
+            .find(header.kid.as_deref())
+            .ok_or_else(|| {
+                error!("Unknown JWT kid: {:?}", header.kid);
+                ErrorCode::InvalidJwtToken
+            })?;
+        let mut valid_options = jsonwebtoken::Validation::new(key.get_algorithm());
         valid_options.set_issuer(&[&self.config.get().jwt_issuer]);
         valid_options.set_audience(&[&self.config.get().jwt_audience]);
         let token_data = jsonwebtoken::decode::<AccessTokenClaims>(
             token,
-            &jsonwebtoken::DecodingKey::from_secret(secret.as_ref()),
+            key.get_decoding_key(),
             &valid_options,
         )
         .map_err(|err| {
@@ -174,4 +227,45 @@ impl JwtService for JwtServiceImpl {
         })?;
         Ok(token_data.claims)
     }
+
+    fn create_verification_token(&self, member_id: Uuid, jti: Uuid, purpose: &str, expires_at: NaiveDateTime) -> Result<String, ErrorCode> {
+        let cfg = self.config.get();
+        let claims = VerificationTokenClaims {
+            sub: member_id.to_string(),
+            jti: jti.to_string(),
+            purpose: purpose.to_string(),
+            exp: expires_at.and_utc().timestamp() as usize,
+            iat: chrono::Utc::now().timestamp() as usize,
+            iss: cfg.jwt_issuer.to_string(),
+            aud: cfg.jwt_audience.to_string(),
+        };
+        encode(
+            &Header::new(jsonwebtoken::Algorithm::HS256),
+            &claims,
+            &jsonwebtoken::EncodingKey::from_secret(cfg.jwt_verification_token_secret.as_ref()),
+        )
+        .map_err(|_| ErrorCode::JwtBuildClaimsException)
+    }
+
+    fn decode_verification_token(&self, token: &str) -> Result<VerificationTokenClaims, ErrorCode> {
+        let cfg = self.config.get();
+        let mut valid_options = jsonwebtoken::Validation::new(jsonwebtoken::Algorithm::HS256);
+        valid_options.set_issuer(&[&cfg.jwt_issuer]);
+        valid_options.set_audience(&[&cfg.jwt_audience]);
+
+        jsonwebtoken::decode::<VerificationTokenClaims>(
+            token,
+            &jsonwebtoken::DecodingKey::from_secret(cfg.jwt_verification_token_secret.as_ref()),
+            &valid_options,
+        )
+        .map(|token_data| token_data.claims)
+        .map_err(|err| {
+            error!("Invalid verification token: {}", err);
+            ErrorCode::InvalidVerificationToken
+        })
+    }
+
+    fn jwks(&self) -> JwkSet {
+        self.key_ring().jwks()
+    }
 }
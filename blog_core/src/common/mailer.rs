@@ -0,0 +1,121 @@
+use std::sync::{Arc, Mutex};
+
+use lettre::message::header::ContentType;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use shaku::{Component, Interface};
+
+use crate::common::error::error_code::ErrorCode;
+use crate::common::AppError;
+use crate::config::{AppConfig, ConfigProvider};
+
+#[derive(Debug, Clone)]
+pub struct Mail {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+/// 메일 전송. 전송 방식은 `mail_transport` 설정으로 고른다.
+/// - `smtp` : SMTP(STARTTLS) 서버로 보낸다.
+/// - `file` : `mail_outbox_path` 아래에 .eml 파일로 남긴다. (개발용)
+/// - `memory` : 메모리에만 쌓아둔다. (테스트용)
+#[async_trait::async_trait]
+pub trait Mailer: Interface {
+    async fn send(&self, mail: Mail) -> Result<(), AppError>;
+}
+
+#[derive(Component)]
+#[shaku(interface = Mailer)]
+pub struct MailerImpl {
+    #[shaku(inject)]
+    pub(crate) config: Arc<dyn ConfigProvider>,
+    #[shaku(default)]
+    pub(crate) outbox: Mutex<Vec<Mail>>,
+}
+
+fn send_failed() -> AppError {
+    AppError::with_message(ErrorCode::InternalServerError, "메일 전송에 실패했습니다.")
+}
+
+fn build_message(config: &AppConfig, mail: &Mail) -> Result<Message, AppError> {
+    Message::builder()
+        .from(config.mail_from.parse().map_err(|_| send_failed())?)
+        .to(mail.to.parse().map_err(|_| {
+            AppError::with_message(ErrorCode::BadRequest, "잘못된 이메일 주소입니다.")
+        })?)
+        .subject(mail.subject.clone())
+        .header(ContentType::TEXT_PLAIN)
+        .body(mail.body.clone())
+        .map_err(|e| {
+            tracing::error!("Failed to build mail: {:?}", e);
+            send_failed()
+        })
+}
+
+impl MailerImpl {
+    async fn send_smtp(&self, config: &AppConfig, message: Message) -> Result<(), AppError> {
+        let mut transport = AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.smtp_host)
+            .map_err(|e| {
+                tracing::error!("Invalid SMTP relay {}: {:?}", config.smtp_host, e);
+                send_failed()
+            })?
+            .port(config.smtp_port);
+        if let (Some(username), Some(password)) = (&config.smtp_username, &config.smtp_password) {
+            transport = transport.credentials(Credentials::new(username.clone(), password.clone()));
+        }
+
+        transport.build().send(message).await.map(|_| ()).map_err(|e| {
+            tracing::error!("Failed to send mail via SMTP: {:?}", e);
+            send_failed()
+        })
+    }
+
+    async fn write_outbox_file(&self, config: &AppConfig, message: Message) -> Result<(), AppError> {
+        let file_error = |e: std::io::Error| {
+            tracing::error!("Failed to write mail outbox: {:?}", e);
+            send_failed()
+        };
+
+        tokio::fs::create_dir_all(&config.mail_outbox_path)
+            .await
+            .map_err(file_error)?;
+        let path = std::path::Path::new(&config.mail_outbox_path).join(format!(
+            "{}-{}.eml",
+            chrono::Utc::now().format("%Y%m%d%H%M%S"),
+            uuid::Uuid::new_v4()
+        ));
+        tokio::fs::write(&path, message.formatted())
+            .await
+            .map_err(file_error)?;
+
+        tracing::info!("Mail written to {}", path.display());
+        Ok(())
+    }
+
+    #[cfg(test)]
+    pub fn sent_mails(&self) -> Vec<Mail> {
+        self.outbox.lock().unwrap().clone()
+    }
+}
+
+#[async_trait::async_trait]
+impl Mailer for MailerImpl {
+    async fn send(&self, mail: Mail) -> Result<(), AppError> {
+        let config = self.config.get();
+        let message = build_message(&config, &mail)?;
+
+        match config.mail_transport.as_str() {
+            "smtp" => self.send_smtp(&config, message).await,
+            "file" => self.write_outbox_file(&config, message).await,
+            "memory" => {
+                self.outbox.lock().unwrap().push(mail);
+                Ok(())
+            }
+            transport => {
+                tracing::error!("Unknown mail transport: {}", transport);
+                Err(send_failed())
+            }
+        }
+    }
+}
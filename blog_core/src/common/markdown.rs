@@ -0,0 +1,412 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use chrono::NaiveDateTime;
+use pulldown_cmark::{html, CodeBlockKind, Event, Options, Parser, Tag, TagEnd};
+use serde::{Deserialize, Serialize};
+use shaku::{Component, Interface};
+use syntect::html::{ClassStyle, ClassedHTMLGenerator};
+use syntect::parsing::SyntaxSet;
+use syntect::util::LinesWithEndings;
+use uuid::Uuid;
+
+/// 코드 블록 하이라이트 span 의 class 접두사. 색은 클라이언트가 syntect 테마 CSS 로 입힌다.
+pub const HIGHLIGHT_CLASS_PREFIX: &str = "hl-";
+
+/// 본문의 id 접두사. 사용자가 쓴 id 가 페이지의 다른 요소를 가리지 않도록(DOM clobbering) 모든 id 에 붙인다.
+pub const USER_CONTENT_ID_PREFIX: &str = "user-content-";
+
+/// 본문에 남기는 class. 렌더러가 만드는 값만 허용한다.
+const ALLOWED_CLASS_PREFIXES: [&str; 3] = [HIGHLIGHT_CLASS_PREFIX, "language-", "footnote-"];
+const ALLOWED_CLASSES: [&str; 1] = ["code"];
+
+/// 렌더링 결과를 캐시하는 게시글 수
+const RENDER_CACHE_CAPACITY: usize = 1_000;
+
+/// 목차 항목. 중첩은 level 로 나타낸다.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TocEntry {
+    pub level: u8,
+    /// 제목 요소의 id (`USER_CONTENT_ID_PREFIX` 포함). `#id` 로 이동할 수 있다.
+    pub id: String,
+    pub title: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RenderedMarkdown {
+    pub html: String,
+    pub toc: Vec<TocEntry>,
+}
+
+/// 게시글 본문(Markdown)을 HTML 로 바꾼다.
+/// CommonMark 에 표, 각주, 취소선, 체크리스트를 더해 해석하고, 코드 블록은 언어별로 하이라이트하며,
+/// 제목마다 id 를 붙여 목차를 만든다. 결과 HTML 은 허용 목록에 있는 태그, 속성만 남기고 지운다.
+pub trait MarkdownRenderer: Interface {
+    fn render(&self, source: &str) -> RenderedMarkdown;
+
+    /// 게시글 본문을 렌더링한다. 같은 글을 고치지 않았다면(`updated_at` 이 같으면) 캐시한 결과를 준다.
+    fn render_post(&self, post_id: Uuid, updated_at: NaiveDateTime, source: &str) -> RenderedMarkdown;
+}
+
+#[derive(Component)]
+#[shaku(interface = MarkdownRenderer)]
+pub struct MarkdownRendererImpl {
+    #[shaku(default)]
+    syntax_set: OnceLock<SyntaxSet>,
+    #[shaku(default)]
+    sanitizer: OnceLock<ammonia::Builder<'static>>,
+    #[shaku(default)]
+    cache: Mutex<HashMap<Uuid, (NaiveDateTime, RenderedMarkdown)>>,
+}
+
+impl MarkdownRendererImpl {
+    fn syntax_set(&self) -> &SyntaxSet {
+        self.syntax_set.get_or_init(SyntaxSet::load_defaults_newlines)
+    }
+
+    fn sanitizer(&self) -> &ammonia::Builder<'static> {
+        self.sanitizer.get_or_init(|| {
+            let mut builder = ammonia::Builder::default();
+            builder
+                .id_prefix(Some(USER_CONTENT_ID_PREFIX))
+                .add_tags(["input"])
+                .add_tag_attributes("input", ["type", "checked", "disabled"])
+                .set_tag_attribute_value("input", "disabled", "")
+                .add_tag_attributes("h1", ["id"])
+                .add_tag_attributes("h2", ["id"])
+                .add_tag_attributes("h3", ["id"])
+                .add_tag_attributes("h4", ["id"])
+                .add_tag_attributes("h5", ["id"])
+                .add_tag_attributes("h6", ["id"])
+                .add_tag_attributes("div", ["id", "class"])
+                .add_tag_attributes("sup", ["class"])
+                .add_tag_attributes("pre", ["class"])
+                .add_tag_attributes("code", ["class"])
+                .add_tag_attributes("span", ["class"])
+                .add_tag_attributes("th", ["style"])
+                .add_tag_attributes("td", ["style"])
+                .attribute_filter(|element, attribute, value| match (element, attribute) {
+                    // 체크리스트 외의 입력 요소는 남기지 않는다.
+                    ("input", "type") => (value == "checkbox").then_some(Cow::Borrowed(value)),
+                    // 표 정렬 외의 인라인 스타일은 지운다.
+                    ("th" | "td", "style") => {
+                        matches!(value, "text-align: left" | "text-align: center" | "text-align: right")
+                            .then_some(Cow::Borrowed(value))
+                    }
+                    (_, "class") => filter_classes(value),
+                    // id 에 접두사가 붙으므로 문서 안 링크(각주, 목차)도 같은 접두사로 바꾼다.
+                    ("a", "href") => match value.strip_prefix('#') {
+                        Some(fragment) if !fragment.starts_with(USER_CONTENT_ID_PREFIX) => {
+                            Some(Cow::Owned(format!("#{}{}", USER_CONTENT_ID_PREFIX, fragment)))
+                        }
+                        _ => Some(Cow::Borrowed(value)),
+                    },
+                    _ => Some(Cow::Borrowed(value)),
+                });
+            builder
+        })
+    }
+
+    /// 언어를 모르는 코드 블록은 하이라이트 없이 escape 만 한다.
+    fn highlight_code(&self, language: &str, code: &str) -> String {
+        let syntax_set = self.syntax_set();
+        let syntax = (!language.is_empty())
+            .then(|| syntax_set.find_syntax_by_token(language))
+            .flatten();
+
+        let mut html = String::new();
+        if let Some(syntax) = syntax {
+            let mut generator = ClassedHTMLGenerator::new_with_class_style(
+                syntax,
+                syntax_set,
+                ClassStyle::SpacedPrefixed { prefix: HIGHLIGHT_CLASS_PREFIX },
+            );
+            let highlighted = LinesWithEndings::from(code)
+                .all(|line| generator.parse_html_for_line_which_includes_newline(line).is_ok());
+            if highlighted {
+                html.push_str(&format!("<pre class=\"code\"><code class=\"language-{}\">", escape_html(language)));
+                html.push_str(&generator.finalize());
+                html.push_str("</code></pre>");
+                return html;
+            }
+            tracing::warn!("코드 블록 하이라이트 실패: language={}", language);
+        }
+
+        html.push_str("<pre class=\"code\"><code>");
+        html.push_str(&escape_html(code));
+        html.push_str("</code></pre>");
+        html
+    }
+}
+
+impl MarkdownRenderer for MarkdownRendererImpl {
+    fn render(&self, source: &str) -> RenderedMarkdown {
+        let options = Options::ENABLE_TABLES
+            | Options::ENABLE_FOOTNOTES
+            | Options::ENABLE_STRIKETHROUGH
+            | Options::ENABLE_TASKLISTS;
+
+        let mut events: Vec<Event> = Vec::new();
+        let mut toc = Vec::new();
+        let mut heading_ids: HashMap<String, usize> = HashMap::new();
+        // 제목, 코드 블록 안의 이벤트를 끝날 때까지 모은다.
+        let mut heading: Option<(u8, Vec<Event>)> = None;
+        let mut code_block: Option<(String, String)> = None;
+
+        for event in Parser::new_ext(source, options) {
+            match event {
+                Event::Start(Tag::Heading { level, .. }) => {
+                    heading = Some((level as u8, Vec::new()));
+                }
+                Event::End(TagEnd::Heading(_)) => {
+                    let Some((level, inner)) = heading.take() else {
+                        continue;
+                    };
+                    let title = plain_text(&inner);
+                    let id = unique_id(&mut heading_ids, slugify(&title));
+                    events.push(Event::Html(format!("<h{} id=\"{}\">", level, id).into()));
+                    events.extend(inner);
+                    events.push(Event::Html(format!("</h{}>", level).into()));
+                    toc.push(TocEntry { level, id: format!("{}{}", USER_CONTENT_ID_PREFIX, id), title });
+                }
+                Event::Start(Tag::CodeBlock(kind)) => {
+                    let language = match kind {
+                        CodeBlockKind::Fenced(info) => info.split_whitespace().next().unwrap_or_default().to_owned(),
+                        CodeBlockKind::Indented => String::new(),
+                    };
+                    code_block = Some((language, String::new()));
+                }
+                Event::End(TagEnd::CodeBlock) => {
+                    if let Some((language, code)) = code_block.take() {
+                        events.push(Event::Html(self.highlight_code(&language, &code).into()));
+                    }
+                }
+                Event::Text(text) if code_block.is_some() => {
+                    if let Some((_, code)) = code_block.as_mut() {
+                        code.push_str(&text);
+                    }
+                }
+                event => match heading.as_mut() {
+                    Some((_, inner)) => inner.push(event),
+                    None => events.push(event),
+                },
+            }
+        }
+
+        let mut unsafe_html = String::with_capacity(source.len() * 3 / 2);
+        html::push_html(&mut unsafe_html, events.into_iter());
+
+        RenderedMarkdown {
+            html: self.sanitizer().clean(&unsafe_html).to_string(),
+            toc,
+        }
+    }
+
+    fn render_post(&self, post_id: Uuid, updated_at: NaiveDateTime, source: &str) -> RenderedMarkdown {
+        if let Some((cached_at, rendered)) = self.cache.lock().unwrap().get(&post_id) {
+            if *cached_at == updated_at {
+                return rendered.clone();
+            }
+        }
+
+        let rendered = self.render(source);
+        let mut cache = self.cache.lock().unwrap();
+        if cache.len() >= RENDER_CACHE_CAPACITY && !cache.contains_key(&post_id) {
+            // 가장 오래된 항목을 고르지 않고 아무 항목이나 비운다. 자주 보는 글은 곧 다시 채워진다.
+            if let Some(evicted) = cache.keys().next().copied() {
+                cache.remove(&evicted);
+            }
+        }
+        cache.insert(post_id, (updated_at, rendered.clone()));
+        rendered
+    }
+}
+
+/// 렌더러가 만드는 class 만 남긴다. 남는 것이 없으면 속성을 지운다.
+fn filter_classes(value: &str) -> Option<Cow<'_, str>> {
+    let classes = value
+        .split_whitespace()
+        .filter(|class| {
+            ALLOWED_CLASSES.contains(class) || ALLOWED_CLASS_PREFIXES.iter().any(|prefix| class.starts_with(prefix))
+        })
+        .collect::<Vec<&str>>();
+    (!classes.is_empty()).then(|| Cow::Owned(classes.join(" ")))
+}
+
+/// 요약에 쓸 Markdown 의 글자만 뽑는다. 코드 블록, 이미지, HTML 은 빼고 블록 사이는 공백으로 띄운다.
+pub fn to_plain_text(source: &str) -> String {
+    let mut text = String::with_capacity(source.len());
+    // 코드 블록, 이미지 안이면 1 이상
+    let mut skip_depth = 0;
+    for event in Parser::new_ext(source, Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH) {
+        match event {
+            Event::Start(Tag::CodeBlock(_) | Tag::Image { .. } | Tag::HtmlBlock) => skip_depth += 1,
+            Event::End(TagEnd::CodeBlock | TagEnd::Image | TagEnd::HtmlBlock) => skip_depth -= 1,
+            Event::Text(value) | Event::Code(value) if skip_depth == 0 => text.push_str(&value),
+            Event::SoftBreak | Event::HardBreak | Event::End(_) => text.push(' '),
+            _ => {}
+        }
+    }
+    text
+}
+
+/// 본문의 첫 이미지 주소. 목록의 표지 이미지로 쓴다.
+pub fn first_image_url(source: &str) -> Option<String> {
+    Parser::new_ext(source, Options::ENABLE_TABLES | Options::ENABLE_FOOTNOTES)
+        .find_map(|event| match event {
+            Event::Start(Tag::Image { dest_url, .. }) if !dest_url.trim().is_empty() => Some(dest_url.trim().to_owned()),
+            _ => None,
+        })
+}
+
+fn plain_text(events: &[Event]) -> String {
+    events.iter()
+        .filter_map(|event| match event {
+            Event::Text(text) | Event::Code(text) => Some(text.as_ref()),
+            Event::SoftBreak | Event::HardBreak => Some(" "),
+            _ => None,
+        })
+        .collect::<String>()
+        .trim()
+        .to_owned()
+}
+
+/// 제목 id. 글자와 숫자는 (한글 포함) 소문자로 남기고, 공백과 `-`, `_` 는 `-` 하나로 바꾸며, 나머지는 지운다.
+fn slugify(title: &str) -> String {
+    let mut slug = String::with_capacity(title.len());
+    for c in title.chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if (c.is_whitespace() || c == '-' || c == '_') && !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() {
+        "section".to_owned()
+    } else {
+        slug.to_owned()
+    }
+}
+
+/// 같은 제목이 여러 번 나오면 `-1`, `-2` … 를 붙인다.
+/// 붙인 id 도 기록해서 `Hello 1` 같은 실제 제목의 id 와 겹치지 않게 한다.
+fn unique_id(heading_ids: &mut HashMap<String, usize>, slug: String) -> String {
+    let mut count = heading_ids.get(&slug).copied().unwrap_or(0);
+    let id = loop {
+        let candidate = if count == 0 { slug.clone() } else { format!("{}-{}", slug, count) };
+        count += 1;
+        if !heading_ids.contains_key(&candidate) {
+            break candidate;
+        }
+    };
+    heading_ids.insert(slug, count);
+    heading_ids.entry(id.clone()).or_insert(1);
+    id
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod test {
+    use super::{first_image_url, slugify, to_plain_text, MarkdownRenderer, MarkdownRendererImpl, TocEntry};
+
+    fn renderer() -> MarkdownRendererImpl {
+        MarkdownRendererImpl {
+            syntax_set: Default::default(),
+            sanitizer: Default::default(),
+            cache: Default::default(),
+        }
+    }
+
+    #[test]
+    fn render_heading_test() {
+        let rendered = renderer().render("# 러스트 `async`\n\n본문\n\n## Hello, World!\n\n## Hello, World!\n\n### Hello\n\n### Hello 1\n\n### Hello\n");
+
+        assert!(rendered.html.contains(r#"<h1 id="user-content-러스트-async">러스트 <code>async</code></h1>"#));
+        assert!(rendered.html.contains(r#"<h2 id="user-content-hello-world-1">"#));
+        assert_eq!(rendered.toc, vec![
+            TocEntry { level: 1, id: "user-content-러스트-async".to_owned(), title: "러스트 async".to_owned() },
+            TocEntry { level: 2, id: "user-content-hello-world".to_owned(), title: "Hello, World!".to_owned() },
+            TocEntry { level: 2, id: "user-content-hello-world-1".to_owned(), title: "Hello, World!".to_owned() },
+            TocEntry { level: 3, id: "user-content-hello".to_owned(), title: "Hello".to_owned() },
+            TocEntry { level: 3, id: "user-content-hello-1".to_owned(), title: "Hello 1".to_owned() },
+            TocEntry { level: 3, id: "user-content-hello-2".to_owned(), title: "Hello".to_owned() },
+        ]);
+        assert!(rendered.html.contains(r#"<h3 id="user-content-hello-2">Hello</h3>"#));
+        assert_eq!(slugify("!!!"), "section");
+    }
+
+    #[test]
+    fn render_extensions_test() {
+        let source = "| a | b |\n|:-:|---|\n| 1 | 2 |\n\n각주[^1]\n\n[^1]: 설명\n\n- [x] 완료\n\n~~취소~~\n\n```rust\nfn main() {}\n```\n\n```\n<b>plain</b>\n```\n";
+        let html = renderer().render(source).html;
+
+        assert!(html.contains(r#"<th style="text-align: center">a</th>"#));
+        assert!(html.contains(r#"<sup class="footnote-reference">"#));
+        assert!(html.contains(r##"<a href="#user-content-1" rel="noopener noreferrer">1</a>"##));
+        assert!(html.contains(r#"<div class="footnote-definition" id="user-content-1">"#));
+        assert!(html.contains(r#"<input disabled="" type="checkbox" checked="">"#));
+        assert!(html.contains("<del>취소</del>"));
+        assert!(html.contains(r#"<pre class="code"><code class="language-rust"><span class="hl-source hl-rust">"#));
+        assert!(html.contains("<pre class=\"code\"><code>&lt;b&gt;plain&lt;/b&gt;\n</code></pre>"));
+    }
+
+    #[test]
+    fn to_plain_text_test() {
+        let source = "# 제목\n\n**굵은** [링크](https://example.com) `code`\n\n![표지](/a.png)\n\n```rust\nfn main() {}\n```\n\n<div>html</div>\n\n- 하나\n- 둘";
+        let text = to_plain_text(source).split_whitespace().collect::<Vec<&str>>().join(" ");
+        assert_eq!(text, "제목 굵은 링크 code 하나 둘");
+
+        assert_eq!(first_image_url(source), Some("/a.png".to_owned()));
+        assert_eq!(first_image_url("`![코드](/b.png)` ![표지]( <https://cdn.example.com/c d.png> )"), Some("https://cdn.example.com/c d.png".to_owned()));
+        assert_eq!(first_image_url("이미지 없음"), None);
+    }
+
+    #[test]
+    fn sanitize_test() {
+        let source = "<script>alert(1)</script>\n\n<a href=\"javascript:alert(1)\" onclick=\"x()\">링크</a> <img src=x onerror=\"x()\">\n\n<input type=\"text\" value=\"a\">\n\n<p style=\"color: red\">빨강</p>";
+        let html = renderer().render(source).html;
+
+        assert!(!html.contains("script"));
+        assert!(!html.contains("javascript"));
+        assert!(!html.contains("onclick"));
+        assert!(!html.contains("onerror"));
+        assert!(!html.contains("type=\"text\""));
+        assert!(!html.contains("style"));
+        assert!(html.contains("링크"));
+
+        // 사용자가 쓴 id, class 로 페이지의 다른 요소를 가리지 못한다.
+        let html = renderer().render("<div id=\"login\" class=\"modal hl-x\">a</div>\n\n<div class=\"modal\">b</div>\n\n[목차](#intro)").html;
+        assert!(html.contains(r#"<div id="user-content-login" class="hl-x">a</div>"#));
+        assert!(html.contains("<div>b</div>"));
+        assert!(html.contains(r##"<a href="#user-content-intro" rel="noopener noreferrer">목차</a>"##));
+    }
+
+    #[test]
+    fn render_post_cache_test() {
+        let renderer = renderer();
+        let post_id = uuid::Uuid::new_v4();
+        let updated_at = chrono::NaiveDateTime::parse_from_str("2024-03-01 09:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+
+        assert_eq!(renderer.render_post(post_id, updated_at, "첫 본문").html, "<p>첫 본문</p>\n");
+        // 같은 버전이면 본문을 다시 렌더링하지 않는다.
+        assert_eq!(renderer.render_post(post_id, updated_at, "무시").html, "<p>첫 본문</p>\n");
+        let modified_at = updated_at + chrono::Duration::seconds(1);
+        assert_eq!(renderer.render_post(post_id, modified_at, "고친 본문").html, "<p>고친 본문</p>\n");
+    }
+}
@@ -1,3 +1,4 @@
 pub mod cookie_middleware;
+pub mod rate_limit;
 pub mod security;
 pub use security::*;
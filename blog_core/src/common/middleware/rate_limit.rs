@@ -0,0 +1,75 @@
+use crate::{
+    common::{
+        error::error_code::ErrorCode,
+        rate_limiter::{RateLimitDecision, RateLimiter},
+        ClientInfo,
+    },
+    di::AppContext,
+};
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::{HeaderMap, HeaderValue},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use shaku::HasComponent;
+use std::sync::Arc;
+
+use super::security::SecurityContext;
+
+/// 라우터에 거는 요청 수 제한. 정책 이름은 `rate_limit_policies` 설정의 이름이다.
+/// `from_fn_with_state(RateLimit::policy(ctx.clone(), "login"), rate_limit)` 형태로 건다.
+#[derive(Clone)]
+pub struct RateLimit {
+    ctx: Arc<AppContext>,
+    policy: &'static str,
+}
+
+impl RateLimit {
+    pub fn policy(ctx: Arc<AppContext>, policy: &'static str) -> Self {
+        Self { ctx, policy }
+    }
+}
+
+fn set_rate_limit_headers(headers: &mut HeaderMap, decision: &RateLimitDecision) {
+    headers.insert("RateLimit-Limit", HeaderValue::from(decision.limit));
+    headers.insert("RateLimit-Remaining", HeaderValue::from(decision.remaining));
+    headers.insert("RateLimit-Reset", HeaderValue::from(decision.reset_after));
+    if !decision.allowed {
+        headers.insert("Retry-After", HeaderValue::from(decision.retry_after));
+    }
+}
+
+/// 로그인한 요청은 회원 ID 로, 그렇지 않으면 IP 로 센다.
+pub async fn rate_limit(
+    State(rate_limit): State<RateLimit>,
+    client: ClientInfo,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let rate_limiter: &dyn RateLimiter = rate_limit.ctx.resolve_ref();
+    let Some(policy) = rate_limiter.policy(rate_limit.policy) else {
+        return next.run(req).await;
+    };
+
+    let subject = req
+        .extensions()
+        .get::<SecurityContext>()
+        .and_then(|ctx| ctx.get_member())
+        .map(|member| format!("member:{}", member.id))
+        .or_else(|| client.ip_address.map(|ip| format!("ip:{}", ip)))
+        .unwrap_or_else(|| "unknown".to_owned());
+    let decision = rate_limiter
+        .acquire(&format!("{}:{}", rate_limit.policy, subject), &policy)
+        .await;
+
+    let mut response = if decision.allowed {
+        next.run(req).await
+    } else {
+        tracing::warn!("Rate limit exceeded: policy={}, subject={}", rate_limit.policy, subject);
+        ErrorCode::TooManyRequests.into_response()
+    };
+    set_rate_limit_headers(response.headers_mut(), &decision);
+    response
+}
@@ -1,4 +1,6 @@
 use crate::{
+    application::auth::{PersonalAccessTokenUseCase, SessionQueryUseCase, PERSONAL_ACCESS_TOKEN_PREFIX},
+    config::ConfigProvider,
     common::{
         error::error_code::ErrorCode,
         jwt::{AccessTokenClaims, JwtService},
@@ -21,23 +23,37 @@ pub struct LoginMember {
     pub id: uuid::Uuid,
     pub nickname: String,
     pub email: String,
-    pub role: String,
+    pub roles: Vec<SecurityRole>,
     pub is_activated: bool,
+    pub session_id: Option<uuid::Uuid>,
+    /// 2단계 인증을 거쳐 로그인했는지 여부
+    pub mfa_authenticated: bool,
+    /// 개인 액세스 토큰으로 인증한 경우 토큰에 허용된 권한. 로그인 세션이면 None
+    pub token_scopes: Option<Vec<Permission>>,
 }
 
 #[allow(dead_code)]
 impl LoginMember {
 
     pub fn is_admin(&self) -> bool {
-        self.role == "ROLE_ADMIN"
+        self.roles.contains(&SecurityRole::Admin)
     }
 
     pub fn is_member(&self) -> bool {
-        self.role == "ROLE_MEMBER"
+        self.roles.contains(&SecurityRole::Member)
     }
 
     pub fn is_anonymous(&self) -> bool {
-        self.role == "ROLE_ANONYMOUS"
+        self.roles.is_empty()
+    }
+
+    /// 역할이 가진 권한이라도 개인 액세스 토큰이면 토큰에 허용된 권한만 쓸 수 있다.
+    pub fn has_permission(&self, permission: Permission) -> bool {
+        let in_scope = self
+            .token_scopes
+            .as_ref()
+            .is_none_or(|scopes| scopes.contains(&permission));
+        in_scope && self.roles.iter().any(|role| role.has_permission(permission))
     }
 
     pub fn from_claims(claims: AccessTokenClaims) -> Self {
@@ -45,13 +61,21 @@ impl LoginMember {
             id: uuid::Uuid::parse_str(&claims.sub).unwrap(),
             nickname: claims.nickname,
             email: claims.email,
-            role: claims.roles[0].clone(),
+            roles: claims
+                .roles
+                .iter()
+                .filter_map(|role| SecurityRole::from_claim(role))
+                .collect(),
             is_activated: claims.is_activated,
+            session_id: uuid::Uuid::parse_str(&claims.sid).ok(),
+            mfa_authenticated: claims.mfa,
+            token_scopes: None,
         }
     }
 }
 
-#[derive(Eq, PartialEq, Debug, PartialOrd, Ord, Clone)]
+/// 선언 순서가 곧 권한 계층이다. 상위 역할은 하위 역할의 권한을 모두 가진다.
+#[derive(Eq, PartialEq, Debug, PartialOrd, Ord, Clone, Copy)]
 #[allow(dead_code)]
 pub enum SecurityRole {
     Anonymouse,
@@ -59,18 +83,102 @@ pub enum SecurityRole {
     Admin,
 }
 
+impl SecurityRole {
+    pub fn from_claim(role: &str) -> Option<Self> {
+        match role {
+            "ROLE_MEMBER" => Some(Self::Member),
+            "ROLE_ADMIN" => Some(Self::Admin),
+            _ => None,
+        }
+    }
+
+    /// 해당 역할에서 새로 부여되는 권한 (하위 역할의 권한은 포함하지 않는다)
+    fn granted_permissions(&self) -> &'static [Permission] {
+        match self {
+            Self::Anonymouse => &[],
+            Self::Member => &[Permission::CommentWrite],
+            Self::Admin => &[
+                Permission::PostWrite,
+                Permission::BoardManage,
+                Permission::MemberAdmin,
+            ],
+        }
+    }
+
+    pub fn has_permission(&self, permission: Permission) -> bool {
+        [Self::Anonymouse, Self::Member, Self::Admin]
+            .iter()
+            .filter(|role| *role <= self)
+            .any(|role| role.granted_permissions().contains(&permission))
+    }
+}
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub enum Permission {
+    CommentWrite,
+    PostWrite,
+    BoardManage,
+    MemberAdmin,
+}
+
+impl Permission {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::CommentWrite => "comment:write",
+            Self::PostWrite => "post:write",
+            Self::BoardManage => "board:manage",
+            Self::MemberAdmin => "member:admin",
+        }
+    }
+
+    pub fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "comment:write" => Some(Self::CommentWrite),
+            "post:write" => Some(Self::PostWrite),
+            "board:manage" => Some(Self::BoardManage),
+            "member:admin" => Some(Self::MemberAdmin),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct SecurityContext {
     roles: Vec<SecurityRole>,
     member: Option<LoginMember>,
 }
 
+impl SecurityContext {
+    /// 로그인하지 않은 요청이면 None
+    pub fn get_member(&self) -> Option<&LoginMember> {
+        self.member.as_ref()
+    }
+
+    /// 역할로 보호되는 API(회원 정보, 세션, 토큰 관리 등)는 로그인 세션으로만 쓸 수 있다.
+    fn has_role(&self, required: SecurityRole) -> bool {
+        if self.member.as_ref().is_some_and(|member| member.token_scopes.is_some()) {
+            return false;
+        }
+        self.roles.iter().any(|role| *role >= required)
+    }
+
+    fn has_permission(&self, permission: Permission) -> bool {
+        match &self.member {
+            Some(member) => member.has_permission(permission),
+            None => self.roles.iter().any(|role| role.has_permission(permission)),
+        }
+    }
+}
+
 pub async fn jwt_authentication_filter(
     State(ctx): State<Arc<AppContext>>,
     mut req: Request<Body>,
     next: Next,
 ) -> Result<Response<Body>, ErrorCode> {
     let jwt_service: &dyn JwtService = ctx.resolve_ref();
+    let session_usecase: &dyn SessionQueryUseCase = ctx.resolve_ref();
+    let access_token_usecase: &dyn PersonalAccessTokenUseCase = ctx.resolve_ref();
+    let config_provider: &dyn ConfigProvider = ctx.resolve_ref();
 
     // 토큰을 우선 추출해야한다. 토큰이 없다면 인증 절차를 거치지 않는다.
     let token = req
@@ -90,66 +198,105 @@ pub async fn jwt_authentication_filter(
         return Ok(next.run(req).await);
     }
 
-    // 토큰이 있다면, 토큰을 검증한다.
-    let claims = jwt_service.decode_access_token(token.as_ref().unwrap())?;
-    // 토큰에서 Claim으로 변환 성공한 경우 인증 정보를 입력한다.
-    let login_member = LoginMember::from_claims(claims);
+    let token = token.unwrap();
+    let mut login_member = if token.starts_with(PERSONAL_ACCESS_TOKEN_PREFIX) {
+        // 개인 액세스 토큰은 DB 에 저장된 해시와 비교해 검증한다.
+        access_token_usecase.authenticate(&token).await?
+    } else {
+        // 토큰이 있다면, 토큰을 검증한다.
+        let claims = jwt_service.decode_access_token(&token)?;
+        // 토큰에서 Claim으로 변환 성공한 경우 인증 정보를 입력한다.
+        let login_member = LoginMember::from_claims(claims);
+
+        // 토큰이 유효하더라도 발급된 세션이 폐기되었다면 거부한다.
+        let session_id = login_member.session_id.ok_or(ErrorCode::InvalidJwtToken)?;
+        if !session_usecase.is_active(session_id).await? {
+            return Err(ErrorCode::RevokedSession);
+        }
+        login_member
+    };
+
+    // 관리자에게 2단계 인증이 필수라면 2단계 인증 없이 로그인한 관리자는 회원 권한만 가진다.
+    // (2단계 인증을 등록한 뒤 다시 로그인하면 관리자 권한을 쓸 수 있다)
+    if config_provider.get().mfa_required_for_admin && !login_member.mfa_authenticated {
+        login_member.roles = login_member
+            .roles
+            .iter()
+            .map(|role| (*role).min(SecurityRole::Member))
+            .collect();
+    }
 
-    // 로그인한 사용자의 권한을 SecurityContext에 저장한다.
+    // 토큰의 roles claim 에 담긴 권한을 SecurityContext에 저장한다.
     req.extensions_mut()
         .insert::<SecurityContext>(SecurityContext {
-            roles: vec![SecurityRole::Member],
+            roles: login_member.roles.clone(),
             member: Some(login_member.clone()),
         });
 
     Ok(next.run(req).await)
 }
 
-pub async fn with_role_member(mut req: Request<Body>, next: Next) -> Result<Response, ErrorCode> {
-    info!("with_role_member");
+fn authorize(req: &mut Request<Body>, satisfied: impl Fn(&SecurityContext) -> bool) -> Result<(), ErrorCode> {
     let exts = req.extensions_mut();
     let ctx = exts
         .get::<SecurityContext>()
         .ok_or(ErrorCode::Unauthorized)?;
 
-    let mut satisfied = false;
+    let member = match &ctx.member {
+        Some(member) => member.clone(),
+        // 로그인하지 않은 경우 401 Unauthorized 응답을 반환합니다.
+        None => return Err(ErrorCode::Unauthorized),
+    };
 
-    for role in &ctx.roles {
-        if *role >= SecurityRole::Member {
-            satisfied = true;
-            break;
-        }
+    if !satisfied(ctx) {
+        // 권한이 없는 경우 403 응답을 반환합니다.
+        return Err(ErrorCode::NotEnoughPermission);
     }
 
-    if satisfied {
-        exts.insert::<LoginMember>(ctx.member.clone().unwrap());
-        return Ok(next.run(req).await);
-    } else {
-        // 권한이 없는 경우 403 Forbidden 응답을 반환합니다.
-        return Err(ErrorCode::Forbidden);
-    }
+    exts.insert::<LoginMember>(member);
+    Ok(())
+}
+
+pub async fn with_role_member(mut req: Request<Body>, next: Next) -> Result<Response, ErrorCode> {
+    info!("with_role_member");
+    authorize(&mut req, |ctx| ctx.has_role(SecurityRole::Member))?;
+    Ok(next.run(req).await)
 }
 
+#[allow(dead_code)]
 pub async fn with_role_admin(mut req: Request<Body>, next: Next) -> Result<Response, ErrorCode> {
-    let exts = req.extensions_mut();
-    let ctx = exts
-        .get::<SecurityContext>()
-        .ok_or(ErrorCode::Unauthorized)?;
+    authorize(&mut req, |ctx| ctx.has_role(SecurityRole::Admin))?;
+    Ok(next.run(req).await)
+}
 
-    let mut satisfied = false;
+/// `from_fn_with_state(Permission::PostWrite, require_permission)` 형태로 라우터에 건다.
+pub async fn require_permission(
+    State(permission): State<Permission>,
+    mut req: Request<Body>,
+    next: Next,
+) -> Result<Response, ErrorCode> {
+    info!("require_permission: {}", permission.as_str());
+    authorize(&mut req, |ctx| ctx.has_permission(permission))?;
+    Ok(next.run(req).await)
+}
 
-    for role in &ctx.roles {
-        if *role >= SecurityRole::Member {
-            satisfied = true;
-            break;
-        }
+#[cfg(test)]
+mod test {
+    use super::{Permission, SecurityRole};
+
+    #[test]
+    fn role_hierarchy_test() {
+        assert!(SecurityRole::Admin.has_permission(Permission::CommentWrite));
+        assert!(SecurityRole::Admin.has_permission(Permission::BoardManage));
+        assert!(SecurityRole::Member.has_permission(Permission::CommentWrite));
+        assert!(!SecurityRole::Member.has_permission(Permission::PostWrite));
+        assert!(!SecurityRole::Anonymouse.has_permission(Permission::CommentWrite));
     }
 
-    if satisfied {
-        exts.insert::<LoginMember>(ctx.member.clone().unwrap());
-        return Ok(next.run(req).await);
-    } else {
-        // 권한이 없는 경우 403 Forbidden 응답을 반환합니다.
-        return Err(ErrorCode::Forbidden);
+    #[test]
+    fn from_claim_test() {
+        assert_eq!(SecurityRole::from_claim("ROLE_ADMIN"), Some(SecurityRole::Admin));
+        assert_eq!(SecurityRole::from_claim("ROLE_MEMBER"), Some(SecurityRole::Member));
+        assert_eq!(SecurityRole::from_claim("ADMIN"), None);
     }
 }
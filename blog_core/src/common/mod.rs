@@ -1,8 +1,14 @@
 pub mod database;
 pub mod error;
 pub mod jwt;
+pub mod jwt_key;
+pub mod mailer;
+pub mod markdown;
 pub mod middleware;
+pub mod ngram_index;
+pub mod rate_limiter;
 pub mod utility;
+pub mod view_counter;
 pub mod wrapper;
 
 pub use database::*;
@@ -0,0 +1,292 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// 제목에서 찾은 토큰은 본문보다 이만큼 높게 친다.
+const TITLE_WEIGHT: f32 = 2.0;
+/// 스니펫 앞쪽에 남길 글자 수
+const SNIPPET_LEADING_CHARS: usize = 30;
+/// 스니펫 최대 글자 수
+const SNIPPET_CHARS: usize = 120;
+
+/// 색인하는 문서. 파일에는 이 목록만 저장하고 역색인은 불러올 때 다시 만든다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexedDocument {
+    pub id: Uuid,
+    pub title: String,
+    pub contents: String,
+}
+
+/// 토큰 하나가 문서의 제목, 본문에 나온 횟수
+#[derive(Debug, Clone, Copy, Default)]
+struct Posting {
+    title: u32,
+    contents: u32,
+}
+
+fn words(text: &str) -> impl Iterator<Item = Vec<char>> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.chars().flat_map(char::to_lowercase).collect())
+}
+
+/// 문서 토큰. 띄어쓰기 단위 낱말의 글자 하나(unigram)와 연속된 두 글자(bigram)를 모두 만든다.
+/// 조사가 붙거나 복합어인 한국어 낱말도 부분 문자열로 찾을 수 있다. (`게시글을` 에서 `게시`, `시글` …)
+fn document_tokens(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    for word in words(text) {
+        tokens.extend(word.iter().map(|c| c.to_string()));
+        tokens.extend(word.windows(2).map(|pair| pair.iter().collect::<String>()));
+    }
+    tokens
+}
+
+/// 검색어 토큰. 한 글자 낱말은 unigram 으로, 나머지는 bigram 으로 찾는다.
+fn query_tokens(query: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    for word in words(query) {
+        if word.len() == 1 {
+            tokens.push(word[0].to_string());
+        } else {
+            tokens.extend(word.windows(2).map(|pair| pair.iter().collect::<String>()));
+        }
+    }
+    tokens.sort();
+    tokens.dedup();
+    tokens
+}
+
+/// n-gram 역색인. PostgreSQL 없이 게시글 제목과 본문을 찾을 때 쓴다.
+#[derive(Debug, Default)]
+pub struct NgramIndex {
+    documents: HashMap<Uuid, IndexedDocument>,
+    postings: HashMap<String, HashMap<Uuid, Posting>>,
+}
+
+impl NgramIndex {
+    pub fn from_documents(documents: Vec<IndexedDocument>) -> Self {
+        let mut index = NgramIndex::default();
+        for document in documents {
+            index.upsert(document);
+        }
+        index
+    }
+
+    /// 파일이 없으면 빈 색인을 돌려준다.
+    pub fn load(path: &Path) -> std::io::Result<Self> {
+        match std::fs::read(path) {
+            Ok(bytes) => {
+                let documents: Vec<IndexedDocument> = serde_json::from_slice(&bytes)?;
+                Ok(Self::from_documents(documents))
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(NgramIndex::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// `documents()` 로 떠 둔 문서 목록을 저장한다. 색인을 잠근 채로 파일을 쓰지 않도록 따로 둔다.
+    /// 쓰는 도중에 멈춰도 기존 파일이 깨지지 않도록 임시 파일에 쓴 뒤 바꿔치기한다.
+    pub fn save_documents(path: &Path, documents: &[IndexedDocument]) -> std::io::Result<()> {
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let temp_path = path.with_extension("tmp");
+        std::fs::write(&temp_path, serde_json::to_vec(documents)?)?;
+        std::fs::rename(temp_path, path)
+    }
+
+    pub fn documents(&self) -> Vec<IndexedDocument> {
+        self.documents.values().cloned().collect()
+    }
+
+    pub fn document(&self, id: Uuid) -> Option<&IndexedDocument> {
+        self.documents.get(&id)
+    }
+
+    pub fn document_count(&self) -> usize {
+        self.documents.len()
+    }
+
+    pub fn upsert(&mut self, document: IndexedDocument) {
+        self.remove(document.id);
+
+        let id = document.id;
+        for token in document_tokens(&document.title) {
+            self.postings.entry(token).or_default().entry(id).or_default().title += 1;
+        }
+        for token in document_tokens(&document.contents) {
+            self.postings.entry(token).or_default().entry(id).or_default().contents += 1;
+        }
+        self.documents.insert(id, document);
+    }
+
+    pub fn remove(&mut self, id: Uuid) {
+        let Some(document) = self.documents.remove(&id) else {
+            return;
+        };
+
+        let tokens: HashSet<String> = document_tokens(&document.title)
+            .into_iter()
+            .chain(document_tokens(&document.contents))
+            .collect();
+        for token in tokens {
+            if let Some(posting) = self.postings.get_mut(&token) {
+                posting.remove(&id);
+                if posting.is_empty() {
+                    self.postings.remove(&token);
+                }
+            }
+        }
+    }
+
+    /// 검색어의 토큰을 모두 가진 문서를 점수가 높은 순으로 돌려준다.
+    /// 점수는 토큰마다 (제목 빈도 × 2 + 본문 빈도) × idf 를 더한 값이다.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<(Uuid, f32)> {
+        let tokens = query_tokens(query);
+        if tokens.is_empty() {
+            return Vec::new();
+        }
+
+        let mut postings = Vec::with_capacity(tokens.len());
+        for token in &tokens {
+            match self.postings.get(token) {
+                Some(posting) => postings.push(posting),
+                None => return Vec::new(),
+            }
+        }
+        // 가장 짧은 목록에서 시작해 나머지 목록에 모두 있는 문서만 남긴다.
+        postings.sort_by_key(|posting| posting.len());
+
+        let total = self.documents.len() as f32;
+        let mut hits: Vec<(Uuid, f32)> = postings[0]
+            .keys()
+            .filter(|id| postings[1..].iter().all(|posting| posting.contains_key(id)))
+            .map(|id| {
+                let score = postings
+                    .iter()
+                    .map(|posting| {
+                        let idf = (1.0 + total / posting.len() as f32).ln();
+                        let frequency = posting[id];
+                        (frequency.title as f32 * TITLE_WEIGHT + frequency.contents as f32) * idf
+                    })
+                    .sum();
+                (*id, score)
+            })
+            .collect();
+
+        hits.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        hits.truncate(limit);
+        hits
+    }
+
+    /// 검색어 낱말과 일치한 부분을 start, stop 문자로 감싼 제목과 본문 스니펫을 만든다.
+    pub fn highlight(&self, id: Uuid, query: &str, start: char, stop: char) -> Option<(String, String)> {
+        let document = self.documents.get(&id)?;
+        let mut query_words: Vec<Vec<char>> = words(query).collect();
+        // 긴 낱말을 먼저 맞춰 본다.
+        query_words.sort_by_key(|word| std::cmp::Reverse(word.len()));
+
+        let title = mark(&document.title.chars().collect::<Vec<char>>(), &query_words, start, stop);
+
+        let contents: Vec<char> = document.contents.chars().collect();
+        let first_match = find_matches(&contents, &query_words).first().map(|(from, _)| *from).unwrap_or(0);
+        let from = first_match.saturating_sub(SNIPPET_LEADING_CHARS);
+        let to = (from + SNIPPET_CHARS).min(contents.len());
+        let mut snippet = mark(&contents[from..to], &query_words, start, stop);
+        if from > 0 {
+            snippet.insert_str(0, "… ");
+        }
+        if to < contents.len() {
+            snippet.push_str(" …");
+        }
+
+        Some((title, snippet))
+    }
+}
+
+/// 대소문자를 가리지 않고 낱말이 나온 위치를 겹치지 않게 찾는다. (시작, 끝) 글자 위치 목록이다.
+fn find_matches(text: &[char], query_words: &[Vec<char>]) -> Vec<(usize, usize)> {
+    let lowered: Vec<char> = text
+        .iter()
+        .map(|c| c.to_lowercase().next().unwrap_or(*c))
+        .collect();
+
+    let mut matches = Vec::new();
+    let mut position = 0;
+    while position < lowered.len() {
+        let matched = query_words
+            .iter()
+            .find(|word| !word.is_empty() && lowered[position..].starts_with(word));
+        match matched {
+            Some(word) => {
+                matches.push((position, position + word.len()));
+                position += word.len();
+            }
+            None => position += 1,
+        }
+    }
+    matches
+}
+
+fn mark(text: &[char], query_words: &[Vec<char>], start: char, stop: char) -> String {
+    let mut marked = String::with_capacity(text.len());
+    let mut position = 0;
+    for (from, to) in find_matches(text, query_words) {
+        marked.extend(&text[position..from]);
+        marked.push(start);
+        marked.extend(&text[from..to]);
+        marked.push(stop);
+        position = to;
+    }
+    marked.extend(&text[position..]);
+    marked
+}
+
+#[cfg(test)]
+mod test {
+    use uuid::Uuid;
+
+    use super::{IndexedDocument, NgramIndex};
+
+    fn document(title: &str, contents: &str) -> IndexedDocument {
+        IndexedDocument {
+            id: Uuid::new_v4(),
+            title: title.to_owned(),
+            contents: contents.to_owned(),
+        }
+    }
+
+    #[test]
+    fn search_test() {
+        let rust = document("러스트 비동기 프로그래밍", "tokio 런타임으로 게시글을 처리합니다.");
+        let java = document("자바 스프링", "러스트와 비교해 봅니다.");
+        let mut index = NgramIndex::from_documents(vec![rust.clone(), java.clone()]);
+
+        // 조사가 붙은 낱말도 부분 문자열로 찾는다.
+        let hits = index.search("게시글", 10);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].0, rust.id);
+
+        // 제목에서 찾은 문서가 본문에서 찾은 문서보다 앞선다.
+        let hits = index.search("러스트", 10);
+        assert_eq!(hits.iter().map(|hit| hit.0).collect::<Vec<Uuid>>(), vec![rust.id, java.id]);
+
+        assert!(index.search("Tokio 스프링", 10).is_empty());
+
+        index.remove(rust.id);
+        assert_eq!(index.search("러스트", 10).len(), 1);
+        assert!(index.search("게시글", 10).is_empty());
+    }
+
+    #[test]
+    fn highlight_test() {
+        let rust = document("Rust 게시글", "본문에 rust 가 두 번 나옵니다. RUST");
+        let index = NgramIndex::from_documents(vec![rust.clone()]);
+
+        let (title, snippet) = index.highlight(rust.id, "rust", '[', ']').unwrap();
+        assert_eq!(title, "[Rust] 게시글");
+        assert_eq!(snippet, "본문에 [rust] 가 두 번 나옵니다. [RUST]");
+    }
+}
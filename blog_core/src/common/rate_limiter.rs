@@ -0,0 +1,308 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use redis::aio::ConnectionManager;
+use shaku::{Component, Interface};
+use tokio::sync::OnceCell;
+
+use crate::config::{AppConfig, ConfigProvider};
+
+/// 가득 찬(오래 쓰이지 않은) 메모리 버킷을 정리하는 주기
+const MEMORY_BUCKET_PRUNE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Redis 에 연결하지 못하면 이 시간 동안은 다시 시도하지 않고 메모리 버킷을 쓴다.
+const REDIS_RETRY_BACKOFF: Duration = Duration::from_secs(30);
+
+/// 토큰 버킷 정책. `capacity` 개까지 한꺼번에 요청할 수 있고, `period_seconds` 동안 `capacity` 개가 고르게 다시 채워진다.
+/// 설정에서는 `이름=capacity/period_seconds` 형식으로 적는다. (`login=10/60`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimitPolicy {
+    pub capacity: u32,
+    pub period_seconds: u64,
+}
+
+impl RateLimitPolicy {
+    pub fn parse(value: &str) -> Option<(String, Self)> {
+        let (name, spec) = value.trim().split_once('=')?;
+        let (capacity, period_seconds) = spec.split_once('/')?;
+        let policy = RateLimitPolicy {
+            capacity: capacity.trim().parse().ok()?,
+            period_seconds: period_seconds.trim().parse().ok()?,
+        };
+        if policy.capacity == 0 || policy.period_seconds == 0 {
+            return None;
+        }
+        Some((name.trim().to_owned(), policy))
+    }
+
+    /// 초당 다시 채워지는 토큰 수
+    fn refill_rate(&self) -> f64 {
+        self.capacity as f64 / self.period_seconds as f64
+    }
+}
+
+/// 요청 하나에 대한 판정. 응답의 `RateLimit-*`, `Retry-After` 헤더에 그대로 쓴다.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimitDecision {
+    pub allowed: bool,
+    pub limit: u32,
+    pub remaining: u32,
+    /// 버킷이 가득 찰 때까지 남은 시간(초)
+    pub reset_after: u64,
+    /// 거절된 경우 다음 요청이 허용될 때까지 남은 시간(초)
+    pub retry_after: u64,
+}
+
+impl RateLimitDecision {
+    fn new(allowed: bool, tokens: f64, policy: &RateLimitPolicy) -> Self {
+        let rate = policy.refill_rate();
+        RateLimitDecision {
+            allowed,
+            limit: policy.capacity,
+            remaining: tokens.floor().max(0.0) as u32,
+            reset_after: ((policy.capacity as f64 - tokens) / rate).ceil().max(0.0) as u64,
+            retry_after: if allowed { 0 } else { ((1.0 - tokens) / rate).ceil().max(1.0) as u64 },
+        }
+    }
+}
+
+/// 남은 토큰과 마지막 갱신 후 지난 시간으로 토큰 하나를 꺼낸다. 반환값은 (남은 토큰, 허용 여부) 이다.
+fn take_token(tokens: f64, elapsed_seconds: f64, policy: &RateLimitPolicy) -> (f64, bool) {
+    let tokens = (tokens + elapsed_seconds.max(0.0) * policy.refill_rate()).min(policy.capacity as f64);
+    if tokens >= 1.0 {
+        (tokens - 1.0, true)
+    } else {
+        (tokens, false)
+    }
+}
+
+/// Redis 에서 버킷을 원자적으로 갱신한다. 인스턴스마다 시계가 다를 수 있으므로 Redis 의 시각을 쓴다.
+const REDIS_TOKEN_BUCKET_SCRIPT: &str = r#"
+local capacity = tonumber(ARGV[1])
+local rate = tonumber(ARGV[2])
+local ttl = tonumber(ARGV[3])
+local time = redis.call('TIME')
+local now = tonumber(time[1]) + tonumber(time[2]) / 1000000
+local bucket = redis.call('HMGET', KEYS[1], 'tokens', 'ts')
+local tokens = tonumber(bucket[1]) or capacity
+local ts = tonumber(bucket[2]) or now
+tokens = math.min(capacity, tokens + math.max(0, now - ts) * rate)
+local allowed = 0
+if tokens >= 1 then
+    tokens = tokens - 1
+    allowed = 1
+end
+redis.call('HSET', KEYS[1], 'tokens', tostring(tokens), 'ts', tostring(now))
+redis.call('EXPIRE', KEYS[1], ttl)
+return {allowed, tostring(tokens)}
+"#;
+
+/// 요청 수 제한. 버킷 저장소는 `rate_limit_backend` 설정으로 고른다.
+/// - `memory` : 프로세스 메모리에 둔다. (인스턴스마다 따로 센다)
+/// - `redis` : `redis_host`, `redis_port` 의 Redis 에 두어 여러 인스턴스가 함께 센다.
+///   Redis 에 연결할 수 없으면 메모리 버킷으로 대신한다.
+#[async_trait::async_trait]
+pub trait RateLimiter: Interface {
+    /// 설정된 정책을 찾는다. 설정에 없는 정책은 제한하지 않는다.
+    fn policy(&self, name: &str) -> Option<RateLimitPolicy>;
+
+    async fn acquire(&self, key: &str, policy: &RateLimitPolicy) -> RateLimitDecision;
+}
+
+pub(crate) struct TokenBucket {
+    tokens: f64,
+    updated_at: Instant,
+    /// 이 시간 동안 쓰이지 않으면 가득 차므로 없는 것과 같다.
+    period: Duration,
+}
+
+#[derive(Default)]
+pub(crate) struct MemoryBuckets {
+    buckets: HashMap<String, TokenBucket>,
+    pruned_at: Option<Instant>,
+}
+
+impl MemoryBuckets {
+    /// 정리 주기가 지났을 때만 전체를 훑는다.
+    fn prune(&mut self, now: Instant) {
+        let pruned_at = *self.pruned_at.get_or_insert(now);
+        if now.duration_since(pruned_at) < MEMORY_BUCKET_PRUNE_INTERVAL {
+            return;
+        }
+        self.buckets.retain(|_, bucket| now.duration_since(bucket.updated_at) < bucket.period);
+        self.pruned_at = Some(now);
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = RateLimiter)]
+pub struct RateLimiterImpl {
+    #[shaku(inject)]
+    pub(crate) config: Arc<dyn ConfigProvider>,
+    #[shaku(default)]
+    buckets: Mutex<MemoryBuckets>,
+    #[shaku(default)]
+    policies: OnceLock<HashMap<String, RateLimitPolicy>>,
+    #[shaku(default)]
+    redis: OnceCell<ConnectionManager>,
+    #[shaku(default)]
+    redis_retry_at: Mutex<Option<Instant>>,
+}
+
+impl RateLimiterImpl {
+    fn acquire_memory(&self, key: &str, policy: &RateLimitPolicy) -> RateLimitDecision {
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().unwrap();
+        buckets.prune(now);
+
+        let bucket = buckets.buckets.entry(key.to_owned()).or_insert(TokenBucket {
+            tokens: policy.capacity as f64,
+            updated_at: now,
+            period: Duration::from_secs(policy.period_seconds),
+        });
+        let (tokens, allowed) = take_token(
+            bucket.tokens,
+            now.duration_since(bucket.updated_at).as_secs_f64(),
+            policy,
+        );
+        bucket.tokens = tokens;
+        bucket.updated_at = now;
+
+        RateLimitDecision::new(allowed, tokens, policy)
+    }
+
+    async fn redis_connection(&self, config: &AppConfig) -> redis::RedisResult<ConnectionManager> {
+        self.redis
+            .get_or_try_init(|| async {
+                let client = redis::Client::open(format!("redis://{}:{}/", config.redis_host, config.redis_port))?;
+                client.get_connection_manager().await
+            })
+            .await
+            .cloned()
+    }
+
+    /// 최근에 Redis 요청이 실패했다면 다시 시도할 때까지 Redis 를 건너뛴다.
+    fn redis_backing_off(&self) -> bool {
+        self.redis_retry_at
+            .lock()
+            .unwrap()
+            .is_some_and(|retry_at| Instant::now() < retry_at)
+    }
+
+    fn back_off_redis(&self) {
+        *self.redis_retry_at.lock().unwrap() = Some(Instant::now() + REDIS_RETRY_BACKOFF);
+    }
+
+    async fn acquire_redis(&self, config: &AppConfig, key: &str, policy: &RateLimitPolicy) -> redis::RedisResult<RateLimitDecision> {
+        let mut connection = self.redis_connection(config).await?;
+        let (allowed, tokens): (i64, String) = redis::Script::new(REDIS_TOKEN_BUCKET_SCRIPT)
+            .key(format!("rate_limit:{}", key))
+            .arg(policy.capacity)
+            .arg(policy.refill_rate())
+            .arg(policy.period_seconds)
+            .invoke_async(&mut connection)
+            .await?;
+
+        Ok(RateLimitDecision::new(allowed == 1, tokens.parse().unwrap_or(0.0), policy))
+    }
+}
+
+#[async_trait::async_trait]
+impl RateLimiter for RateLimiterImpl {
+    fn policy(&self, name: &str) -> Option<RateLimitPolicy> {
+        self.policies
+            .get_or_init(|| {
+                self.config
+                    .get()
+                    .rate_limit_policies
+                    .iter()
+                    .filter_map(|policy| RateLimitPolicy::parse(policy))
+                    .collect()
+            })
+            .get(name)
+            .copied()
+    }
+
+    async fn acquire(&self, key: &str, policy: &RateLimitPolicy) -> RateLimitDecision {
+        let config = self.config.get();
+
+        match config.rate_limit_backend.as_str() {
+            "redis" if self.redis_backing_off() => self.acquire_memory(key, policy),
+            "redis" => match self.acquire_redis(&config, key, policy).await {
+                Ok(decision) => decision,
+                Err(e) => {
+                    tracing::warn!("Redis rate limiter unavailable, falling back to memory for {:?}: {:?}", REDIS_RETRY_BACKOFF, e);
+                    self.back_off_redis();
+                    self.acquire_memory(key, policy)
+                }
+            },
+            "memory" => self.acquire_memory(key, policy),
+            backend => {
+                tracing::error!("Unknown rate limit backend: {}, falling back to memory", backend);
+                self.acquire_memory(key, policy)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use super::{take_token, MemoryBuckets, RateLimitDecision, RateLimitPolicy, TokenBucket};
+
+    #[test]
+    fn parse_policy_test() {
+        assert_eq!(
+            RateLimitPolicy::parse(" login=10/60 "),
+            Some(("login".to_owned(), RateLimitPolicy { capacity: 10, period_seconds: 60 }))
+        );
+        assert_eq!(RateLimitPolicy::parse("login=10"), None);
+        assert_eq!(RateLimitPolicy::parse("login=0/60"), None);
+    }
+
+    #[test]
+    fn token_bucket_test() {
+        let policy = RateLimitPolicy { capacity: 2, period_seconds: 60 };
+
+        let (tokens, allowed) = take_token(2.0, 0.0, &policy);
+        assert!(allowed);
+        let (tokens, allowed) = take_token(tokens, 0.0, &policy);
+        assert!(allowed);
+        let (tokens, allowed) = take_token(tokens, 0.0, &policy);
+        assert!(!allowed);
+
+        let decision = RateLimitDecision::new(allowed, tokens, &policy);
+        assert_eq!(decision.remaining, 0);
+        assert_eq!(decision.retry_after, 30);
+        assert_eq!(decision.reset_after, 60);
+
+        // 30초가 지나면 토큰 하나가 다시 채워진다.
+        let (_, allowed) = take_token(tokens, 30.0, &policy);
+        assert!(allowed);
+    }
+
+    #[test]
+    fn prune_memory_buckets_test() {
+        let now = Instant::now();
+        let mut buckets = MemoryBuckets::default();
+        for (key, period) in [("login", 60), ("signup", 3600)] {
+            buckets.buckets.insert(key.to_owned(), TokenBucket {
+                tokens: 0.0,
+                updated_at: now,
+                period: Duration::from_secs(period),
+            });
+        }
+
+        // 정리 주기가 지나기 전에는 훑지 않는다.
+        buckets.prune(now);
+        buckets.prune(now + Duration::from_secs(59));
+        assert_eq!(buckets.buckets.len(), 2);
+
+        // 버킷마다 자기 정책의 기간이 지났는지로 정리한다.
+        buckets.prune(now + Duration::from_secs(61));
+        assert!(buckets.buckets.contains_key("signup"));
+        assert!(!buckets.buckets.contains_key("login"));
+    }
+}
@@ -0,0 +1,27 @@
+use aes_gcm::aead::{Aead, AeadCore, OsRng};
+use aes_gcm::{Aes256Gcm, Nonce};
+
+/// AES-GCM nonce 길이(96bit)
+const NONCE_LEN: usize = 12;
+
+/// 새 nonce 로 암호화하고 `nonce || 암호문` 을 반환한다.
+pub(crate) fn seal(cipher: &Aes256Gcm, plain: &[u8]) -> Vec<u8> {
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plain)
+        .expect("AES-GCM encryption failed");
+
+    let mut payload = nonce.to_vec();
+    payload.extend_from_slice(&ciphertext);
+    payload
+}
+
+/// `seal` 로 만든 값을 복호화한다. 변조되었거나 다른 키로 암호화된 값이면 None 을 반환한다.
+pub(crate) fn open(cipher: &Aes256Gcm, payload: &[u8]) -> Option<Vec<u8>> {
+    if payload.len() <= NONCE_LEN {
+        return None;
+    }
+
+    let (nonce, ciphertext) = payload.split_at(NONCE_LEN);
+    cipher.decrypt(Nonce::from_slice(nonce), ciphertext).ok()
+}
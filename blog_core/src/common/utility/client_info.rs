@@ -0,0 +1,128 @@
+use std::convert::Infallible;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+
+use axum::extract::{ConnectInfo, FromRequestParts};
+use axum::http::request::Parts;
+use axum::http::HeaderMap;
+use ipnet::IpNet;
+
+/// 요청을 보낸 클라이언트의 기기/네트워크 정보.
+#[derive(Debug, Clone, Default)]
+pub struct ClientInfo {
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+}
+
+/// X-Forwarded-For, X-Real-IP 를 믿을 프록시 주소 목록.
+/// 라우터에 `Extension` 으로 걸어 두면 `ClientInfo` 추출 시 사용한다.
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProxies(Arc<Vec<IpNet>>);
+
+impl TrustedProxies {
+    /// IP 또는 CIDR 목록을 읽는다. 잘못된 값은 기동 시점에 드러나도록 panic 한다.
+    pub fn parse(proxies: &[String]) -> Self {
+        let proxies = proxies
+            .iter()
+            .map(|proxy| proxy.trim())
+            .filter(|proxy| !proxy.is_empty())
+            .map(|proxy| {
+                proxy.parse::<IpNet>()
+                    .or_else(|_| proxy.parse::<IpAddr>().map(IpNet::from))
+                    .unwrap_or_else(|_| panic!("Invalid trusted proxy: {}", proxy))
+            })
+            .collect();
+        Self(Arc::new(proxies))
+    }
+
+    pub fn contains(&self, ip: &IpAddr) -> bool {
+        self.0.iter().any(|proxy| proxy.contains(ip))
+    }
+}
+
+impl ClientInfo {
+    /// 소켓 주소가 믿을 수 있는 프록시일 때만 X-Forwarded-For, X-Real-IP 를 확인한다.
+    /// X-Forwarded-For 는 오른쪽부터 프록시를 건너뛰고 처음 만나는 주소를 클라이언트로 본다.
+    /// 헤더를 믿을 수 없거나 올바른 IP 가 아니면 소켓 주소를 사용한다.
+    pub fn from_headers(headers: &HeaderMap, remote_addr: Option<SocketAddr>, trusted_proxies: &TrustedProxies) -> Self {
+        let user_agent = headers
+            .get("User-Agent")
+            .and_then(|value| value.to_str().ok())
+            .map(String::from);
+
+        let remote_ip = remote_addr.map(|addr| addr.ip());
+        let forwarded_ip = remote_ip
+            .filter(|ip| trusted_proxies.contains(ip))
+            .and_then(|_| forwarded_client_ip(headers, trusted_proxies));
+
+        ClientInfo {
+            user_agent,
+            ip_address: forwarded_ip.or(remote_ip).map(|ip| ip.to_string()),
+        }
+    }
+}
+
+fn forwarded_client_ip(headers: &HeaderMap, trusted_proxies: &TrustedProxies) -> Option<IpAddr> {
+    if let Some(forwarded_for) = headers.get("X-Forwarded-For").and_then(|value| value.to_str().ok()) {
+        for hop in forwarded_for.rsplit(',') {
+            let ip = hop.trim().parse::<IpAddr>().ok()?;
+            if !trusted_proxies.contains(&ip) {
+                return Some(ip);
+            }
+        }
+        return None;
+    }
+
+    headers
+        .get("X-Real-IP")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<IpAddr>().ok())
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for ClientInfo {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let remote_addr = parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| *addr);
+        let trusted_proxies = parts
+            .extensions
+            .get::<TrustedProxies>()
+            .cloned()
+            .unwrap_or_default();
+
+        Ok(ClientInfo::from_headers(&parts.headers, remote_addr, &trusted_proxies))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ClientInfo, TrustedProxies};
+    use axum::http::HeaderMap;
+
+    #[test]
+    fn from_headers_test() {
+        let proxies = TrustedProxies::parse(&["127.0.0.1".to_string(), "10.0.0.0/8".to_string()]);
+        let mut headers = HeaderMap::new();
+        headers.insert("User-Agent", "Mozilla/5.0".parse().unwrap());
+        headers.insert("X-Forwarded-For", "1.1.1.1, 203.0.113.7, 10.0.0.2".parse().unwrap());
+
+        let info = ClientInfo::from_headers(&headers, Some("127.0.0.1:8080".parse().unwrap()), &proxies);
+        assert_eq!(info.user_agent.as_deref(), Some("Mozilla/5.0"));
+        assert_eq!(info.ip_address.as_deref(), Some("203.0.113.7"));
+
+        // 믿을 수 없는 곳에서 온 헤더는 무시한다.
+        let info = ClientInfo::from_headers(&headers, Some("198.51.100.1:8080".parse().unwrap()), &proxies);
+        assert_eq!(info.ip_address.as_deref(), Some("198.51.100.1"));
+        let info = ClientInfo::from_headers(&headers, Some("127.0.0.1:8080".parse().unwrap()), &TrustedProxies::default());
+        assert_eq!(info.ip_address.as_deref(), Some("127.0.0.1"));
+
+        // IP 가 아닌 값은 저장하지 않는다.
+        let mut headers = HeaderMap::new();
+        headers.insert("X-Real-IP", "<script>".parse().unwrap());
+        let info = ClientInfo::from_headers(&headers, Some("127.0.0.1:8080".parse().unwrap()), &proxies);
+        assert_eq!(info.ip_address.as_deref(), Some("127.0.0.1"));
+    }
+}
@@ -0,0 +1,67 @@
+use aes_gcm::aead::KeyInit;
+use aes_gcm::{Aes256Gcm, Key};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use sha2::{Digest, Sha256};
+use shaku::{Component, Interface};
+use std::sync::Arc;
+
+use super::aead::{open, seal};
+use crate::config::ConfigProvider;
+
+/// 브라우저에 잠시 맡겨두는 값(OAuth2 인가 요청 등)을 암호화한다.
+/// 쿠키 값은 `base64url(nonce || ciphertext)` 형식이다.
+pub trait CookieCipher: Interface {
+    fn encrypt(&self, plain: &str) -> String;
+
+    /// 변조되었거나 다른 키로 암호화된 값이면 None 을 반환한다.
+    fn decrypt(&self, encoded: &str) -> Option<String>;
+}
+
+#[derive(Component)]
+#[shaku(interface = CookieCipher)]
+pub struct CookieCipherImpl {
+    #[shaku(inject)]
+    config: Arc<dyn ConfigProvider>,
+}
+
+impl CookieCipherImpl {
+    fn cipher(&self) -> Aes256Gcm {
+        let key = Sha256::digest(self.config.get().cookie_secret.as_bytes());
+        Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key))
+    }
+}
+
+impl CookieCipher for CookieCipherImpl {
+    fn encrypt(&self, plain: &str) -> String {
+        URL_SAFE_NO_PAD.encode(seal(&self.cipher(), plain.as_bytes()))
+    }
+
+    fn decrypt(&self, encoded: &str) -> Option<String> {
+        let payload = URL_SAFE_NO_PAD.decode(encoded).ok()?;
+        let plain = open(&self.cipher(), &payload)?;
+        String::from_utf8(plain).ok()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CookieCipher, CookieCipherImpl};
+    use crate::config::test_config;
+
+    #[test]
+    fn encrypt_decrypt_test() {
+        let cipher = CookieCipherImpl {
+            config: test_config(&[]),
+        };
+
+        let encrypted = cipher.encrypt("{\"state\":\"abc\"}");
+        assert_ne!(encrypted, cipher.encrypt("{\"state\":\"abc\"}"));
+        assert_eq!(cipher.decrypt(&encrypted), Some("{\"state\":\"abc\"}".to_owned()));
+
+        let mut tampered = encrypted.into_bytes();
+        let last = tampered.len() - 2;
+        tampered[last] = if tampered[last] == b'A' { b'B' } else { b'A' };
+        assert_eq!(cipher.decrypt(&String::from_utf8(tampered).unwrap()), None);
+    }
+}
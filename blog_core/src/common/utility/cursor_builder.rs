@@ -69,7 +69,7 @@ impl<T: Serialize + Clone> CursorListBuilder<T> {
         queries.push_str(&format!("{}={}", "size", self.size));
 
         for (key, value) in &self.query {
-            queries.push_str(&format!("&{}={}", key, value));
+            queries.push_str(&format!("&{}={}", key, encode_query_value(value)));
         }
 
         queries
@@ -86,11 +86,14 @@ impl<T: Serialize + Clone> CursorListBuilder<T> {
             }).to_string();
             let value = value.trim_matches('"').to_string();
 
-            return Some(format!("&{}={}", target, value));
+            return Some(format!("&{}={}", target, encode_query_value(&value)));
         } else {
             None
         }
     }
 }
 
-
+/// 검색어처럼 공백이나 한글이 들어간 값도 다음 페이지 링크에 그대로 쓸 수 있도록 인코딩한다.
+fn encode_query_value(value: &str) -> String {
+    form_urlencoded::byte_serialize(value.as_bytes()).collect()
+}
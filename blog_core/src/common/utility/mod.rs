@@ -1,8 +1,16 @@
+pub mod aead;
+pub mod client_info;
 pub mod cookie_builder;
+pub mod cookie_cipher;
 pub mod cursor_builder;
 pub mod file_writer;
 pub mod cookie_maker;
+pub mod token_cipher;
+pub mod token_hasher;
 
+pub use client_info::{ClientInfo, TrustedProxies};
 pub use cookie_maker::CookieMaker;
 pub use cookie_builder::CookieBuilderImpl;
+pub use cookie_cipher::{CookieCipher, CookieCipherImpl};
 pub use cursor_builder::CursorListBuilder;
+pub use token_cipher::{TokenCipher, TokenCipherImpl};
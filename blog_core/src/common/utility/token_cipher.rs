@@ -0,0 +1,241 @@
+use aes_gcm::aead::{KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key};
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+use base64::Engine;
+use shaku::{Component, Interface};
+use std::sync::{Arc, OnceLock};
+
+use super::aead::{open, seal};
+use crate::common::error::error_code::ErrorCode;
+use crate::common::AppError;
+use crate::config::ConfigProvider;
+
+/// 저장 형식 버전. 형식이 바뀌면 올린다.
+const ENVELOPE_VERSION: &str = "v1";
+
+/// 데이터 키(DEK) 길이(256bit)
+const DATA_KEY_LEN: usize = 32;
+
+/// DB 에 저장하는 비밀 값(OAuth2 제공자 access token 등)을 envelope 방식으로 암호화한다.
+/// 값마다 새 데이터 키(DEK)로 암호화하고, DEK 는 설정의 키(KEK)로 감싸서 함께 저장한다.
+/// 저장 형식은 `v1:{kid}:{base64url(감싼 DEK)}:{base64url(nonce || 암호문)}` 이다.
+pub trait TokenCipher: Interface {
+    fn encrypt(&self, plain: &str) -> String;
+
+    /// 형식 접두사가 없는 값은 암호화 도입 전에 저장된 평문으로 보고 그대로 돌려준다.
+    fn decrypt(&self, stored: &str) -> Result<String, AppError>;
+
+    /// 활성 키로 감싸져 있지 않은 값을 활성 키로 다시 감싼다. 이미 활성 키라면 None 을 반환한다.
+    /// 키 교체 시 암호문은 그대로 두고 DEK 만 다시 감싼다.
+    fn rewrap(&self, stored: &str) -> Result<Option<String>, AppError>;
+
+    /// 새로 암호화할 때 쓰는 키 ID. 키 설정이 잘못되어 있으면 panic 한다.
+    fn active_kid(&self) -> String;
+}
+
+pub(crate) struct DataKeyRing {
+    keys: Vec<(String, Aes256Gcm)>,
+    active: usize,
+}
+
+impl DataKeyRing {
+    /// `kid:base64(32바이트 키)` 목록을 읽는다. 잘못된 설정은 기동 시점에 드러나도록 panic 한다.
+    fn from_config(keys: &[String], active_kid: Option<&str>) -> Self {
+        let keys: Vec<(String, Aes256Gcm)> = keys
+            .iter()
+            .filter(|key| !key.trim().is_empty())
+            .map(|key| {
+                let (kid, encoded) = key
+                    .trim()
+                    .split_once(':')
+                    .unwrap_or_else(|| panic!("데이터 암호화 키 형식이 잘못되었습니다. (kid:base64)"));
+                let key = STANDARD
+                    .decode(encoded)
+                    .ok()
+                    .filter(|key| key.len() == DATA_KEY_LEN)
+                    .unwrap_or_else(|| panic!("데이터 암호화 키 {} 는 32바이트 base64 값이어야 합니다.", kid));
+                (kid.to_owned(), Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key)))
+            })
+            .collect();
+
+        if keys.is_empty() {
+            panic!("데이터 암호화 키가 설정되지 않았습니다.");
+        }
+
+        let active = match active_kid {
+            Some(active_kid) => keys
+                .iter()
+                .position(|(kid, _)| kid == active_kid)
+                .unwrap_or_else(|| panic!("데이터 암호화 키 {} 를 찾을 수 없습니다.", active_kid)),
+            None => 0,
+        };
+
+        DataKeyRing { keys, active }
+    }
+
+    fn active(&self) -> &(String, Aes256Gcm) {
+        &self.keys[self.active]
+    }
+
+    fn find(&self, kid: &str) -> Option<&Aes256Gcm> {
+        self.keys
+            .iter()
+            .find(|(key_id, _)| key_id == kid)
+            .map(|(_, cipher)| cipher)
+    }
+}
+
+/// 저장된 값을 (kid, 감싼 DEK, 암호문) 으로 나눈다. 형식 접두사가 없으면 None 이다.
+fn parse_envelope(stored: &str) -> Option<(&str, &str, &str)> {
+    let mut parts = stored.splitn(4, ':');
+    if parts.next() != Some(ENVELOPE_VERSION) {
+        return None;
+    }
+    Some((parts.next()?, parts.next()?, parts.next()?))
+}
+
+fn decrypt_failed() -> AppError {
+    AppError::with_message(ErrorCode::InternalServerError, "저장된 값을 복호화할 수 없습니다.")
+}
+
+#[derive(Component)]
+#[shaku(interface = TokenCipher)]
+pub struct TokenCipherImpl {
+    #[shaku(inject)]
+    pub(crate) config: Arc<dyn ConfigProvider>,
+    #[shaku(default)]
+    pub(crate) key_ring: OnceLock<DataKeyRing>,
+}
+
+impl TokenCipherImpl {
+    fn key_ring(&self) -> &DataKeyRing {
+        self.key_ring.get_or_init(|| {
+            let config = self.config.get();
+            DataKeyRing::from_config(&config.data_encryption_keys, config.data_encryption_active_kid.as_deref())
+        })
+    }
+
+    fn unwrap_data_key(&self, kid: &str, wrapped_key: &str) -> Result<Vec<u8>, AppError> {
+        let key_encryption_key = self.key_ring().find(kid).ok_or_else(|| {
+            tracing::error!("Unknown data encryption key: {}", kid);
+            decrypt_failed()
+        })?;
+        URL_SAFE_NO_PAD
+            .decode(wrapped_key)
+            .ok()
+            .and_then(|wrapped_key| open(key_encryption_key, &wrapped_key))
+            .filter(|data_key| data_key.len() == DATA_KEY_LEN)
+            .ok_or_else(decrypt_failed)
+    }
+}
+
+impl TokenCipher for TokenCipherImpl {
+    fn encrypt(&self, plain: &str) -> String {
+        let data_key = Aes256Gcm::generate_key(&mut OsRng);
+        let ciphertext = seal(&Aes256Gcm::new(&data_key), plain.as_bytes());
+
+        let (kid, key_encryption_key) = self.key_ring().active();
+        let wrapped_key = seal(key_encryption_key, &data_key);
+
+        format!(
+            "{}:{}:{}:{}",
+            ENVELOPE_VERSION,
+            kid,
+            URL_SAFE_NO_PAD.encode(wrapped_key),
+            URL_SAFE_NO_PAD.encode(ciphertext),
+        )
+    }
+
+    fn decrypt(&self, stored: &str) -> Result<String, AppError> {
+        let Some((kid, wrapped_key, ciphertext)) = parse_envelope(stored) else {
+            return Ok(stored.to_owned());
+        };
+
+        let data_key = self.unwrap_data_key(kid, wrapped_key)?;
+        let data_key = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&data_key));
+        URL_SAFE_NO_PAD
+            .decode(ciphertext)
+            .ok()
+            .and_then(|ciphertext| open(&data_key, &ciphertext))
+            .and_then(|plain| String::from_utf8(plain).ok())
+            .ok_or_else(decrypt_failed)
+    }
+
+    fn rewrap(&self, stored: &str) -> Result<Option<String>, AppError> {
+        let Some((kid, wrapped_key, ciphertext)) = parse_envelope(stored) else {
+            return Ok(Some(self.encrypt(stored)));
+        };
+
+        let (active_kid, key_encryption_key) = self.key_ring().active();
+        if kid == active_kid {
+            return Ok(None);
+        }
+
+        let data_key = self.unwrap_data_key(kid, wrapped_key)?;
+
+        Ok(Some(format!(
+            "{}:{}:{}:{}",
+            ENVELOPE_VERSION,
+            active_kid,
+            URL_SAFE_NO_PAD.encode(seal(key_encryption_key, &data_key)),
+            ciphertext,
+        )))
+    }
+
+    fn active_kid(&self) -> String {
+        self.key_ring().active().0.clone()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{TokenCipher, TokenCipherImpl};
+    use crate::config::test_config;
+
+    const OLD_KEY: &str = "old:IM7R7GoKy+kBurCz01geUpItmbGYLKPibUswUcNy06Q=";
+    const NEW_KEY: &str = "new:AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8=";
+
+    fn create_cipher(args: &[&str]) -> TokenCipherImpl {
+        TokenCipherImpl {
+            config: test_config(args),
+            key_ring: Default::default(),
+        }
+    }
+
+    #[test]
+    fn encrypt_decrypt_test() {
+        let cipher = create_cipher(&["--data-encryption-keys", OLD_KEY]);
+
+        let encrypted = cipher.encrypt("gho_token");
+        assert!(encrypted.starts_with("v1:old:"));
+        assert!(!encrypted.contains("gho_token"));
+        assert_ne!(encrypted, cipher.encrypt("gho_token"));
+        assert_eq!(cipher.decrypt(&encrypted).ok().unwrap(), "gho_token");
+
+        // 암호화 도입 전에 저장된 평문
+        assert_eq!(cipher.decrypt("gho_plain").ok().unwrap(), "gho_plain");
+    }
+
+    #[test]
+    fn rotate_key_test() {
+        let old_cipher = create_cipher(&["--data-encryption-keys", OLD_KEY]);
+        let encrypted = old_cipher.encrypt("gho_token");
+
+        let rotated_cipher = create_cipher(&[
+            "--data-encryption-keys",
+            &format!("{},{}", NEW_KEY, OLD_KEY),
+            "--data-encryption-active-kid",
+            "new",
+        ]);
+        assert_eq!(rotated_cipher.decrypt(&encrypted).ok().unwrap(), "gho_token");
+
+        let rewrapped = rotated_cipher.rewrap(&encrypted).ok().unwrap().unwrap();
+        assert!(rewrapped.starts_with("v1:new:"));
+        assert!(rotated_cipher.rewrap(&rewrapped).ok().unwrap().is_none());
+
+        // 이전 키를 제거해도 다시 감싼 값은 복호화할 수 있다.
+        let new_cipher = create_cipher(&["--data-encryption-keys", NEW_KEY]);
+        assert_eq!(new_cipher.decrypt(&rewrapped).ok().unwrap(), "gho_token");
+        assert!(new_cipher.decrypt(&encrypted).is_err());
+    }
+}
@@ -0,0 +1,20 @@
+use sha2::{Digest, Sha256};
+
+/// 서버에 저장하는 토큰은 원문 대신 SHA-256 해시(hex)로 보관한다.
+pub fn hash_token(token: &str) -> String {
+    let digest = Sha256::digest(token.as_bytes());
+    format!("{:x}", digest)
+}
+
+#[cfg(test)]
+mod test {
+    use super::hash_token;
+
+    #[test]
+    fn hash_token_test() {
+        let hashed = hash_token("refresh-token");
+        assert_eq!(hashed.len(), 64);
+        assert_eq!(hashed, hash_token("refresh-token"));
+        assert_ne!(hashed, hash_token("refresh-token2"));
+    }
+}
@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use shaku::{Component, Interface};
+use uuid::Uuid;
+
+use crate::config::ConfigProvider;
+
+/// 게시글 조회수 집계.
+/// 같은 방문자가 `view_dedup_window_seconds` 안에 다시 본 글은 세지 않는다.
+/// 센 조회수는 메모리에 모아 두었다가 스케줄러가 `view_flush_interval_seconds` 마다 한꺼번에 반영하므로,
+/// 조회가 몰리는 글이라도 요청마다 행을 잠그지 않는다.
+pub trait ViewCounter: Interface {
+    /// 조회를 기록한다. 중복 판정 시간 안에 이미 본 방문자면 세지 않고 false 를 돌려준다.
+    /// 누구인지 알 수 없는 방문자(`None`)는 가릴 방법이 없으므로 매번 센다.
+    fn record(&self, post_id: Uuid, visitor: Option<&str>) -> bool;
+
+    /// 중복 판정 시간이 지난 방문 기록을 정리한다. 조회할 때마다 하지 않고 반영 주기마다 부른다.
+    fn prune_visits(&self);
+
+    /// 아직 반영하지 않은 조회수
+    fn pending(&self, post_id: Uuid) -> i64;
+
+    /// 반영할 조회수를 모두 꺼낸다.
+    fn take_pending(&self) -> HashMap<Uuid, i64>;
+
+    /// 반영하지 못한 조회수를 다음 반영 때 함께 쓰도록 되돌려 놓는다.
+    fn restore_pending(&self, pending: HashMap<Uuid, i64>);
+}
+
+#[derive(Component)]
+#[shaku(interface = ViewCounter)]
+pub struct ViewCounterImpl {
+    #[shaku(inject)]
+    pub(crate) config: Arc<dyn ConfigProvider>,
+    #[shaku(default)]
+    visits: Mutex<HashMap<(Uuid, String), Instant>>,
+    #[shaku(default)]
+    pending: Mutex<HashMap<Uuid, i64>>,
+}
+
+impl ViewCounterImpl {
+    fn dedup_window(&self) -> Duration {
+        Duration::from_secs(self.config.get().view_dedup_window_seconds)
+    }
+
+    fn record_at(&self, post_id: Uuid, visitor: Option<&str>, now: Instant) -> bool {
+        if let Some(visitor) = visitor {
+            let window = self.dedup_window();
+            let mut visits = self.visits.lock().unwrap();
+            let key = (post_id, visitor.to_owned());
+            if visits.get(&key).is_some_and(|visited_at| now.duration_since(*visited_at) < window) {
+                return false;
+            }
+            visits.insert(key, now);
+        }
+
+        *self.pending.lock().unwrap().entry(post_id).or_default() += 1;
+        true
+    }
+
+    fn prune_visits_at(&self, now: Instant) {
+        let window = self.dedup_window();
+        self.visits.lock().unwrap().retain(|_, visited_at| now.duration_since(*visited_at) < window);
+    }
+}
+
+impl ViewCounter for ViewCounterImpl {
+    fn record(&self, post_id: Uuid, visitor: Option<&str>) -> bool {
+        self.record_at(post_id, visitor, Instant::now())
+    }
+
+    fn prune_visits(&self) {
+        self.prune_visits_at(Instant::now())
+    }
+
+    fn pending(&self, post_id: Uuid) -> i64 {
+        self.pending.lock().unwrap().get(&post_id).copied().unwrap_or(0)
+    }
+
+    fn take_pending(&self) -> HashMap<Uuid, i64> {
+        std::mem::take(&mut *self.pending.lock().unwrap())
+    }
+
+    fn restore_pending(&self, restored: HashMap<Uuid, i64>) {
+        let mut pending = self.pending.lock().unwrap();
+        for (post_id, count) in restored {
+            *pending.entry(post_id).or_default() += count;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use uuid::Uuid;
+
+    use crate::config::test_config;
+
+    use super::{ViewCounter, ViewCounterImpl};
+
+    #[test]
+    fn record_view_test() {
+        let counter = ViewCounterImpl {
+            config: test_config(&["--view-dedup-window-seconds", "60"]),
+            visits: Default::default(),
+            pending: Default::default(),
+        };
+        let post_id = Uuid::new_v4();
+        let now = Instant::now();
+
+        assert!(counter.record_at(post_id, Some("ip:127.0.0.1"), now));
+        assert!(!counter.record_at(post_id, Some("ip:127.0.0.1"), now + Duration::from_secs(30)));
+        assert!(counter.record_at(post_id, Some("member:1"), now + Duration::from_secs(30)));
+        // 중복 판정 시간이 지나면 다시 센다.
+        assert!(counter.record_at(post_id, Some("ip:127.0.0.1"), now + Duration::from_secs(61)));
+        // 알 수 없는 방문자는 매번 센다.
+        assert!(counter.record_at(post_id, None, now));
+        assert!(counter.record_at(post_id, None, now));
+        assert_eq!(counter.pending(post_id), 5);
+
+        let pending = counter.take_pending();
+        assert_eq!(pending.get(&post_id), Some(&5));
+        assert_eq!(counter.pending(post_id), 0);
+
+        counter.record_at(post_id, Some("member:2"), now + Duration::from_secs(61));
+        counter.restore_pending(pending);
+        assert_eq!(counter.pending(post_id), 6);
+
+        counter.prune_visits_at(now + Duration::from_secs(100));
+        assert_eq!(counter.visits.lock().unwrap().len(), 2);
+    }
+}
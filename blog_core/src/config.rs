@@ -3,50 +3,111 @@ use std::sync::Arc;
 use clap::Parser;
 use shaku::{Component, Interface};
 
-pub trait OAuth2ConfigProvider: Interface {
-
-    fn github_login_url(&self) -> String;
+use crate::application::oauth2::OAuth2Provider;
+use crate::domain::board::entity::command::post_entity::EXCERPT_SOURCE_CHARS;
 
-    fn github_client_id(&self) -> String;
-
-    fn github_client_secret(&self) -> String;
+/// OAuth2 / OIDC 제공자별 설정.
+/// 환경변수 `{PROVIDER}_CLIENT_ID` 가 있어야 활성화되고, 나머지 값은 제공자별 기본값을 덮어쓴다.
+#[derive(Debug, Clone)]
+pub struct OAuth2ProviderConfig {
+    pub provider: OAuth2Provider,
+    pub client_id: String,
+    pub client_secret: String,
+    pub redirect_uri: String,
+    pub scopes: String,
+    pub authorization_uri: Option<String>,
+    pub token_uri: Option<String>,
+    pub userinfo_uri: Option<String>,
+    /// 설정되어 있으면 OIDC 제공자로 보고 discovery 로 엔드포인트를 찾은 뒤 id_token 을 검증한다.
+    pub issuer: Option<String>,
+    /// 사용자 프로필(또는 id_token)에서 식별자를 꺼낼 claim 경로 (예: `id`, `sub`)
+    pub user_id_claim: String,
+    /// 이메일 claim 경로 (예: `email`, `kakao_account.email`)
+    pub email_claim: Option<String>,
+    pub prompt: Option<String>,
+}
 
-    fn github_code_redirect_uri(&self) -> String;
+impl OAuth2ProviderConfig {
+    fn preset(provider: OAuth2Provider, client_id: String, client_secret: String, redirect_uri: String) -> Self {
+        let config = OAuth2ProviderConfig {
+            provider: provider.clone(),
+            client_id,
+            client_secret,
+            redirect_uri,
+            scopes: String::new(),
+            authorization_uri: None,
+            token_uri: None,
+            userinfo_uri: None,
+            issuer: None,
+            user_id_claim: "id".to_string(),
+            email_claim: None,
+            prompt: None,
+        };
 
-    fn github_token_redirect_uri(&self) -> String;
+        match provider {
+            OAuth2Provider::Github => OAuth2ProviderConfig {
+                scopes: "profiles".to_string(),
+                authorization_uri: Some("https://github.com/login/oauth/authorize".to_string()),
+                token_uri: Some("https://github.com/login/oauth/access_token".to_string()),
+                userinfo_uri: Some("https://api.github.com/user".to_string()),
+                ..config
+            },
+            OAuth2Provider::Google => OAuth2ProviderConfig {
+                scopes: "openid email profile".to_string(),
+                issuer: Some("https://accounts.google.com".to_string()),
+                user_id_claim: "sub".to_string(),
+                email_claim: Some("email".to_string()),
+                prompt: Some("select_account".to_string()),
+                ..config
+            },
+            OAuth2Provider::Kakao => OAuth2ProviderConfig {
+                scopes: "account_email".to_string(),
+                authorization_uri: Some("https://kauth.kakao.com/oauth/authorize".to_string()),
+                token_uri: Some("https://kauth.kakao.com/oauth/token".to_string()),
+                userinfo_uri: Some("https://kapi.kakao.com/v2/user/me".to_string()),
+                email_claim: Some("kakao_account.email".to_string()),
+                ..config
+            },
+        }
+    }
+}
 
-    fn github_scopes(&self) -> String;
+pub trait OAuth2ConfigProvider: Interface {
+    /// 설정되지 않은 제공자라면 None 을 반환한다.
+    fn provider_config(&self, provider: &OAuth2Provider) -> Option<OAuth2ProviderConfig>;
 }
 
 #[derive(Component)]
 #[shaku(interface = OAuth2ConfigProvider)]
 pub struct OAuth2ConfigProviderImpl {
+    #[shaku(inject)]
+    config: Arc<dyn ConfigProvider>,
 }
 
 impl OAuth2ConfigProvider for OAuth2ConfigProviderImpl {
 
-    fn github_login_url(&self) -> String {
-        std::env::var("GITHUB_LOGIN_URL").unwrap_or("https://github.com/login/oauth/authorize".to_string())
-    }
-
-    fn github_client_id(&self) -> String {
-        std::env::var("GITHUB_CLIENT_ID").expect("GITHUB_CLIENT_ID not set")
-    }
-
-    fn github_client_secret(&self) -> String {
-        std::env::var("GITHUB_CLIENT_SECRET").expect("GITHUB_CLIENT_SECRET not set")
-    }
-
-    fn github_code_redirect_uri(&self) -> String {
-        std::env::var("GITHUB_CODE_REDIRECT_URI").expect("GITHUB_CODE_REDIRECT_URI not set")
-    }
+    fn provider_config(&self, provider: &OAuth2Provider) -> Option<OAuth2ProviderConfig> {
+        let prefix = provider.as_str();
+        let env = |key: &str| std::env::var(format!("{}_{}", prefix, key)).ok();
 
-    fn github_token_redirect_uri(&self) -> String {
-        std::env::var("GITHUB_TOKEN_REDIRECT_URI").expect("GITHUB_TOKEN_REDIRECT_URI not set")
-    }
+        let client_id = env("CLIENT_ID")?;
+        let client_secret = env("CLIENT_SECRET").unwrap_or_default();
+        let redirect_uri = env("CODE_REDIRECT_URI").unwrap_or_else(|| {
+            self.config
+                .get_uri(&format!("/api/v1/oauth2/{}/callback", provider.path()))
+        });
 
-    fn github_scopes(&self) -> String {
-        std::env::var("GITHUB_SCOPES").unwrap_or("profiles".to_string())
+        let preset = OAuth2ProviderConfig::preset(provider.clone(), client_id, client_secret, redirect_uri);
+        Some(OAuth2ProviderConfig {
+            scopes: env("SCOPES").unwrap_or(preset.scopes),
+            authorization_uri: env("LOGIN_URL").or(preset.authorization_uri),
+            token_uri: env("TOKEN_URL").or(preset.token_uri),
+            userinfo_uri: env("USERINFO_URL").or(preset.userinfo_uri),
+            issuer: env("ISSUER").or(preset.issuer),
+            user_id_claim: env("USER_ID_CLAIM").unwrap_or(preset.user_id_claim),
+            email_claim: env("EMAIL_CLAIM").or(preset.email_claim),
+            ..preset
+        })
     }
 }
 
@@ -101,6 +162,9 @@ pub struct AppConfig {
     pub server_host: String,
     #[arg(long, default_value = "http")]
     protocol: String,
+    // X-Forwarded-For, X-Real-IP 를 믿을 프록시 주소 목록 (IP 또는 CIDR). 비우면 소켓 주소만 쓴다.
+    #[arg(long, value_delimiter = ',')]
+    pub trusted_proxies: Vec<String>,
 
     // Datasource
     #[arg(long, default_value = "localhost")]
@@ -118,6 +182,62 @@ pub struct AppConfig {
     #[arg(long, default_value_t = 6379)]
     pub redis_port: u16,
 
+    // Rate limit
+    // 요청 수 제한 버킷 저장소 (memory, redis)
+    #[arg(long, default_value = "memory")]
+    pub rate_limit_backend: String,
+    // 라우트별 정책 목록 (이름=허용 횟수/기간(초)). 목록에 없는 정책은 제한하지 않는다.
+    #[arg(long, value_delimiter = ',', default_value = "login=10/60,signup=5/3600,upload=60/60,mail=5/3600")]
+    pub rate_limit_policies: Vec<String>,
+
+    // Scheduler
+    // 예약된 게시글을 발행하는 주기(초)
+    #[arg(long, default_value_t = 60)]
+    pub post_scheduler_interval_seconds: u64,
+    // 모아 둔 조회수를 DB 에 반영하는 주기(초)
+    #[arg(long, default_value_t = 10)]
+    pub view_flush_interval_seconds: u64,
+    // 같은 방문자가 이 시간(초) 안에 다시 본 글은 조회수를 세지 않는다.
+    #[arg(long, default_value_t = 1800)]
+    pub view_dedup_window_seconds: u64,
+
+    // Post summary
+    // 게시글 목록 요약 글자 수. 본문 앞 2000자(EXCERPT_SOURCE_CHARS) 안에서 만들므로 그보다 길 수 없다.
+    #[arg(long, default_value_t = 200, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..=EXCERPT_SOURCE_CHARS as u64))]
+    pub excerpt_length: usize,
+
+    // Search
+    // 게시글 검색 저장소 (postgres, embedded)
+    #[arg(long, default_value = "postgres")]
+    pub search_backend: String,
+    // search_backend 가 embedded 일 때 색인을 저장하는 파일
+    #[arg(long, default_value = "./search_index.json")]
+    pub search_index_path: String,
+    // 임베디드 색인의 변경을 파일에 쓰는 주기(초)
+    #[arg(long, default_value_t = 5)]
+    pub search_index_flush_interval_seconds: u64,
+    // 모든 게시글로 검색 색인을 다시 만든 뒤 종료한다.
+    // 서버가 떠 있는 동안 실행하면 서버는 다음 flush 주기에 새 파일을 불러온다.
+    #[arg(long)]
+    pub rebuild_search_index: bool,
+
+    // Feed
+    #[arg(long, default_value = "dohoon-kim.kr")]
+    pub feed_title: String,
+    #[arg(long, default_value = "dohoon-kim.kr 블로그의 새 글")]
+    pub feed_description: String,
+    // 피드에 담는 최근 글 수
+    #[arg(long, default_value_t = 20)]
+    pub feed_size: u64,
+    // 피드에 요약 대신 본문 전체를 담는다.
+    #[arg(long)]
+    pub feed_full_content: bool,
+
+    // robots.txt
+    // 검색 엔진이 크롤링하지 않을 경로. 비우면 모두 허용한다.
+    #[arg(long, value_delimiter = ',', default_value = "/api/")]
+    pub robots_disallow: Vec<String>,
+
     // JWT
     #[arg(long, default_value = "test-access-token-secret")]
     pub jwt_access_token_secret: String,
@@ -134,8 +254,78 @@ pub struct AppConfig {
     pub jwt_issuer: String,
     #[arg(long, default_value = "https://www.dohoon-kim.kr")]
     pub jwt_audience: String,
+    // 비밀번호 재설정, 이메일 인증 메일에 담기는 토큰 서명 secret
+    #[arg(long, default_value = "test-verification-token-secret")]
+    pub jwt_verification_token_secret: String,
+    // access token 서명 키 목록 (kid:ALG:공개키경로[:개인키경로]), 비어 있으면 HS256 secret 사용
+    #[arg(long, value_delimiter = ',')]
+    pub jwt_signing_keys: Vec<String>,
+    // 서명에 사용할 kid, 없으면 개인키가 있는 첫 번째 키
+    #[arg(long)]
+    pub jwt_active_kid: Option<String>,
+    // 개인 액세스 토큰 유효 기간(일). 발급할 때 지정하지 않으면 기본값을 쓴다.
+    #[arg(long, default_value_t = 90)]
+    pub access_token_default_days: i64,
+    #[arg(long, default_value_t = 365)]
+    pub access_token_max_days: i64,
+    // 로그인 잠금
+    // 계정(이메일) 별로 이 횟수만큼 연속 실패하면 잠근다.
+    #[arg(long, default_value_t = 5)]
+    pub login_lockout_threshold: i32,
+    // IP 별로 이 횟수만큼 연속 실패하면 잠근다.
+    #[arg(long, default_value_t = 20)]
+    pub login_ip_lockout_threshold: i32,
+    // 처음 잠그는 시간(초). 잠긴 뒤 다시 실패할 때마다 두 배로 늘어난다.
+    #[arg(long, default_value_t = 30)]
+    pub login_lockout_base_seconds: i64,
+    #[arg(long, default_value_t = 3600)]
+    pub login_lockout_max_seconds: i64,
+    // 마지막 실패 후 이 시간(분)이 지나면 실패 횟수를 다시 센다.
+    #[arg(long, default_value_t = 60)]
+    pub login_failure_reset_minutes: i64,
+    // MFA
+    // ROLE_ADMIN 의 관리자 권한을 2단계 인증으로 로그인한 경우에만 허용한다.
+    #[arg(long)]
+    pub mfa_required_for_admin: bool,
+    // 인증 앱에 표시되는 TOTP 발급자 이름
+    #[arg(long, default_value = "dohoon-kim.kr")]
+    pub mfa_issuer: String,
     // OAuth
+    // OAuth2 인가 요청 쿠키 암호화에 사용하는 secret
+    #[arg(long, default_value = "test-cookie-secret")]
+    pub cookie_secret: String,
+    // OAuth2 access token 등 저장 데이터 암호화 키 목록 (kid:base64 32바이트 키), 복호화는 모든 키로 시도
+    // 기본값이 없으므로 반드시 설정해야 한다. (`openssl rand -base64 32` 로 만든다)
+    #[arg(long, value_delimiter = ',')]
+    pub data_encryption_keys: Vec<String>,
+    // 새로 암호화할 때 사용할 kid, 없으면 첫 번째 키
+    #[arg(long)]
+    pub data_encryption_active_kid: Option<String>,
+    // 저장된 OAuth2 access token 을 활성 키로 다시 암호화한 뒤 종료한다. (키 교체 시 사용)
+    #[arg(long)]
+    pub reencrypt_oauth2_tokens: bool,
     
+    // Mail
+    // 메일 전송 방식 (smtp, file, memory)
+    #[arg(long, default_value = "file")]
+    pub mail_transport: String,
+    #[arg(long, default_value = "no-reply@dohoon-kim.kr")]
+    pub mail_from: String,
+    // mail_transport 가 file 일 때 메일을 남기는 경로
+    #[arg(long, default_value = "./mail_outbox")]
+    pub mail_outbox_path: String,
+    #[arg(long, default_value = "localhost")]
+    pub smtp_host: String,
+    #[arg(long, default_value_t = 587)]
+    pub smtp_port: u16,
+    #[arg(long)]
+    pub smtp_username: Option<String>,
+    #[arg(long)]
+    pub smtp_password: Option<String>,
+    // 메일 본문의 링크가 가리키는 화면 주소
+    #[arg(long, default_value = "http://localhost:3000")]
+    pub web_origin: String,
+
     // FileIO
     #[arg(long, default_value = "/Users/terrapin/.volumes/media")]
     pub storage_path: String,
@@ -148,3 +338,24 @@ impl AppConfig {
         self.app_env == "prod"
     }
 }
+
+/// 테스트용 설정. 프로그램 이름 없이 명령줄 인자만 넘긴다.
+#[cfg(test)]
+pub(crate) fn test_config(args: &[&str]) -> Arc<dyn ConfigProvider> {
+    let args = std::iter::once("blog_core").chain(args.iter().copied());
+    Arc::new(ConfigProviderImpl { config: Arc::new(AppConfig::parse_from(args)) })
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::AppConfig;
+
+    #[test]
+    fn excerpt_length_test() {
+        assert!(AppConfig::try_parse_from(["blog_core", "--excerpt-length", "2000"]).is_ok());
+        assert!(AppConfig::try_parse_from(["blog_core", "--excerpt-length", "2001"]).is_err());
+        assert!(AppConfig::try_parse_from(["blog_core", "--excerpt-length", "0"]).is_err());
+    }
+}
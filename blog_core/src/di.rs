@@ -1,11 +1,16 @@
-use crate::application::board::{BoardCreateUsecaseImpl, BoardDeleteUsecaseImpl, BoardModifyUsecaseImpl, BoardQueryUsecaseImpl, PostCreateUsecaseImpl, PostDeleteUsecaseImpl, PostModifyUsecaseImpl, PostQueryUsecaseImpl};
+use crate::application::board::{BoardCreateUsecaseImpl, BoardDeleteUsecaseImpl, BoardModifyUsecaseImpl, BoardQueryUsecaseImpl, CommentCreateUsecaseImpl, CommentDeleteUsecaseImpl, CommentModifyUsecaseImpl, CommentQueryUsecaseImpl, FeedQueryUsecaseImpl, PostCreateUsecaseImpl, PostDeleteUsecaseImpl, PostModifyUsecaseImpl, PostPublishUsecaseImpl, PostQueryUsecaseImpl, PostRevisionQueryUsecaseImpl, PostRevisionRestoreUsecaseImpl, PostSearchUsecaseImpl, PostViewFlushUsecaseImpl, ReactionToggleUsecaseImpl, SearchIndexFlushUsecaseImpl, SearchIndexRebuildUsecaseImpl, SitemapQueryUsecaseImpl, TagQueryUsecaseImpl};
 use crate::application::member::adapter::{
     MemberCreateUseCaseImpl, MemberDeleteUseCaseImpl, MemberUpdateUseCaseImpl,
 };
 use crate::common::file_writer::FileWriterImpl;
-use crate::common::CookieBuilderImpl;
+use crate::common::mailer::MailerImpl;
+use crate::common::markdown::MarkdownRendererImpl;
+use crate::common::rate_limiter::RateLimiterImpl;
+use crate::common::view_counter::ViewCounterImpl;
+use crate::common::{CookieBuilderImpl, CookieCipherImpl, TokenCipherImpl};
+use crate::application::oauth2::OidcDiscoveryImpl;
 use crate::config::OAuth2ConfigProviderImpl;
-use crate::domain::board::repository::{SeaOrmLoadPostAdapter, SeaOrmSavePostAdapter};
+use crate::domain::board::repository::{SeaOrmLoadCommentAdapter, SeaOrmLoadPostAdapter, SeaOrmLoadPostRevisionAdapter, SeaOrmSaveCommentAdapter, SeaOrmSavePostAdapter, SeaOrmSavePostRevisionAdapter, SeaOrmLoadTagAdapter, SeaOrmSaveTagAdapter, SeaOrmLoadReactionAdapter, SeaOrmSaveReactionAdapter, SearchIndexAdapter};
 use shaku::module;
 
 use super::application::auth::*;
@@ -17,6 +22,19 @@ use super::domain::member::repository::{MemberCommandRepository, MemberQueryRepo
 use super::domain::member::oauth2_member::repository::{
     SeaormLoadOAuth2MemberAdapter, SeaormSaveOAuth2MemberAdapter,
 };
+use super::domain::member::refresh_token::repository::{
+    SeaormLoadRefreshTokenAdapter, SeaormSaveRefreshTokenAdapter,
+};
+use super::domain::member::access_token::repository::{SeaormLoadAccessTokenAdapter, SeaormSaveAccessTokenAdapter};
+use super::domain::audit::repository::SeaormSaveAuditLogAdapter;
+use super::domain::member::login_attempt::repository::{SeaormLoadLoginAttemptAdapter, SeaormSaveLoginAttemptAdapter};
+use super::domain::member::mfa::repository::{SeaormLoadMfaAdapter, SeaormSaveMfaAdapter};
+use super::domain::member::session::repository::{
+    SeaormLoadSessionAdapter, SeaormSaveSessionAdapter,
+};
+use super::domain::member::verification_token::repository::{
+    SeaormLoadVerificationTokenAdapter, SeaormSaveVerificationTokenAdapter,
+};
 use super::common::utility::cookie_maker::CookieMakerImpl;
 
 module! {
@@ -28,17 +46,57 @@ module! {
             CookieBuilderImpl,
             FileWriterImpl,
             CookieMakerImpl,
+            CookieCipherImpl,
+            TokenCipherImpl,
+            MailerImpl,
+            RateLimiterImpl,
+            ViewCounterImpl,
+            MarkdownRendererImpl,
 
             // OAuth2
             OAuth2ConfigProviderImpl,
+            OidcDiscoveryImpl,
             SeaormLoadOAuth2MemberAdapter,
             SeaormSaveOAuth2MemberAdapter,
+            OAuth2IdentityUseCaseImpl,
+            OAuth2TokenReencryptUseCaseImpl,
 
 
             // Auth
             AuthService,
             JwtUseCaseImpl,
             JwtServiceImpl,
+            LogoutUseCaseImpl,
+            SeaormLoadRefreshTokenAdapter,
+            SeaormSaveRefreshTokenAdapter,
+
+            // Personal access token
+            PersonalAccessTokenUseCaseImpl,
+            SeaormLoadAccessTokenAdapter,
+            SeaormSaveAccessTokenAdapter,
+
+            // Login lockout, Audit
+            LoginAttemptGuardImpl,
+            SeaormLoadLoginAttemptAdapter,
+            SeaormSaveLoginAttemptAdapter,
+            SeaormSaveAuditLogAdapter,
+
+            // MFA
+            MfaUseCaseImpl,
+            SeaormLoadMfaAdapter,
+            SeaormSaveMfaAdapter,
+
+            // Password reset, Email verification
+            PasswordResetUseCaseImpl,
+            EmailVerificationUseCaseImpl,
+            SeaormLoadVerificationTokenAdapter,
+            SeaormSaveVerificationTokenAdapter,
+
+            // Session
+            SessionQueryUseCaseImpl,
+            SessionRevokeUseCaseImpl,
+            SeaormLoadSessionAdapter,
+            SeaormSaveSessionAdapter,
 
             // Member
             MemberQueryRepository,
@@ -57,8 +115,43 @@ module! {
             PostModifyUsecaseImpl,
             PostDeleteUsecaseImpl,
             PostQueryUsecaseImpl,
+            PostPublishUsecaseImpl,
+            PostViewFlushUsecaseImpl,
             SeaOrmSavePostAdapter,
             SeaOrmLoadPostAdapter,
+            PostRevisionQueryUsecaseImpl,
+            PostRevisionRestoreUsecaseImpl,
+            SeaOrmLoadPostRevisionAdapter,
+            SeaOrmSavePostRevisionAdapter,
+
+            // Tag
+            TagQueryUsecaseImpl,
+            SeaOrmLoadTagAdapter,
+            SeaOrmSaveTagAdapter,
+
+            // Reaction
+            ReactionToggleUsecaseImpl,
+            SeaOrmLoadReactionAdapter,
+            SeaOrmSaveReactionAdapter,
+
+            // Feed
+            FeedQueryUsecaseImpl,
+            // Sitemap
+            SitemapQueryUsecaseImpl,
+
+            // Search
+            PostSearchUsecaseImpl,
+            SearchIndexRebuildUsecaseImpl,
+            SearchIndexFlushUsecaseImpl,
+            SearchIndexAdapter,
+
+            // Comment
+            CommentCreateUsecaseImpl,
+            CommentModifyUsecaseImpl,
+            CommentDeleteUsecaseImpl,
+            CommentQueryUsecaseImpl,
+            SeaOrmSaveCommentAdapter,
+            SeaOrmLoadCommentAdapter,
 
             /* Member Service Related */
             MemberCreateUseCaseImpl,
@@ -0,0 +1,79 @@
+use chrono::NaiveDateTime;
+use uuid::Uuid;
+
+/// 감사 로그에 남기는 사건의 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditEventType {
+    /// 로그인 실패가 쌓여 계정 또는 IP 가 잠김
+    LoginLocked,
+}
+
+impl AuditEventType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditEventType::LoginLocked => "LOGIN_LOCKED",
+        }
+    }
+
+    pub fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "LOGIN_LOCKED" => Some(AuditEventType::LoginLocked),
+            _ => None,
+        }
+    }
+}
+
+/// 보안과 관련된 사건 기록. 한 번 남기면 수정하지 않는다.
+#[derive(Debug, Clone)]
+pub struct AuditLogEntity {
+    id: Uuid,
+    event_type: AuditEventType,
+    member_id: Option<Uuid>,
+    ip_address: Option<String>,
+    detail: serde_json::Value,
+    created_at: NaiveDateTime,
+}
+
+impl AuditLogEntity {
+    pub fn new(
+        id: Option<Uuid>,
+        event_type: AuditEventType,
+        member_id: Option<Uuid>,
+        ip_address: Option<String>,
+        detail: serde_json::Value,
+        created_at: Option<NaiveDateTime>,
+    ) -> Self {
+        Self {
+            id: id.unwrap_or_else(Uuid::new_v4),
+            event_type,
+            member_id,
+            ip_address,
+            detail,
+            created_at: created_at.unwrap_or_else(|| chrono::Utc::now().naive_utc()),
+        }
+    }
+
+    pub fn get_id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn get_event_type(&self) -> AuditEventType {
+        self.event_type
+    }
+
+    pub fn get_member_id(&self) -> Option<Uuid> {
+        self.member_id
+    }
+
+    pub fn get_ip_address(&self) -> &Option<String> {
+        &self.ip_address
+    }
+
+    pub fn get_detail(&self) -> &serde_json::Value {
+        &self.detail
+    }
+
+    pub fn get_created_at(&self) -> NaiveDateTime {
+        self.created_at
+    }
+}
@@ -0,0 +1,33 @@
+pub mod audit_log_mapper {
+
+    use sea_orm::ActiveValue::Set;
+    use sea_orm::DbErr;
+
+    use crate::domain::audit::entity::{AuditEventType, AuditLogEntity};
+    use crate::domain::audit::schema::{ActiveModel, Model};
+
+    pub fn to_domain(orm_model: Model) -> Result<AuditLogEntity, DbErr> {
+        let event_type = AuditEventType::from_str(&orm_model.event_type)
+            .ok_or_else(|| DbErr::Type(format!("Unknown audit event type: {}", orm_model.event_type)))?;
+
+        Ok(AuditLogEntity::new(
+            Some(orm_model.id),
+            event_type,
+            orm_model.member_id,
+            orm_model.ip_address,
+            orm_model.detail,
+            Some(orm_model.created_at),
+        ))
+    }
+
+    pub fn to_orm(entity: &AuditLogEntity) -> ActiveModel {
+        ActiveModel {
+            id: Set(entity.get_id()),
+            event_type: Set(entity.get_event_type().as_str().to_owned()),
+            member_id: Set(entity.get_member_id()),
+            ip_address: Set(entity.get_ip_address().clone()),
+            detail: Set(entity.get_detail().clone()),
+            created_at: Set(entity.get_created_at()),
+        }
+    }
+}
@@ -0,0 +1,4 @@
+pub mod entity;
+pub mod mapper;
+pub mod repository;
+pub mod schema;
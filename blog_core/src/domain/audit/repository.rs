@@ -0,0 +1,25 @@
+use sea_orm::{ActiveModelTrait, DatabaseTransaction, DbErr};
+use shaku::{Component, Interface};
+use super::entity::AuditLogEntity;
+use super::mapper::audit_log_mapper;
+
+#[async_trait::async_trait]
+pub trait SaveAuditLogPort: Interface {
+
+    async fn save(&self, txn: &DatabaseTransaction, entity: AuditLogEntity) -> Result<AuditLogEntity, DbErr>;
+}
+
+#[derive(Component)]
+#[shaku(interface = SaveAuditLogPort)]
+pub struct SeaormSaveAuditLogAdapter {}
+
+#[async_trait::async_trait]
+impl SaveAuditLogPort for SeaormSaveAuditLogAdapter {
+
+    async fn save(&self, txn: &DatabaseTransaction, entity: AuditLogEntity) -> Result<AuditLogEntity, DbErr> {
+        audit_log_mapper::to_orm(&entity)
+            .insert(txn)
+            .await
+            .and_then(audit_log_mapper::to_domain)
+    }
+}
@@ -0,0 +1,142 @@
+use chrono::NaiveDateTime;
+use derive_builder::Builder;
+use uuid::Uuid;
+
+use crate::common::error_code::ErrorCode;
+use crate::common::AppError;
+
+#[derive(Debug, Clone, Builder)]
+pub struct CommentEntity {
+    id: Option<i64>,
+    post_id: Uuid,
+    member_id: Uuid,
+    #[builder(default = "None")]
+    parent_id: Option<i64>,
+    contents: String,
+    #[builder(default = "false")]
+    is_deleted: bool,
+    created_at: NaiveDateTime,
+    #[builder(default = "None")]
+    updated_at: Option<NaiveDateTime>,
+}
+
+#[allow(dead_code)]
+impl CommentEntity {
+    pub fn check_ownership(&self, member_id: Uuid) -> Result<(), AppError> {
+        if self.member_id != member_id {
+            return Err(AppError::with_message(ErrorCode::Forbidden, "댓글 작성자가 아닙니다."));
+        }
+        Ok(())
+    }
+
+    pub fn check_post(&self, post_id: Uuid) -> Result<(), AppError> {
+        if self.post_id != post_id {
+            return Err(AppError::with_message(ErrorCode::NotFound, "해당 게시글의 댓글이 아닙니다."));
+        }
+        Ok(())
+    }
+
+    pub fn validate(&self) -> Result<bool, AppError> {
+        if !Self::validate_contents(&self.contents) {
+            return Err(AppError::with_message(ErrorCode::BadRequest, "댓글은 1자 이상 1000자 이하로 작성해야 합니다."));
+        }
+        Ok(true)
+    }
+
+    pub fn validate_contents(value: &str) -> bool {
+        let sz_chrs = value.trim().chars().count();
+        sz_chrs > 0 && sz_chrs <= 1000
+    }
+
+    pub fn change_contents(&mut self, new_contents: String) -> Result<(), AppError> {
+        if self.is_deleted {
+            return Err(AppError::with_message(ErrorCode::BadRequest, "삭제된 댓글은 수정할 수 없습니다."));
+        }
+        if !Self::validate_contents(&new_contents) {
+            return Err(AppError::with_message(ErrorCode::BadRequest, "댓글은 1자 이상 1000자 이하로 작성해야 합니다."));
+        }
+        self.contents = new_contents;
+        Ok(())
+    }
+
+    /// 답글이 달린 댓글은 스레드 구조를 유지하기 위해 내용만 지우고 삭제 표시를 남긴다.
+    pub fn mark_deleted(&mut self) {
+        self.is_deleted = true;
+        self.contents = String::new();
+    }
+
+    pub fn get_id(&self) -> Option<i64> {
+        self.id
+    }
+
+    pub fn get_post_id(&self) -> Uuid {
+        self.post_id
+    }
+
+    pub fn get_member_id(&self) -> Uuid {
+        self.member_id
+    }
+
+    pub fn get_parent_id(&self) -> Option<i64> {
+        self.parent_id
+    }
+
+    pub fn get_contents(&self) -> String {
+        self.contents.to_owned()
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.is_deleted
+    }
+
+    pub fn get_created_at(&self) -> NaiveDateTime {
+        self.created_at
+    }
+
+    pub fn get_updated_at(&self) -> Option<NaiveDateTime> {
+        self.updated_at
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::CommentEntityBuilder;
+    use uuid::Uuid;
+
+    fn create_comment(member_id: Uuid) -> super::CommentEntity {
+        CommentEntityBuilder::default()
+            .id(Some(1))
+            .post_id(Uuid::new_v4())
+            .member_id(member_id)
+            .contents("댓글 내용".to_owned())
+            .created_at(chrono::Utc::now().naive_utc())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn check_ownership_test() {
+        let owner = Uuid::new_v4();
+        let comment = create_comment(owner);
+        assert!(comment.check_ownership(owner).is_ok());
+        let err = comment.check_ownership(Uuid::new_v4()).err().unwrap();
+        assert_eq!(err.message, "댓글 작성자가 아닙니다.");
+    }
+
+    #[test]
+    fn change_contents_test() {
+        let mut comment = create_comment(Uuid::new_v4());
+        assert!(comment.change_contents("수정된 댓글".to_owned()).is_ok());
+        assert_eq!(comment.get_contents(), "수정된 댓글");
+        assert!(comment.change_contents("   ".to_owned()).is_err());
+    }
+
+    #[test]
+    fn mark_deleted_test() {
+        let mut comment = create_comment(Uuid::new_v4());
+        comment.mark_deleted();
+        assert!(comment.is_deleted());
+        assert_eq!(comment.get_contents(), "");
+        assert!(comment.change_contents("다시 작성".to_owned()).is_err());
+    }
+}
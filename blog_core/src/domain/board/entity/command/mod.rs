@@ -1,2 +1,6 @@
 pub mod board_entity;
+pub mod comment_entity;
 pub mod post_entity;
+pub mod post_revision_entity;
+pub mod reaction_entity;
+pub mod tag_entity;
@@ -1,10 +1,88 @@
 use chrono::NaiveDateTime;
 use derive_builder::Builder;
 use crate::common::error_code::ErrorCode;
+use unicode_segmentation::UnicodeSegmentation;
 use uuid::Uuid;
 
 use crate::common::AppError;
 
+/// 게시글 발행 상태
+/// - `Draft` : 작성 중. 작성자만 볼 수 있다.
+/// - `Scheduled` : 예약 발행. `published_at` 이 되면 스케줄러가 `Published` 로 바꾼다.
+/// - `Published` : 발행됨
+/// - `Archived` : 보관됨. 목록에서 내려가고 작성자만 볼 수 있다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PostStatus {
+    Draft,
+    Scheduled,
+    Published,
+    Archived,
+}
+
+impl PostStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PostStatus::Draft => "DRAFT",
+            PostStatus::Scheduled => "SCHEDULED",
+            PostStatus::Published => "PUBLISHED",
+            PostStatus::Archived => "ARCHIVED",
+        }
+    }
+
+    pub fn from_str(value: &str) -> Option<Self> {
+        match value.to_ascii_uppercase().as_str() {
+            "DRAFT" => Some(PostStatus::Draft),
+            "SCHEDULED" => Some(PostStatus::Scheduled),
+            "PUBLISHED" => Some(PostStatus::Published),
+            "ARCHIVED" => Some(PostStatus::Archived),
+            _ => None,
+        }
+    }
+
+    /// 누구에게나 보이는 글인지 판단한다.
+    /// 스케줄러가 돌기 전이라도 예약 시각이 지난 글은 발행된 것으로 본다.
+    pub fn is_public(&self, published_at: Option<NaiveDateTime>, now: NaiveDateTime) -> bool {
+        match self {
+            PostStatus::Published | PostStatus::Scheduled => published_at.is_some_and(|at| at <= now),
+            PostStatus::Draft | PostStatus::Archived => false,
+        }
+    }
+}
+
+/// 분당 읽는 글자 수. 한국어 기준으로 공백, Markdown 기호까지 포함해 센다.
+pub const READING_CHARS_PER_MINUTE: usize = 500;
+
+/// 목록 요약을 만들려고 저장해 두는 본문 앞부분 글자 수. 설정한 요약 길이는 이보다 길 수 없다.
+/// 바꾸면 저장된 `excerpt_source` 를 다시 계산해야 한다 (V18 마이그레이션 참고).
+pub const EXCERPT_SOURCE_CHARS: i32 = 2000;
+
+/// 본문 앞부분을 공백을 한 칸으로 줄여 max_chars 글자까지 자른다. 잘렸으면 끝에 `…` 을 붙인다.
+/// 글자는 화면에 한 글자로 보이는 단위(grapheme cluster)로 세므로 조합형 한글, 결합 문자, 이모지를 중간에서 자르지 않는다.
+pub fn excerpt(contents: &str, max_chars: usize) -> String {
+    let collapsed = contents.split_whitespace().collect::<Vec<&str>>().join(" ");
+    let end = collapsed.grapheme_indices(true)
+        .nth(max_chars)
+        .map(|(end, _)| end);
+    match end {
+        Some(end) => format!("{}…", collapsed[..end].trim_end()),
+        None => collapsed,
+    }
+}
+
+/// 글자 수로 어림한 읽는 시간(분). 짧은 글도 1분으로 본다.
+pub fn reading_time_minutes(chars: usize) -> i64 {
+    chars.div_ceil(READING_CHARS_PER_MINUTE).max(1) as i64
+}
+
+/// 표지 이미지로 쓸 수 있는 주소인지 확인한다. http(s) 절대 주소와 사이트 안 경로만 허용한다.
+pub fn cover_image_url(url: &str) -> Option<String> {
+    let url = url.trim();
+    let allowed = url.starts_with("https://")
+        || url.starts_with("http://")
+        || (url.starts_with('/') && !url.starts_with("//"));
+    allowed.then(|| url.to_owned())
+}
+
 #[derive(Debug, Clone, Builder)]
 pub struct PostEntity {
     id: Option<Uuid>,
@@ -13,6 +91,8 @@ pub struct PostEntity {
     title: String,
     contents: String,
     view_count: i64,
+    status: PostStatus,
+    published_at: Option<NaiveDateTime>,
     created_at: NaiveDateTime,
     updated_at: Option<NaiveDateTime>,
 }
@@ -36,6 +116,8 @@ impl PostEntity {
             title: title.to_owned(),
             contents: contents.to_owned(),
             view_count,
+            status: PostStatus::Draft,
+            published_at: None,
             created_at: created_at.unwrap_or(chrono::Utc::now().naive_utc()),
             updated_at,
         }
@@ -48,6 +130,50 @@ impl PostEntity {
         Ok(())
     }
 
+    /// 발행 상태를 바꾼다. 예약 발행은 현재 이후의 시각이 있어야 하고, 보관은 발행된 글만 할 수 있다.
+    pub fn change_status(
+        &mut self,
+        status: PostStatus,
+        publish_at: Option<NaiveDateTime>,
+        now: NaiveDateTime,
+    ) -> Result<(), AppError> {
+        match status {
+            PostStatus::Draft => {
+                self.published_at = None;
+            }
+            PostStatus::Scheduled => {
+                let publish_at = publish_at
+                    .filter(|at| *at > now)
+                    .ok_or_else(|| AppError::with_message(ErrorCode::BadRequest, "예약 발행 시각은 현재 이후여야 합니다."))?;
+                self.published_at = Some(publish_at);
+            }
+            PostStatus::Published => {
+                // 이미 발행된 글은 처음 발행한 시각을 유지한다.
+                // 예약 시각이 지났지만 스케줄러가 아직 바꾸지 않은 글도 이미 공개된 것이므로 예약 시각을 유지한다.
+                let is_due = self.status == PostStatus::Scheduled && self.published_at.is_some_and(|at| at <= now);
+                if !self.is_published() && !is_due {
+                    self.published_at = Some(now);
+                }
+            }
+            PostStatus::Archived => {
+                if !self.is_published() {
+                    return Err(AppError::with_message(ErrorCode::BadRequest, "발행된 게시글만 보관할 수 있습니다."));
+                }
+            }
+        }
+        self.status = status;
+        Ok(())
+    }
+
+    fn is_published(&self) -> bool {
+        matches!(self.status, PostStatus::Published | PostStatus::Archived) && self.published_at.is_some()
+    }
+
+    /// 로그인하지 않았으면 viewer 는 None 이다. 공개되지 않은 글은 작성자만 볼 수 있다.
+    pub fn is_visible_to(&self, viewer: Option<Uuid>, now: NaiveDateTime) -> bool {
+        self.status.is_public(self.published_at, now) || viewer == Some(self.member_id)
+    }
+
     pub fn validate(&self) -> Result<bool, AppError> {
         if !Self::validate_title(&self.title) {
             return Err(AppError::with_message(ErrorCode::BadRequest, "게시글 제목은 5자 이상 255자 이하로 작성해야 합니다."));
@@ -103,6 +229,14 @@ impl PostEntity {
         self.contents.to_owned()
     }
 
+    pub fn get_status(&self) -> PostStatus {
+        self.status
+    }
+
+    pub fn get_published_at(&self) -> Option<NaiveDateTime> {
+        self.published_at
+    }
+
     pub fn get_created_at(&self) -> NaiveDateTime {
         self.created_at
     }
@@ -115,3 +249,80 @@ impl PostEntity {
         self.view_count
     }
 }
+
+#[cfg(test)]
+mod test {
+    use chrono::{Duration, Utc};
+    use uuid::Uuid;
+
+    use super::{cover_image_url, excerpt, reading_time_minutes, PostEntity, PostEntityBuilder, PostStatus};
+
+    fn draft(member_id: Uuid) -> PostEntity {
+        PostEntityBuilder::default()
+            .id(None)
+            .category_id(1)
+            .member_id(member_id)
+            .title("title".to_owned())
+            .contents("contents".to_owned())
+            .view_count(0)
+            .status(PostStatus::Draft)
+            .published_at(None)
+            .created_at(Utc::now().naive_utc())
+            .updated_at(None)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn post_status_visibility_test() {
+        let now = Utc::now().naive_utc();
+        let author = Uuid::new_v4();
+        let mut post = draft(author);
+        assert!(!post.is_visible_to(None, now));
+        assert!(post.is_visible_to(Some(author), now));
+
+        // 예약 시각은 현재 이후여야 한다.
+        assert!(post.change_status(PostStatus::Scheduled, Some(now - Duration::minutes(1)), now).is_err());
+        assert!(post.change_status(PostStatus::Archived, None, now).is_err());
+
+        post.change_status(PostStatus::Scheduled, Some(now + Duration::hours(1)), now).ok().unwrap();
+        assert!(!post.is_visible_to(None, now));
+        // 스케줄러가 돌기 전이라도 예약 시각이 지나면 보인다.
+        assert!(post.is_visible_to(None, now + Duration::hours(2)));
+
+        // 예약 시각이 지난 글을 직접 발행해도 예약 시각을 발행 시각으로 유지한다.
+        let mut due = post.clone();
+        due.change_status(PostStatus::Published, None, now + Duration::hours(2)).ok().unwrap();
+        assert_eq!(due.get_published_at(), Some(now + Duration::hours(1)));
+
+        post.change_status(PostStatus::Published, None, now).ok().unwrap();
+        assert_eq!(post.get_published_at(), Some(now));
+        post.change_status(PostStatus::Archived, None, now + Duration::days(1)).ok().unwrap();
+        assert!(!post.is_visible_to(None, now));
+        // 다시 발행해도 처음 발행 시각을 유지한다.
+        post.change_status(PostStatus::Published, None, now + Duration::days(2)).ok().unwrap();
+        assert_eq!(post.get_published_at(), Some(now));
+    }
+
+    #[test]
+    fn excerpt_test() {
+        assert_eq!(excerpt("첫 줄\n\n  둘째   줄", 100), "첫 줄 둘째 줄");
+        assert_eq!(excerpt("러스트 비동기 프로그래밍", 7), "러스트 비동기…");
+        // 조합형(NFD) 한글 `한글` 은 6글자지만 음절 중간에서 자르지 않는다.
+        assert_eq!(excerpt("\u{1112}\u{1161}\u{11AB}\u{1100}\u{1173}\u{11AF}", 4), "\u{1112}\u{1161}\u{11AB}\u{1100}\u{1173}\u{11AF}");
+        assert_eq!(excerpt("\u{1112}\u{1161}\u{11AB}\u{1100}\u{1173}\u{11AF} 입니다", 1), "\u{1112}\u{1161}\u{11AB}…");
+        // 가족 이모지(ZWJ 연결)와 국기(지역 표시 문자 두 개)도 한 글자로 센다.
+        assert_eq!(excerpt("👨\u{200D}👩\u{200D}👧🇰🇷 가족", 2), "👨\u{200D}👩\u{200D}👧🇰🇷…");
+        assert_eq!(excerpt("🇰🇷🇯🇵", 1), "🇰🇷…");
+    }
+
+    #[test]
+    fn summary_helper_test() {
+        assert_eq!(reading_time_minutes(0), 1);
+        assert_eq!(reading_time_minutes(501), 2);
+        assert_eq!(cover_image_url(" /api/v1/files/a.png "), Some("/api/v1/files/a.png".to_owned()));
+        assert_eq!(cover_image_url("https://cdn.example.com/a.png"), Some("https://cdn.example.com/a.png".to_owned()));
+        assert_eq!(cover_image_url("javascript:alert(1)"), None);
+        assert_eq!(cover_image_url("//evil.example.com/a.png"), None);
+    }
+}
@@ -0,0 +1,123 @@
+use chrono::NaiveDateTime;
+use derive_builder::Builder;
+use similar::TextDiff;
+use uuid::Uuid;
+
+use super::post_entity::PostEntity;
+
+/// 게시글을 저장할 때마다 남기는 변경 불가능한 리비전. 리비전 번호는 게시글마다 1부터 매긴다.
+#[derive(Debug, Clone, Builder)]
+pub struct PostRevisionEntity {
+    #[builder(default = "None")]
+    id: Option<i64>,
+    post_id: Uuid,
+    revision_no: i32,
+    member_id: Uuid,
+    category_id: i64,
+    title: String,
+    contents: String,
+    /// 복원으로 만들어진 리비전이면 복원한 리비전 번호
+    #[builder(default = "None")]
+    restored_from: Option<i32>,
+    created_at: NaiveDateTime,
+}
+
+#[allow(dead_code)]
+impl PostRevisionEntity {
+    /// 저장된 게시글의 현재 내용으로 리비전을 만든다. member_id 는 수정한 회원이다.
+    pub fn from_post(
+        post: &PostEntity,
+        revision_no: i32,
+        member_id: Uuid,
+        restored_from: Option<i32>,
+        created_at: NaiveDateTime,
+    ) -> Self {
+        PostRevisionEntity {
+            id: None,
+            post_id: post.get_id().expect("저장된 게시글만 리비전을 남길 수 있습니다."),
+            revision_no,
+            member_id,
+            category_id: post.get_category_id(),
+            title: post.get_title(),
+            contents: post.get_contents(),
+            restored_from,
+            created_at,
+        }
+    }
+
+    /// 이 리비전에서 다른 리비전으로의 본문 변경을 unified diff 형식으로 만든다.
+    pub fn diff(&self, to: &PostRevisionEntity) -> String {
+        TextDiff::from_lines(&self.contents, &to.contents)
+            .unified_diff()
+            .context_radius(3)
+            .header(
+                &format!("revision/{}", self.revision_no),
+                &format!("revision/{}", to.revision_no),
+            )
+            .to_string()
+    }
+
+    pub fn get_id(&self) -> Option<i64> {
+        self.id
+    }
+
+    pub fn get_post_id(&self) -> Uuid {
+        self.post_id
+    }
+
+    pub fn get_revision_no(&self) -> i32 {
+        self.revision_no
+    }
+
+    pub fn get_member_id(&self) -> Uuid {
+        self.member_id
+    }
+
+    pub fn get_category_id(&self) -> i64 {
+        self.category_id
+    }
+
+    pub fn get_title(&self) -> String {
+        self.title.to_owned()
+    }
+
+    pub fn get_contents(&self) -> String {
+        self.contents.to_owned()
+    }
+
+    pub fn get_restored_from(&self) -> Option<i32> {
+        self.restored_from
+    }
+
+    pub fn get_created_at(&self) -> NaiveDateTime {
+        self.created_at
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PostRevisionEntityBuilder;
+
+    #[test]
+    fn diff_test() {
+        let revision = |revision_no: i32, contents: &str| {
+            PostRevisionEntityBuilder::default()
+                .post_id(uuid::Uuid::nil())
+                .revision_no(revision_no)
+                .member_id(uuid::Uuid::nil())
+                .category_id(1)
+                .title("title".to_owned())
+                .contents(contents.to_owned())
+                .created_at(chrono::Utc::now().naive_utc())
+                .build()
+                .unwrap()
+        };
+
+        let diff = revision(1, "first\nsecond\n").diff(&revision(2, "first\nchanged\n"));
+        assert_eq!(
+            diff,
+            "--- revision/1\n+++ revision/2\n@@ -1,2 +1,2 @@\n first\n-second\n+changed\n"
+        );
+        assert!(revision(1, "same\n").diff(&revision(2, "same\n")).is_empty());
+    }
+}
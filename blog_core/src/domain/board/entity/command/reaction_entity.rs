@@ -0,0 +1,128 @@
+use chrono::{NaiveDateTime, Utc};
+use uuid::Uuid;
+
+/// 게시글, 댓글에 남기는 반응 종류. 회원은 대상마다 종류별로 한 번씩 반응할 수 있다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReactionKind {
+    /// 👍
+    Like,
+    /// ❤️
+    Love,
+    /// 😄
+    Laugh,
+    /// 😮
+    Wow,
+    /// 😢
+    Sad,
+    /// 🎉
+    Celebrate,
+}
+
+impl ReactionKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReactionKind::Like => "LIKE",
+            ReactionKind::Love => "LOVE",
+            ReactionKind::Laugh => "LAUGH",
+            ReactionKind::Wow => "WOW",
+            ReactionKind::Sad => "SAD",
+            ReactionKind::Celebrate => "CELEBRATE",
+        }
+    }
+
+    pub fn from_str(value: &str) -> Option<Self> {
+        match value.to_ascii_uppercase().as_str() {
+            "LIKE" => Some(ReactionKind::Like),
+            "LOVE" => Some(ReactionKind::Love),
+            "LAUGH" => Some(ReactionKind::Laugh),
+            "WOW" => Some(ReactionKind::Wow),
+            "SAD" => Some(ReactionKind::Sad),
+            "CELEBRATE" => Some(ReactionKind::Celebrate),
+            _ => None,
+        }
+    }
+}
+
+/// 반응 대상. 댓글 반응은 댓글이 달린 게시글도 함께 가리킨다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReactionTarget {
+    Post(Uuid),
+    Comment { post_id: Uuid, comment_id: i64 },
+}
+
+impl ReactionTarget {
+    pub fn post_id(&self) -> Uuid {
+        match self {
+            ReactionTarget::Post(post_id) => *post_id,
+            ReactionTarget::Comment { post_id, .. } => *post_id,
+        }
+    }
+
+    pub fn comment_id(&self) -> Option<i64> {
+        match self {
+            ReactionTarget::Post(_) => None,
+            ReactionTarget::Comment { comment_id, .. } => Some(*comment_id),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ReactionEntity {
+    id: Option<i64>,
+    member_id: Uuid,
+    target: ReactionTarget,
+    kind: ReactionKind,
+    created_at: NaiveDateTime,
+}
+
+impl ReactionEntity {
+    pub fn new(id: Option<i64>, member_id: Uuid, target: ReactionTarget, kind: ReactionKind, created_at: Option<NaiveDateTime>) -> Self {
+        ReactionEntity {
+            id,
+            member_id,
+            target,
+            kind,
+            created_at: created_at.unwrap_or_else(|| Utc::now().naive_utc()),
+        }
+    }
+
+    pub fn get_id(&self) -> Option<i64> {
+        self.id
+    }
+
+    pub fn get_member_id(&self) -> Uuid {
+        self.member_id
+    }
+
+    pub fn get_target(&self) -> ReactionTarget {
+        self.target
+    }
+
+    pub fn get_kind(&self) -> ReactionKind {
+        self.kind
+    }
+
+    pub fn get_created_at(&self) -> NaiveDateTime {
+        self.created_at
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use uuid::Uuid;
+
+    use super::{ReactionKind, ReactionTarget};
+
+    #[test]
+    fn reaction_kind_test() {
+        assert_eq!(ReactionKind::from_str("like"), Some(ReactionKind::Like));
+        assert_eq!(ReactionKind::from_str(ReactionKind::Celebrate.as_str()), Some(ReactionKind::Celebrate));
+        assert_eq!(ReactionKind::from_str("DISLIKE"), None);
+
+        let post_id = Uuid::new_v4();
+        let target = ReactionTarget::Comment { post_id, comment_id: 3 };
+        assert_eq!(target.post_id(), post_id);
+        assert_eq!(target.comment_id(), Some(3));
+        assert_eq!(ReactionTarget::Post(post_id).comment_id(), None);
+    }
+}
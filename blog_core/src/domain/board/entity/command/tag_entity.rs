@@ -0,0 +1,90 @@
+use chrono::{NaiveDateTime, Utc};
+
+use crate::common::error_code::ErrorCode;
+use crate::common::AppError;
+
+/// 게시글 하나에 붙일 수 있는 최대 태그 수
+pub const MAX_TAGS_PER_POST: usize = 10;
+
+#[derive(Debug, Clone)]
+pub struct TagEntity {
+    id: Option<i64>,
+    name: String,
+    created_at: NaiveDateTime,
+}
+
+impl TagEntity {
+    pub fn new(id: Option<i64>, name: String, created_at: Option<NaiveDateTime>) -> Self {
+        TagEntity {
+            id,
+            name,
+            created_at: created_at.unwrap_or_else(|| Utc::now().naive_utc()),
+        }
+    }
+
+    /// 태그 이름은 앞뒤 공백을 떼고 소문자로, 가운데 공백은 `-` 로 바꿔 저장한다. (`Rust Async` → `rust-async`)
+    pub fn normalize_name(name: &str) -> String {
+        name.split_whitespace()
+            .collect::<Vec<&str>>()
+            .join("-")
+            .to_lowercase()
+    }
+
+    /// 요청으로 받은 태그 목록을 정규화하고 중복을 없앤다. 순서는 처음 나온 순서를 따른다.
+    pub fn normalize_names(names: Vec<String>) -> Result<Vec<String>, AppError> {
+        let mut normalized: Vec<String> = Vec::new();
+        for name in names {
+            let name = Self::normalize_name(&name);
+            if name.is_empty() || name.chars().count() > 50 || name.contains(',') {
+                return Err(AppError::with_message(
+                    ErrorCode::BadRequest,
+                    "태그는 쉼표 없이 1자 이상 50자 이하로 작성해야 합니다.",
+                ));
+            }
+            if !normalized.contains(&name) {
+                normalized.push(name);
+            }
+        }
+
+        if normalized.len() > MAX_TAGS_PER_POST {
+            return Err(AppError::with_message(
+                ErrorCode::BadRequest,
+                "태그는 게시글마다 10개까지 붙일 수 있습니다.",
+            ));
+        }
+        Ok(normalized)
+    }
+
+    pub fn get_id(&self) -> Option<i64> {
+        self.id
+    }
+
+    pub fn get_name(&self) -> String {
+        self.name.to_owned()
+    }
+
+    pub fn get_created_at(&self) -> NaiveDateTime {
+        self.created_at
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TagEntity;
+
+    #[test]
+    fn normalize_names_test() {
+        let names = TagEntity::normalize_names(vec![
+            " Rust  Async ".to_owned(),
+            "rust-async".to_owned(),
+            "Axum".to_owned(),
+        ])
+        .ok()
+        .unwrap();
+        assert_eq!(names, vec!["rust-async".to_owned(), "axum".to_owned()]);
+
+        assert!(TagEntity::normalize_names(vec!["  ".to_owned()]).is_err());
+        assert!(TagEntity::normalize_names(vec!["a,b".to_owned()]).is_err());
+        assert!(TagEntity::normalize_names((0..11).map(|i| i.to_string()).collect()).is_err());
+    }
+}
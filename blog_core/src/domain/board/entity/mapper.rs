@@ -31,9 +31,11 @@ pub mod board_mapper {
 pub mod post_mapper {
 
     use sea_orm::ActiveValue::Set;
+    use unicode_segmentation::UnicodeSegmentation;
     use uuid::Uuid;
 
-    use crate::domain::board::entity::command::post_entity::{PostEntity, PostEntityBuilder};
+    use crate::common::markdown::first_image_url;
+    use crate::domain::board::entity::command::post_entity::{PostEntity, PostEntityBuilder, PostStatus, EXCERPT_SOURCE_CHARS};
     use crate::domain::board::schema::post::{
         ActiveModel as ActivePostModel, Model as PostModel,
     };
@@ -46,13 +48,29 @@ pub mod post_mapper {
             .title(orm_post.title.to_owned())
             .contents(orm_post.contents.to_owned())
             .view_count(orm_post.view_count)
+            .status(PostStatus::from_str(&orm_post.status).unwrap_or(PostStatus::Draft))
+            .published_at(orm_post.published_at)
             .created_at(orm_post.created_at)
             .updated_at(orm_post.updated_at)
             .build()
             .unwrap()
     }
 
+    /// 본문 앞 `EXCERPT_SOURCE_CHARS` 글자. 끝의 글자(grapheme cluster)가 잘리면 그 글자는 뺀다.
+    fn excerpt_source(contents: &str) -> String {
+        let mut chars = 0;
+        contents
+            .graphemes(true)
+            .take_while(|grapheme| {
+                chars += grapheme.chars().count();
+                chars <= EXCERPT_SOURCE_CHARS as usize
+            })
+            .collect()
+    }
+
+    /// 목록 요약용 컬럼(excerpt_source, contents_length, cover_image)은 본문에서 계산한다.
     pub fn to_orm(post_entity: &PostEntity) -> ActivePostModel {
+        let contents = post_entity.get_contents();
         ActivePostModel {
             id: if post_entity.get_id().is_some() {
                 Set(post_entity.get_id().unwrap())
@@ -62,14 +80,154 @@ pub mod post_mapper {
             member_id: Set(post_entity.get_member_id()),
             category_id: Set(post_entity.get_category_id()),
             title: Set(post_entity.get_title()),
-            contents: Set(post_entity.get_contents()),
+            excerpt_source: Set(excerpt_source(&contents)),
+            contents_length: Set(contents.chars().count().try_into().unwrap_or(i32::MAX)),
+            cover_image: Set(first_image_url(&contents)),
+            contents: Set(contents),
             view_count: Set(post_entity.get_view_count()),
+            status: Set(post_entity.get_status().as_str().to_owned()),
+            published_at: Set(post_entity.get_published_at()),
             created_at: Set(post_entity.get_created_at()),
             updated_at: Set(post_entity.get_updated_at()),
         }
     }
 }
 
+pub mod comment_mapper {
+
+    use sea_orm::ActiveValue::{NotSet, Set};
+
+    use crate::domain::board::entity::command::comment_entity::{CommentEntity, CommentEntityBuilder};
+    use crate::domain::board::schema::comment::{
+        ActiveModel as ActiveCommentModel, Model as CommentModel,
+    };
+
+    pub fn to_domain(orm_comment: &CommentModel) -> CommentEntity {
+        CommentEntityBuilder::default()
+            .id(Some(orm_comment.id))
+            .post_id(orm_comment.article_id)
+            .member_id(orm_comment.member_id)
+            .parent_id(orm_comment.parent_id)
+            .contents(orm_comment.contents.to_owned())
+            .is_deleted(orm_comment.is_deleted)
+            .created_at(orm_comment.created_at)
+            .updated_at(orm_comment.updated_at)
+            .build()
+            .unwrap()
+    }
+
+    pub fn to_orm(comment_entity: &CommentEntity) -> ActiveCommentModel {
+        ActiveCommentModel {
+            id: match comment_entity.get_id() {
+                Some(id) => Set(id),
+                None => NotSet,
+            },
+            member_id: Set(comment_entity.get_member_id()),
+            article_id: Set(comment_entity.get_post_id()),
+            parent_id: Set(comment_entity.get_parent_id()),
+            contents: Set(comment_entity.get_contents()),
+            is_deleted: Set(comment_entity.is_deleted()),
+            created_at: Set(comment_entity.get_created_at()),
+            updated_at: Set(comment_entity.get_updated_at()),
+        }
+    }
+}
+
+pub mod post_revision_mapper {
+
+    use sea_orm::ActiveValue::{NotSet, Set};
+
+    use crate::domain::board::entity::command::post_revision_entity::{PostRevisionEntity, PostRevisionEntityBuilder};
+    use crate::domain::board::schema::post_revision::{
+        ActiveModel as ActivePostRevisionModel, Model as PostRevisionModel,
+    };
+
+    pub fn to_domain(orm_revision: &PostRevisionModel) -> PostRevisionEntity {
+        PostRevisionEntityBuilder::default()
+            .id(Some(orm_revision.id))
+            .post_id(orm_revision.article_id)
+            .revision_no(orm_revision.revision_no)
+            .member_id(orm_revision.member_id)
+            .category_id(orm_revision.category_id)
+            .title(orm_revision.title.to_owned())
+            .contents(orm_revision.contents.to_owned())
+            .restored_from(orm_revision.restored_from)
+            .created_at(orm_revision.created_at)
+            .build()
+            .unwrap()
+    }
+
+    pub fn to_orm(revision_entity: &PostRevisionEntity) -> ActivePostRevisionModel {
+        ActivePostRevisionModel {
+            id: match revision_entity.get_id() {
+                Some(id) => Set(id),
+                None => NotSet,
+            },
+            article_id: Set(revision_entity.get_post_id()),
+            revision_no: Set(revision_entity.get_revision_no()),
+            member_id: Set(revision_entity.get_member_id()),
+            category_id: Set(revision_entity.get_category_id()),
+            title: Set(revision_entity.get_title()),
+            contents: Set(revision_entity.get_contents()),
+            restored_from: Set(revision_entity.get_restored_from()),
+            created_at: Set(revision_entity.get_created_at()),
+        }
+    }
+}
+
+pub mod tag_mapper {
+
+    use sea_orm::ActiveValue::{NotSet, Set};
+
+    use crate::domain::board::entity::command::tag_entity::TagEntity;
+    use crate::domain::board::schema::tag::{
+        ActiveModel as ActiveTagModel, Model as TagModel,
+    };
+
+    pub fn to_domain(orm_tag: &TagModel) -> TagEntity {
+        TagEntity::new(
+            Some(orm_tag.id),
+            orm_tag.name.to_owned(),
+            Some(orm_tag.created_at),
+        )
+    }
+
+    pub fn to_orm(tag_entity: &TagEntity) -> ActiveTagModel {
+        ActiveTagModel {
+            id: match tag_entity.get_id() {
+                Some(id) => Set(id),
+                None => NotSet,
+            },
+            name: Set(tag_entity.get_name()),
+            created_at: Set(tag_entity.get_created_at()),
+        }
+    }
+}
+
+pub mod reaction_mapper {
+
+    use sea_orm::ActiveValue::{NotSet, Set};
+
+    use crate::domain::board::entity::command::reaction_entity::ReactionEntity;
+    use crate::domain::board::schema::reaction::{
+        ActiveModel as ActiveReactionModel,
+    };
+
+    pub fn to_orm(reaction_entity: &ReactionEntity) -> ActiveReactionModel {
+        ActiveReactionModel {
+            id: match reaction_entity.get_id() {
+                Some(id) => Set(id),
+                None => NotSet,
+            },
+            member_id: Set(reaction_entity.get_member_id()),
+            article_id: Set(reaction_entity.get_target().post_id()),
+            comment_id: Set(reaction_entity.get_target().comment_id()),
+            kind: Set(reaction_entity.get_kind().as_str().to_owned()),
+            created_at: Set(reaction_entity.get_created_at()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::board_mapper;
@@ -0,0 +1,16 @@
+use chrono::NaiveDateTime;
+use sea_orm::FromQueryResult;
+
+use crate::application::board::usecases::WriterVo;
+
+#[derive(Debug, Clone, FromQueryResult)]
+pub struct QCommentEntity {
+    pub id: i64,
+    pub parent_id: Option<i64>,
+    #[sea_orm(nested)]
+    pub writer: WriterVo,
+    pub contents: String,
+    pub is_deleted: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: Option<NaiveDateTime>,
+}
@@ -1,5 +1,17 @@
 pub mod board_query_entity;
+pub mod comment_query_entity;
 pub mod post_query_entity;
+pub mod post_revision_query_entity;
+pub mod post_search_query_entity;
+pub mod reaction_query_entity;
+pub mod sitemap_query_entity;
+pub mod tag_query_entity;
 
 pub use board_query_entity::*;
+pub use comment_query_entity::*;
 pub use post_query_entity::*;
+pub use post_revision_query_entity::*;
+pub use post_search_query_entity::*;
+pub use reaction_query_entity::*;
+pub use sitemap_query_entity::*;
+pub use tag_query_entity::*;
@@ -12,7 +12,17 @@ pub struct QPostEntity {
     #[sea_orm(nested)]
     pub category: CategoryVo,
     pub title: String,
+    /// 본문 전체. 목록 조회에서는 비어 있다.
     pub contents: Option<String>,
+    /// 요약을 만들 본문 앞부분 (`EXCERPT_SOURCE_CHARS` 글자)
+    pub excerpt_source: Option<String>,
+    /// 본문 글자 수
+    pub contents_length: Option<i32>,
+    /// 본문의 첫 Markdown 이미지 주소
+    pub cover_image: Option<String>,
+    pub status: String,
+    pub published_at: Option<NaiveDateTime>,
     pub created_at: NaiveDateTime,
     pub updated_at: Option<NaiveDateTime>,
+    pub view_count: i64,
 }
@@ -0,0 +1,16 @@
+use chrono::NaiveDateTime;
+use sea_orm::FromQueryResult;
+
+use crate::application::board::usecases::WriterVo;
+
+/// 리비전 목록 조회용. 본문은 포함하지 않는다.
+#[derive(Debug, Clone, FromQueryResult)]
+pub struct QPostRevisionEntity {
+    pub revision_no: i32,
+    #[sea_orm(nested)]
+    pub writer: WriterVo,
+    pub category_id: i64,
+    pub title: String,
+    pub restored_from: Option<i32>,
+    pub created_at: NaiveDateTime,
+}
@@ -0,0 +1,20 @@
+use chrono::NaiveDateTime;
+use sea_orm::FromQueryResult;
+use uuid::Uuid;
+
+use crate::application::board::usecases::{CategoryVo, WriterVo};
+
+/// 검색 결과. title_highlight, snippet 은 일치한 부분이 구분 문자로 감싸인 원문 그대로이다.
+#[derive(Debug, Clone, FromQueryResult)]
+pub struct QPostSearchEntity {
+    pub id: Uuid,
+    #[sea_orm(nested)]
+    pub writer: WriterVo,
+    #[sea_orm(nested)]
+    pub category: CategoryVo,
+    pub title: String,
+    pub title_highlight: String,
+    pub snippet: String,
+    pub rank: f32,
+    pub published_at: Option<NaiveDateTime>,
+}
@@ -0,0 +1,13 @@
+use sea_orm::FromQueryResult;
+use uuid::Uuid;
+
+/// 대상별, 종류별 반응 수. 게시글 반응이면 comment_id 가 없다.
+#[derive(Debug, Clone, PartialEq, Eq, FromQueryResult)]
+pub struct QReactionCountEntity {
+    pub post_id: Uuid,
+    pub comment_id: Option<i64>,
+    pub kind: String,
+    pub count: i64,
+    /// 조회한 회원이 남긴 반응인지
+    pub reacted: bool,
+}
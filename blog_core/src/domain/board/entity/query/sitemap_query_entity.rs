@@ -0,0 +1,16 @@
+use chrono::NaiveDateTime;
+use sea_orm::FromQueryResult;
+
+/// 사이트맵에 싣는 게시판. last_modified 는 게시판이나 게시판의 공개 글이 마지막으로 바뀐 시각이다.
+#[derive(Debug, Clone, PartialEq, Eq, FromQueryResult)]
+pub struct QBoardSitemapEntity {
+    pub id: i64,
+    pub last_modified: Option<NaiveDateTime>,
+}
+
+/// 사이트맵에 싣는 공개 게시글
+#[derive(Debug, Clone, PartialEq, Eq, FromQueryResult)]
+pub struct QPostSitemapEntity {
+    pub id: uuid::Uuid,
+    pub last_modified: Option<NaiveDateTime>,
+}
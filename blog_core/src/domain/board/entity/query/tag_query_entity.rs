@@ -0,0 +1,15 @@
+use sea_orm::FromQueryResult;
+
+#[derive(Debug, Clone, PartialEq, Eq, FromQueryResult)]
+pub struct QTagEntity {
+    pub id: i64,
+    pub name: String,
+    pub count: i64,
+}
+
+/// 여러 게시글의 태그를 한 번에 조회할 때 쓴다.
+#[derive(Debug, Clone, PartialEq, Eq, FromQueryResult)]
+pub struct QPostTagEntity {
+    pub post_id: uuid::Uuid,
+    pub name: String,
+}
@@ -1,4 +1,5 @@
 use sea_orm::prelude::*;
+use sea_orm::sea_query::Func;
 use sea_orm::ActiveModelTrait;
 use sea_orm::JoinType;
 use sea_orm::QueryOrder;
@@ -8,8 +9,9 @@ use shaku::{Component, Interface};
 
 use crate::domain::board::entity::command::board_entity::BoardEntity;
 use crate::domain::board::entity::mapper::board_mapper;
-use crate::domain::board::entity::query::QBoardEntity;
+use crate::domain::board::entity::query::{QBoardEntity, QBoardSitemapEntity};
 use crate::domain::board::schema::board;
+use crate::domain::board::repository::public_post_condition;
 use crate::domain::board::schema::post;
 use std::option::Option;
 use std::result::Result;
@@ -19,6 +21,9 @@ pub trait LoadBoardPort: Interface {
     async fn load_entity_by_id(&self, txn: &DatabaseTransaction, id: i64) -> Option<BoardEntity>;
 
     async fn find_all(&self,txn: &DatabaseTransaction) -> Result<Vec<QBoardEntity>, DbErr>;
+
+    /// 사이트맵에 실을 게시판과 게시판의 공개 글이 마지막으로 바뀐 시각
+    async fn find_sitemap_boards(&self, txn: &DatabaseTransaction) -> Result<Vec<QBoardSitemapEntity>, DbErr>;
 }
 
 #[async_trait::async_trait]
@@ -70,6 +75,8 @@ impl LoadBoardPort for SeaOrmLoadBoardAdapter {
 
         let query_result: QueryResult =  */
 
+        // 공개된 글만 센다.
+        let now = chrono::Utc::now().naive_utc();
         let result = board::Entity::find()
             .select_only()
             .column(board::Column::Id)
@@ -77,7 +84,9 @@ impl LoadBoardPort for SeaOrmLoadBoardAdapter {
             .column_as(post::Column::Id.count(), "count")
             .join(
                 JoinType::LeftJoin,
-                board::Relation::Post.def()
+                board::Relation::Post
+                    .def()
+                    .on_condition(move |_, _| public_post_condition(now))
             )
             .group_by(board::Column::Id)
             .order_by_asc(board::Column::Id)
@@ -88,6 +97,35 @@ impl LoadBoardPort for SeaOrmLoadBoardAdapter {
 
         Ok(Vec::from(result))
     }
+
+    async fn find_sitemap_boards(&self, txn: &DatabaseTransaction) -> Result<Vec<QBoardSitemapEntity>, DbErr> {
+        let now = chrono::Utc::now().naive_utc();
+        // GREATEST 는 NULL 을 건너뛰므로 공개 글이 없는 게시판은 게시판이 바뀐 시각을 쓴다.
+        let board_modified = Func::coalesce([
+            Expr::col((board::Entity, board::Column::UpdatedAt)).into(),
+            Expr::col((board::Entity, board::Column::CreatedAt)).into(),
+        ]);
+        let post_modified = Func::max(Func::greatest([
+            Expr::col((post::Entity, post::Column::PublishedAt)).into(),
+            Expr::col((post::Entity, post::Column::UpdatedAt)).into(),
+        ]));
+
+        board::Entity::find()
+            .select_only()
+            .column(board::Column::Id)
+            .expr_as(Func::greatest([board_modified.into(), post_modified.into()]), "last_modified")
+            .join(
+                JoinType::LeftJoin,
+                board::Relation::Post
+                    .def()
+                    .on_condition(move |_, _| public_post_condition(now))
+            )
+            .group_by(board::Column::Id)
+            .order_by_asc(board::Column::Id)
+            .into_model::<QBoardSitemapEntity>()
+            .all(txn)
+            .await
+    }
 }
 
 #[derive(Component)]
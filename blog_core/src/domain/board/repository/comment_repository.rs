@@ -0,0 +1,235 @@
+use sea_orm::entity::*;
+use sea_orm::prelude::*;
+use sea_orm::*;
+use shaku::Component;
+use shaku::Interface;
+
+use crate::domain;
+use crate::domain::board::entity::command::comment_entity::CommentEntity;
+use crate::domain::board::entity::mapper::comment_mapper;
+use crate::domain::board::entity::query::QCommentEntity;
+use crate::domain::board::schema::comment;
+
+#[async_trait::async_trait]
+pub trait LoadCommentPort: Interface {
+
+    async fn load_by_id(&self, txn: &DatabaseTransaction, id: i64) -> Option<CommentEntity>;
+
+    /// 게시글의 최상위 댓글을 id 오름차순으로 조회한다. cursor 는 조회를 시작할 댓글 id(포함)이다.
+    async fn find_comments(&self, txn: &DatabaseTransaction, post_id: Uuid, cursor: Option<i64>, size: u64) -> Option<Vec<QCommentEntity>>;
+
+    /// 주어진 댓글들에 달린 모든 하위 답글을 깊이에 상관없이 조회한다.
+    async fn find_replies(&self, txn: &DatabaseTransaction, parent_ids: Vec<i64>) -> Option<Vec<QCommentEntity>>;
+
+    async fn has_replies(&self, txn: &DatabaseTransaction, id: i64) -> Result<bool, DbErr>;
+}
+
+#[async_trait::async_trait]
+pub trait SaveCommentPort: Interface {
+
+    async fn save(&self, txn: &DatabaseTransaction, comment: CommentEntity) -> Result<CommentEntity, DbErr>;
+
+    async fn update(&self, txn: &DatabaseTransaction, comment: CommentEntity) -> Result<CommentEntity, DbErr>;
+
+    async fn delete(&self, txn: &DatabaseTransaction, id: i64) -> Result<(), DbErr>;
+}
+
+#[derive(Component)]
+#[shaku(interface = LoadCommentPort)]
+pub struct SeaOrmLoadCommentAdapter {}
+
+#[derive(Component)]
+#[shaku(interface = SaveCommentPort)]
+pub struct SeaOrmSaveCommentAdapter {}
+
+#[async_trait::async_trait]
+impl LoadCommentPort for SeaOrmLoadCommentAdapter {
+
+    async fn load_by_id(&self, txn: &DatabaseTransaction, id: i64) -> Option<CommentEntity> {
+        match comment::Entity::find_by_id(id).one(txn).await {
+            Ok(Some(comment)) => Some(comment_mapper::to_domain(&comment)),
+            Ok(None) => None,
+            Err(e) => {
+                tracing::error!("Error loading comment by id: {:?}", e);
+                None
+            }
+        }
+    }
+
+    async fn find_comments(&self, txn: &DatabaseTransaction, post_id: Uuid, cursor: Option<i64>, size: u64) -> Option<Vec<QCommentEntity>> {
+        let filter_expr = comment::Column::ArticleId.eq(post_id)
+            .and(comment::Column::ParentId.is_null());
+
+        let filter_expr = if let Some(cursor) = cursor {
+            filter_expr.and(comment::Column::Id.gte(cursor))
+        } else {
+            filter_expr
+        };
+
+        let result = comment::Entity::find()
+            .select_only()
+            .column(comment::Column::Id)
+            .column(comment::Column::ParentId)
+            .column_as(comment::Column::MemberId, "writer_id")
+            .column_as(domain::member::schema::Column::Nickname, "writer_name")
+            .column(comment::Column::Contents)
+            .column(comment::Column::IsDeleted)
+            .column(comment::Column::CreatedAt)
+            .column(comment::Column::UpdatedAt)
+            .join(
+                JoinType::LeftJoin,
+                comment::Relation::Member.def()
+            )
+            .filter(filter_expr)
+            .limit(size)
+            .order_by_asc(comment::Column::Id)
+            .into_model::<QCommentEntity>()
+            .all(txn)
+            .await;
+
+        match result {
+            Ok(comments) => Some(comments),
+            Err(e) => {
+                tracing::error!("Error finding comments: {:?}", e);
+                None
+            }
+        }
+    }
+
+    async fn find_replies(&self, txn: &DatabaseTransaction, parent_ids: Vec<i64>) -> Option<Vec<QCommentEntity>> {
+        if parent_ids.is_empty() {
+            return Some(Vec::new());
+        }
+
+        let placeholders = (1..=parent_ids.len())
+            .map(|idx| format!("${}", idx))
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        let sql = format!(r#"
+            WITH RECURSIVE thread AS (
+                SELECT c.* FROM article_comment c WHERE c.parent_id IN ({})
+                UNION ALL
+                SELECT c.* FROM article_comment c JOIN thread t ON c.parent_id = t.id
+            )
+            SELECT t.id, t.parent_id,
+                t.member_id AS writer_id, m.nickname AS writer_name,
+                t.contents, t.is_deleted, t.created_at, t.updated_at
+            FROM thread t
+            LEFT JOIN member m ON m.id = t.member_id
+            ORDER BY t.id ASC
+        "#, placeholders);
+
+        let values = parent_ids.into_iter()
+            .map(Value::from)
+            .collect::<Vec<Value>>();
+
+        let result = QCommentEntity::find_by_statement(
+                Statement::from_sql_and_values(DbBackend::Postgres, sql, values)
+            )
+            .all(txn)
+            .await;
+
+        match result {
+            Ok(replies) => Some(replies),
+            Err(e) => {
+                tracing::error!("Error finding replies: {:?}", e);
+                None
+            }
+        }
+    }
+
+    async fn has_replies(&self, txn: &DatabaseTransaction, id: i64) -> Result<bool, DbErr> {
+        let count = comment::Entity::find()
+            .filter(comment::Column::ParentId.eq(id))
+            .count(txn)
+            .await?;
+
+        Ok(count > 0)
+    }
+}
+
+#[async_trait::async_trait]
+impl SaveCommentPort for SeaOrmSaveCommentAdapter {
+
+    async fn save(&self, txn: &DatabaseTransaction, comment: CommentEntity) -> Result<CommentEntity, DbErr> {
+        comment_mapper::to_orm(&comment)
+            .insert(txn)
+            .await
+            .map_err(|e| {
+                tracing::error!("Error inserting comment: {:?}", e);
+                e
+            })
+            .map(|x| comment_mapper::to_domain(&x))
+    }
+
+    async fn update(&self, txn: &DatabaseTransaction, comment: CommentEntity) -> Result<CommentEntity, DbErr> {
+        if comment.get_id().is_none() {
+            return Err(DbErr::Custom("Primary key not found".to_string()));
+        }
+
+        let mut active_model = comment_mapper::to_orm(&comment);
+        active_model.updated_at = Set(Some(chrono::Utc::now().naive_utc()));
+        active_model.update(txn)
+            .await
+            .map_err(|e| {
+                tracing::error!("Error updating comment: {:?}", e);
+                e
+            })
+            .map(|x| comment_mapper::to_domain(&x))
+    }
+
+    async fn delete(&self, txn: &DatabaseTransaction, id: i64) -> Result<(), DbErr> {
+        comment::Entity::delete_by_id(id)
+            .exec(txn)
+            .await
+            .map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{LoadCommentPort, SeaOrmLoadCommentAdapter};
+    use crate::domain::board::schema::comment::Model as CommentModel;
+    use sea_orm::{DatabaseConnection, MockDatabase, TransactionTrait};
+
+    fn create_mock_db() -> DatabaseConnection {
+        MockDatabase::new(sea_orm::DatabaseBackend::Postgres)
+            .append_query_results([vec![
+                CommentModel {
+                    id: 1,
+                    member_id: uuid::Uuid::new_v4(),
+                    article_id: uuid::Uuid::new_v4(),
+                    parent_id: None,
+                    contents: "첫 번째 댓글".to_string(),
+                    is_deleted: false,
+                    created_at: chrono::Utc::now().naive_utc(),
+                    updated_at: None,
+                },
+            ]])
+            .into_connection()
+    }
+
+    #[tokio::test]
+    async fn load_by_id_test() {
+        let db: DatabaseConnection = create_mock_db();
+        let txn = db.begin().await.unwrap();
+        let adapter = SeaOrmLoadCommentAdapter {};
+
+        let result = adapter.load_by_id(&txn, 1).await.unwrap();
+        assert_eq!(result.get_id(), Some(1));
+        assert_eq!(result.get_parent_id(), None);
+        assert_eq!(result.get_contents(), "첫 번째 댓글");
+    }
+
+    #[tokio::test]
+    async fn find_replies_without_parents_test() {
+        let db: DatabaseConnection = MockDatabase::new(sea_orm::DatabaseBackend::Postgres)
+            .into_connection();
+        let txn = db.begin().await.unwrap();
+        let adapter = SeaOrmLoadCommentAdapter {};
+
+        let result = adapter.find_replies(&txn, vec![]).await.unwrap();
+        assert!(result.is_empty());
+    }
+}
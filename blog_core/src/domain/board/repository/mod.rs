@@ -1,5 +1,15 @@
 pub mod board_repository;
+pub mod comment_repository;
 pub mod post_repository;
+pub mod post_revision_repository;
+pub mod post_search_repository;
+pub mod reaction_repository;
+pub mod tag_repository;
 
 pub use board_repository::*;
+pub use comment_repository::*;
 pub use post_repository::*;
+pub use post_revision_repository::*;
+pub use post_search_repository::*;
+pub use reaction_repository::*;
+pub use tag_repository::*;
@@ -1,5 +1,7 @@
 // use sea_orm::sea_query::extension::postgres::PgExpr;
 // use sea_orm::sea_query::Func;
+use std::collections::{BTreeMap, HashMap};
+
 use sea_orm::DatabaseTransaction;
 use sea_orm::JoinType;
 use shaku::Component;
@@ -8,21 +10,77 @@ use sea_orm::entity::*;
 use sea_orm::prelude::*;
 use sea_orm::*;
 use chrono::NaiveDateTime;
-use crate::domain::board::entity::command::post_entity::PostEntity;
+use crate::domain::board::entity::command::post_entity::{PostEntity, PostStatus};
 use crate::domain::board::entity::mapper::post_mapper;
-use crate::domain::board::entity::query::QPostEntity;
-use crate::domain::board::schema::post;
+use crate::domain::board::entity::query::{QPostEntity, QPostSitemapEntity};
+use crate::domain::board::schema::{post, post_tag, tag};
 use crate::domain;
 
+/// 게시글 목록 조회 범위
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PostListScope {
+    /// 공개된 글. 최신순은 발행 시각(`published_at`) 순이다.
+    Public,
+    /// 작성자 본인의 특정 상태 글. 최신순은 작성 시각(`created_at`) 순이다.
+    Author { member_id: Uuid, status: PostStatus },
+}
+
+/// 게시글 목록 정렬
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PostListOrder {
+    /// 최신순. 커서 시각 이전의 글부터 가져온다.
+    Latest { cursor: Option<NaiveDateTime> },
+    /// 조회수순. 조회수는 계속 바뀌므로 목록에서의 순번으로 넘긴다.
+    MostViewed { offset: u64 },
+}
+
+/// 목록과 상세 조회에 함께 싣는 요약용 컬럼. 글을 쓸 때 계산해 둔 값이라 본문 전체를 읽지 않는다.
+fn select_summary_columns(query: Select<post::Entity>) -> Select<post::Entity> {
+    query
+        .column(post::Column::ExcerptSource)
+        .column(post::Column::ContentsLength)
+        .column(post::Column::CoverImage)
+}
+
+/// 주어진 이름의 태그가 달린 글의 조건
+pub fn tagged_post_condition(tag: String) -> sea_query::SimpleExpr {
+    post::Column::Id.in_subquery(
+        sea_query::Query::select()
+            .column(post_tag::Column::ArticleId)
+            .from(post_tag::Entity)
+            .inner_join(
+                tag::Entity,
+                Expr::col((tag::Entity, tag::Column::Id)).equals((post_tag::Entity, post_tag::Column::TagId)),
+            )
+            .and_where(Expr::col((tag::Entity, tag::Column::Name)).eq(tag))
+            .to_owned(),
+    )
+}
+
+/// 공개된 글의 조건. `PostStatus::is_public` 과 같은 규칙이다.
+pub fn public_post_condition(now: NaiveDateTime) -> Condition {
+    Condition::all()
+        .add(post::Column::Status.is_in([PostStatus::Published.as_str(), PostStatus::Scheduled.as_str()]))
+        .add(post::Column::PublishedAt.lte(now))
+}
 
 #[async_trait::async_trait]
 pub trait LoadPostPort: Interface {
 
     async fn load_by_id(&self, txn: &DatabaseTransaction, id: Uuid) -> Option<PostEntity>;
 
-    async fn find_posts(&self, txn: &DatabaseTransaction, category_id: Option<i64>, cursor: Option<NaiveDateTime>, size: u64) -> Option<Vec<QPostEntity>>;
+    async fn find_posts(&self, txn: &DatabaseTransaction, category_id: Option<i64>, tag: Option<String>, scope: PostListScope, order: PostListOrder, size: u64) -> Option<Vec<QPostEntity>>;
 
     async fn find_by_id(&self, txn: &DatabaseTransaction, id: Uuid) -> Option<QPostEntity>;
+
+    /// 여러 게시글의 본문 전체를 한 번에 조회한다.
+    async fn find_contents(&self, txn: &DatabaseTransaction, ids: Vec<Uuid>) -> Result<HashMap<Uuid, String>, DbErr>;
+
+    /// 공개된 글 수
+    async fn count_public_posts(&self, txn: &DatabaseTransaction) -> Result<u64, DbErr>;
+
+    /// 사이트맵에 실을 공개된 글을 발행 순으로 offset 부터 limit 개 가져온다.
+    async fn find_sitemap_posts(&self, txn: &DatabaseTransaction, offset: u64, limit: u64) -> Result<Vec<QPostSitemapEntity>, DbErr>;
 }
 
 #[async_trait::async_trait]
@@ -33,6 +91,12 @@ pub trait SavePostPort: Interface {
     async fn update(&self, txn: &DatabaseTransaction, post: PostEntity) -> Result<PostEntity, DbErr>;
 
     async fn delete(&self, txn: &DatabaseTransaction, id: Uuid) -> Result<(), DbErr>;
+
+    /// 예약 시각이 지난 글을 발행 상태로 바꾸고 바뀐 글 수를 돌려준다.
+    async fn publish_due(&self, txn: &DatabaseTransaction, now: NaiveDateTime) -> Result<u64, DbErr>;
+
+    /// 게시글별 조회수 증가분을 더하고 바뀐 글 수를 돌려준다.
+    async fn increase_view_counts(&self, txn: &DatabaseTransaction, counts: &HashMap<Uuid, i64>) -> Result<u64, DbErr>;
 }
 
 #[derive(Component)]
@@ -58,21 +122,31 @@ impl LoadPostPort for SeaOrmLoadPostAdapter {
         }
     }
 
-    async fn find_posts(&self, txn: &DatabaseTransaction, category_id: Option<i64>, cursor: Option<NaiveDateTime>, size: u64) -> Option<Vec<QPostEntity>> {
+    async fn find_posts(&self, txn: &DatabaseTransaction, category_id: Option<i64>, tag: Option<String>, scope: PostListScope, order: PostListOrder, size: u64) -> Option<Vec<QPostEntity>> {
+        let now = chrono::Utc::now().naive_utc();
 
-        let filter_expr = if let Some(category_id) = category_id {
-            post::Column::CategoryId.eq(category_id)
-        } else {
-            post::Column::CategoryId.is_not_null()
-        };
+        let mut condition = Condition::all();
+        if let Some(category_id) = category_id {
+            condition = condition.add(post::Column::CategoryId.eq(category_id));
+        }
+        if let Some(tag) = tag {
+            condition = condition.add(tagged_post_condition(tag));
+        }
 
-        let filter_expr = if let Some(cursor) = cursor {
-            filter_expr.and(post::Column::CreatedAt.lte(cursor))
-        } else {
-            filter_expr.and(post::Column::CreatedAt.lte(chrono::Utc::now().naive_utc()))
+        let time_column = match scope {
+            PostListScope::Public => {
+                condition = condition.add(public_post_condition(now));
+                post::Column::PublishedAt
+            }
+            PostListScope::Author { member_id, status } => {
+                condition = condition
+                    .add(post::Column::MemberId.eq(member_id))
+                    .add(post::Column::Status.eq(status.as_str()));
+                post::Column::CreatedAt
+            }
         };
 
-        let result = post::Entity::find()
+        let mut query = post::Entity::find()
             .select_only()
             .column(post::Column::Id)
             .column_as(post::Column::MemberId, "writer_id")
@@ -80,9 +154,12 @@ impl LoadPostPort for SeaOrmLoadPostAdapter {
             .column_as(post::Column::CategoryId, "category_id")
             .column_as(domain::board::schema::board::Column::Name, "category_name")
             .column(post::Column::Title)
-            .column(post::Column::Contents)
+            .expr_as(Expr::cust("NULL"), "contents")
+            .column(post::Column::Status)
+            .column(post::Column::PublishedAt)
             .column(post::Column::CreatedAt)
             .column(post::Column::UpdatedAt)
+            .column(post::Column::ViewCount)
             .join(
                 JoinType::LeftJoin, 
                 post::Relation::Member.def()
@@ -90,10 +167,22 @@ impl LoadPostPort for SeaOrmLoadPostAdapter {
             .join(
                 JoinType::LeftJoin, 
                 post::Relation::Board.def()
-            )
-            .filter(filter_expr)
+            );
+        query = select_summary_columns(query);
+        query = match order {
+            PostListOrder::Latest { cursor } => query
+                .filter(time_column.lte(cursor.unwrap_or(now)))
+                .order_by_desc(time_column),
+            PostListOrder::MostViewed { offset } => query
+                .order_by_desc(post::Column::ViewCount)
+                .order_by_desc(time_column)
+                .order_by_asc(post::Column::Id)
+                .offset(offset),
+        };
+
+        let result = query
+            .filter(condition)
             .limit(size)
-            .order_by_desc(post::Column::CreatedAt)
             .into_model::<QPostEntity>()
             .all(txn)
             .await;
@@ -110,7 +199,7 @@ impl LoadPostPort for SeaOrmLoadPostAdapter {
     }
 
     async fn find_by_id(&self, txn: &DatabaseTransaction, id: Uuid) -> Option<QPostEntity> {
-        let result = post::Entity::find()
+        let query = post::Entity::find()
             .select_only()
             .column(post::Column::Id)
             .column_as(post::Column::MemberId, "writer_id")
@@ -119,8 +208,11 @@ impl LoadPostPort for SeaOrmLoadPostAdapter {
             .column_as(domain::board::schema::board::Column::Name, "category_name")
             .column(post::Column::Title)
             .column(post::Column::Contents)
+            .column(post::Column::Status)
+            .column(post::Column::PublishedAt)
             .column(post::Column::CreatedAt)
             .column(post::Column::UpdatedAt)
+            .column(post::Column::ViewCount)
             .join(
                 JoinType::LeftJoin, 
                 post::Relation::Member.def()
@@ -128,7 +220,9 @@ impl LoadPostPort for SeaOrmLoadPostAdapter {
             .join(
                 JoinType::LeftJoin, 
                 post::Relation::Board.def()
-            )
+            );
+
+        let result = select_summary_columns(query)
             .filter(post::Column::Id.eq(id))
             .into_model::<QPostEntity>()
             .one(txn)
@@ -145,6 +239,52 @@ impl LoadPostPort for SeaOrmLoadPostAdapter {
             }
         }
     }
+
+    async fn find_contents(&self, txn: &DatabaseTransaction, ids: Vec<Uuid>) -> Result<HashMap<Uuid, String>, DbErr> {
+        if ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let contents: Vec<(Uuid, String)> = post::Entity::find()
+            .select_only()
+            .column(post::Column::Id)
+            .column(post::Column::Contents)
+            .filter(post::Column::Id.is_in(ids))
+            .into_tuple()
+            .all(txn)
+            .await?;
+        Ok(contents.into_iter().collect())
+    }
+
+    async fn count_public_posts(&self, txn: &DatabaseTransaction) -> Result<u64, DbErr> {
+        let now = chrono::Utc::now().naive_utc();
+        post::Entity::find()
+            .filter(public_post_condition(now))
+            .count(txn)
+            .await
+    }
+
+    async fn find_sitemap_posts(&self, txn: &DatabaseTransaction, offset: u64, limit: u64) -> Result<Vec<QPostSitemapEntity>, DbErr> {
+        let now = chrono::Utc::now().naive_utc();
+        // 발행 전에 고친 예약 글은 updated_at 이 published_at 보다 앞설 수 있다.
+        let last_modified = sea_query::Func::greatest([
+            Expr::col((post::Entity, post::Column::PublishedAt)).into(),
+            Expr::col((post::Entity, post::Column::UpdatedAt)).into(),
+        ]);
+
+        post::Entity::find()
+            .select_only()
+            .column(post::Column::Id)
+            .expr_as(last_modified, "last_modified")
+            .filter(public_post_condition(now))
+            .order_by_asc(post::Column::PublishedAt)
+            .order_by_asc(post::Column::Id)
+            .offset(offset)
+            .limit(limit)
+            .into_model::<QPostSitemapEntity>()
+            .all(txn)
+            .await
+    }
 }
 
 #[async_trait::async_trait]
@@ -163,6 +303,8 @@ impl SavePostPort for SeaOrmSavePostAdapter {
     async fn update(&self, txn: &DatabaseTransaction, post: PostEntity) -> Result<PostEntity, DbErr> {
         let mut active_model = post_mapper::to_orm(&post);
         active_model.updated_at = Set(Some(chrono::Utc::now().naive_utc()));
+        // 조회수는 increase_view_counts 로만 바꾼다. 불러온 뒤 더해진 조회수를 덮어쓰지 않도록 제외한다.
+        active_model.view_count = NotSet;
         active_model.update(txn).await
             .map_err(|e| {
                 tracing::error!("Error updating post: {:?}", e);
@@ -182,4 +324,35 @@ impl SavePostPort for SeaOrmSavePostAdapter {
 
         Ok(())
     }
+
+    async fn publish_due(&self, txn: &DatabaseTransaction, now: NaiveDateTime) -> Result<u64, DbErr> {
+        let result = post::Entity::update_many()
+            .col_expr(post::Column::Status, Expr::value(PostStatus::Published.as_str()))
+            .filter(post::Column::Status.eq(PostStatus::Scheduled.as_str()))
+            .filter(post::Column::PublishedAt.lte(now))
+            .exec(txn)
+            .await?;
+
+        Ok(result.rows_affected)
+    }
+
+    async fn increase_view_counts(&self, txn: &DatabaseTransaction, counts: &HashMap<Uuid, i64>) -> Result<u64, DbErr> {
+        // 증가분이 같은 글끼리 묶어 한 번의 UPDATE 로 더한다.
+        let mut groups: BTreeMap<i64, Vec<Uuid>> = BTreeMap::new();
+        for (post_id, count) in counts {
+            groups.entry(*count).or_default().push(*post_id);
+        }
+
+        let mut rows_affected = 0;
+        for (count, post_ids) in groups {
+            let result = post::Entity::update_many()
+                .col_expr(post::Column::ViewCount, Expr::col(post::Column::ViewCount).add(count))
+                .filter(post::Column::Id.is_in(post_ids))
+                .exec(txn)
+                .await?;
+            rows_affected += result.rows_affected;
+        }
+
+        Ok(rows_affected)
+    }
 }
@@ -0,0 +1,119 @@
+use sea_orm::entity::*;
+use sea_orm::prelude::*;
+use sea_orm::*;
+use shaku::Component;
+use shaku::Interface;
+
+use crate::domain;
+use crate::domain::board::entity::command::post_revision_entity::PostRevisionEntity;
+use crate::domain::board::entity::mapper::post_revision_mapper;
+use crate::domain::board::entity::query::QPostRevisionEntity;
+use crate::domain::board::schema::{post, post_revision};
+
+#[async_trait::async_trait]
+pub trait LoadPostRevisionPort: Interface {
+
+    /// 게시글의 리비전을 최신순으로 조회한다.
+    async fn find_by_post_id(&self, txn: &DatabaseTransaction, post_id: Uuid) -> Option<Vec<QPostRevisionEntity>>;
+
+    async fn load_by_revision_no(&self, txn: &DatabaseTransaction, post_id: Uuid, revision_no: i32) -> Option<PostRevisionEntity>;
+
+    /// 게시글 행을 잠근(FOR UPDATE) 뒤 마지막 리비전 번호를 조회한다.
+    /// 같은 게시글에 동시에 리비전을 남겨도 트랜잭션이 끝날 때까지 기다리므로 번호가 겹치지 않는다.
+    async fn lock_latest_revision_no(&self, txn: &DatabaseTransaction, post_id: Uuid) -> Result<Option<i32>, DbErr>;
+}
+
+#[async_trait::async_trait]
+pub trait SavePostRevisionPort: Interface {
+
+    async fn save(&self, txn: &DatabaseTransaction, revision: PostRevisionEntity) -> Result<PostRevisionEntity, DbErr>;
+}
+
+#[derive(Component)]
+#[shaku(interface = LoadPostRevisionPort)]
+pub struct SeaOrmLoadPostRevisionAdapter {}
+
+#[derive(Component)]
+#[shaku(interface = SavePostRevisionPort)]
+pub struct SeaOrmSavePostRevisionAdapter {}
+
+#[async_trait::async_trait]
+impl LoadPostRevisionPort for SeaOrmLoadPostRevisionAdapter {
+
+    async fn find_by_post_id(&self, txn: &DatabaseTransaction, post_id: Uuid) -> Option<Vec<QPostRevisionEntity>> {
+        let result = post_revision::Entity::find()
+            .select_only()
+            .column(post_revision::Column::RevisionNo)
+            .column_as(post_revision::Column::MemberId, "writer_id")
+            .column_as(domain::member::schema::Column::Nickname, "writer_name")
+            .column(post_revision::Column::CategoryId)
+            .column(post_revision::Column::Title)
+            .column(post_revision::Column::RestoredFrom)
+            .column(post_revision::Column::CreatedAt)
+            .join(
+                JoinType::LeftJoin,
+                post_revision::Relation::Member.def()
+            )
+            .filter(post_revision::Column::ArticleId.eq(post_id))
+            .order_by_desc(post_revision::Column::RevisionNo)
+            .into_model::<QPostRevisionEntity>()
+            .all(txn)
+            .await;
+
+        match result {
+            Ok(revisions) => Some(revisions),
+            Err(e) => {
+                tracing::error!("Error finding post revisions: {:?}", e);
+                None
+            }
+        }
+    }
+
+    async fn load_by_revision_no(&self, txn: &DatabaseTransaction, post_id: Uuid, revision_no: i32) -> Option<PostRevisionEntity> {
+        match post_revision::Entity::find()
+            .filter(post_revision::Column::ArticleId.eq(post_id))
+            .filter(post_revision::Column::RevisionNo.eq(revision_no))
+            .one(txn)
+            .await {
+            Ok(revision) => revision.map(|revision| post_revision_mapper::to_domain(&revision)),
+            Err(e) => {
+                tracing::error!("Error loading post revision: {:?}", e);
+                None
+            }
+        }
+    }
+
+    async fn lock_latest_revision_no(&self, txn: &DatabaseTransaction, post_id: Uuid) -> Result<Option<i32>, DbErr> {
+        post::Entity::find_by_id(post_id)
+            .select_only()
+            .column(post::Column::Id)
+            .lock_exclusive()
+            .into_tuple::<Uuid>()
+            .one(txn)
+            .await?;
+
+        post_revision::Entity::find()
+            .select_only()
+            .column_as(post_revision::Column::RevisionNo.max(), "revision_no")
+            .filter(post_revision::Column::ArticleId.eq(post_id))
+            .into_tuple::<Option<i32>>()
+            .one(txn)
+            .await
+            .map(Option::flatten)
+    }
+}
+
+#[async_trait::async_trait]
+impl SavePostRevisionPort for SeaOrmSavePostRevisionAdapter {
+
+    async fn save(&self, txn: &DatabaseTransaction, revision: PostRevisionEntity) -> Result<PostRevisionEntity, DbErr> {
+        post_revision_mapper::to_orm(&revision)
+            .insert(txn)
+            .await
+            .map(|revision| post_revision_mapper::to_domain(&revision))
+            .map_err(|e| {
+                tracing::error!("Error inserting post revision: {:?}", e);
+                e
+            })
+    }
+}
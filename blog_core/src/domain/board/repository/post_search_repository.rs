@@ -0,0 +1,457 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::SystemTime;
+
+use sea_orm::prelude::*;
+use sea_orm::*;
+use shaku::Component;
+use shaku::Interface;
+
+use crate::common::ngram_index::{IndexedDocument, NgramIndex};
+use crate::config::ConfigProvider;
+use crate::domain;
+use crate::domain::board::entity::query::QPostSearchEntity;
+use crate::domain::board::repository::{public_post_condition, tagged_post_condition};
+use crate::domain::board::schema::post;
+
+/// 하이라이트 구분 문자. 본문에 나올 일이 없는 사용자 정의 영역 문자를 쓰고, 응답을 만들 때 이스케이프한 뒤 `<mark>` 로 바꾼다.
+pub const HIGHLIGHT_START: char = '\u{E000}';
+pub const HIGHLIGHT_STOP: char = '\u{E001}';
+
+/// 검색 조건. 공개된 글만 찾는다.
+#[derive(Debug, Clone)]
+pub struct PostSearchQuery {
+    pub keyword: String,
+    pub category_id: Option<i64>,
+    pub tag: Option<String>,
+    pub author_id: Option<Uuid>,
+    pub offset: u64,
+    pub size: u64,
+}
+
+/// 게시글 검색 색인. 저장소는 `search_backend` 설정으로 고른다.
+/// - `postgres` : `article.search_vector` (제목 A, 본문 B 가중치의 생성 컬럼)와 GIN 인덱스를 쓰는 전문 검색.
+///   생성 컬럼이므로 색인을 따로 갱신할 필요가 없다.
+/// - `embedded` : 프로세스 안의 n-gram 색인. 게시글을 쓰고 고치고 지울 때 메모리의 색인을 함께 갱신하고,
+///   바뀐 내용은 스케줄러가 `search_index_path` 파일에 모아서 쓴다. PostgreSQL 전문 검색 없이 개발하거나 테스트할 때 쓴다.
+#[async_trait::async_trait]
+pub trait SearchIndexPort: Interface {
+
+    /// 관련도가 높은 순으로, 같으면 최근에 발행된 순으로 찾는다.
+    async fn search(&self, txn: &DatabaseTransaction, query: PostSearchQuery) -> Result<Vec<QPostSearchEntity>, DbErr>;
+
+    /// 게시글을 색인에 넣거나 고친다. 색인 갱신 실패는 게시글 저장을 막지 않도록 기록만 남긴다.
+    async fn index(&self, document: IndexedDocument);
+
+    async fn remove(&self, post_id: Uuid);
+
+    /// 임베디드 색인의 바뀐 내용을 파일에 쓴다. 쓴 내용이 있으면 true 를 돌려준다.
+    /// 다른 프로세스가 파일을 다시 만들었다면(`--rebuild-search-index`) 먼저 불러온 뒤 아직 쓰지 않은 변경을 얹는다.
+    async fn flush(&self) -> Result<bool, DbErr>;
+
+    /// 모든 게시글로 색인을 다시 만들고 색인한 게시글 수를 돌려준다.
+    async fn rebuild(&self, txn: &DatabaseTransaction) -> Result<u64, DbErr>;
+}
+
+/// 임베디드 색인. 처음 쓸 때 파일에서 불러온다.
+/// 파일 쓰기는 요청을 처리하는 동안 하지 않고 `flush` 에서 색인을 잠그지 않은 채로 한다.
+#[derive(Default)]
+pub(crate) struct EmbeddedSearchIndex {
+    index: RwLock<Option<NgramIndex>>,
+    /// 마지막으로 파일에 쓴 뒤 바뀐 게시글
+    dirty: Mutex<HashSet<Uuid>>,
+    /// 마지막으로 읽거나 쓴 파일의 수정 시각. 달라졌다면 다른 프로세스가 파일을 다시 만든 것이다.
+    file_modified: Mutex<Option<SystemTime>>,
+}
+
+fn modified_at(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+fn index_io_error(e: impl std::fmt::Debug) -> DbErr {
+    DbErr::Custom(format!("Search index file error: {:?}", e))
+}
+
+/// 파일 입출력은 런타임 스레드를 막지 않도록 blocking 스레드에서 한다.
+async fn run_blocking<T: Send + 'static>(f: impl FnOnce() -> std::io::Result<T> + Send + 'static) -> Result<T, DbErr> {
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(index_io_error)?
+        .map_err(index_io_error)
+}
+
+impl EmbeddedSearchIndex {
+    fn load(&self, path: &Path) -> NgramIndex {
+        *self.file_modified.lock().unwrap() = modified_at(path);
+        NgramIndex::load(path).unwrap_or_else(|e| {
+            tracing::error!("Failed to load search index from {:?}, run with --rebuild-search-index: {:?}", path, e);
+            NgramIndex::default()
+        })
+    }
+
+    fn read<R>(&self, path: &Path, f: impl FnOnce(&NgramIndex) -> R) -> R {
+        if let Some(index) = self.index.read().unwrap().as_ref() {
+            return f(index);
+        }
+        let mut index = self.index.write().unwrap();
+        f(index.get_or_insert_with(|| self.load(path)))
+    }
+
+    fn write(&self, path: &Path, post_id: Uuid, f: impl FnOnce(&mut NgramIndex)) {
+        let mut index = self.index.write().unwrap();
+        f(index.get_or_insert_with(|| self.load(path)));
+        self.dirty.lock().unwrap().insert(post_id);
+    }
+
+    async fn flush(&self, path: &Path) -> Result<bool, DbErr> {
+        if self.index.read().unwrap().is_none() {
+            return Ok(false);
+        }
+
+        let path: PathBuf = path.to_owned();
+        let known_modified = *self.file_modified.lock().unwrap();
+        let reloaded = run_blocking({
+            let path = path.clone();
+            move || {
+                let modified = modified_at(&path);
+                if modified.is_none() || modified == known_modified {
+                    return Ok(None);
+                }
+                NgramIndex::load(&path).map(|index| Some((index, modified)))
+            }
+        }).await?;
+
+        let (documents, flushed_ids) = {
+            let mut index = self.index.write().unwrap();
+            let Some(index) = index.as_mut() else {
+                return Ok(false);
+            };
+            let mut dirty = self.dirty.lock().unwrap();
+            if let Some((mut reloaded, modified)) = reloaded {
+                for post_id in dirty.iter() {
+                    match index.document(*post_id) {
+                        Some(document) => reloaded.upsert(document.clone()),
+                        None => reloaded.remove(*post_id),
+                    }
+                }
+                *index = reloaded;
+                *self.file_modified.lock().unwrap() = modified;
+                tracing::info!("Reloaded search index rebuilt by another process from {:?}", path);
+            }
+            if dirty.is_empty() {
+                return Ok(false);
+            }
+            (index.documents(), std::mem::take(&mut *dirty))
+        };
+
+        let saved = run_blocking({
+            let path = path.clone();
+            move || {
+                NgramIndex::save_documents(&path, &documents)?;
+                Ok(modified_at(&path))
+            }
+        }).await;
+        match saved {
+            Ok(modified) => {
+                *self.file_modified.lock().unwrap() = modified;
+                Ok(true)
+            }
+            Err(e) => {
+                // 다음 주기에 다시 쓴다.
+                self.dirty.lock().unwrap().extend(flushed_ids);
+                Err(e)
+            }
+        }
+    }
+
+    /// 색인을 통째로 바꾸고 바로 파일에 쓴다. 저장에 실패하면 에러를 돌려준다.
+    async fn replace(&self, path: &Path, rebuilt: NgramIndex) -> Result<(), DbErr> {
+        let documents = rebuilt.documents();
+        {
+            *self.index.write().unwrap() = Some(rebuilt);
+            self.dirty.lock().unwrap().clear();
+        }
+
+        let path: PathBuf = path.to_owned();
+        let modified = run_blocking(move || {
+            NgramIndex::save_documents(&path, &documents)?;
+            Ok(modified_at(&path))
+        }).await?;
+        *self.file_modified.lock().unwrap() = modified;
+        Ok(())
+    }
+}
+
+/// 임베디드 색인에서 한 번에 가져오는 최대 후보 수. 공개 여부와 필터는 후보를 DB 에서 다시 확인해 거른다.
+const EMBEDDED_CANDIDATE_LIMIT: usize = 1000;
+
+#[derive(Component)]
+#[shaku(interface = SearchIndexPort)]
+pub struct SearchIndexAdapter {
+    #[shaku(inject)]
+    pub(crate) config: Arc<dyn ConfigProvider>,
+    #[shaku(default)]
+    embedded: EmbeddedSearchIndex,
+}
+
+const TS_QUERY: &str = "websearch_to_tsquery('simple', $1)";
+
+fn headline_options(max_words: u32, min_words: u32, max_fragments: u32) -> String {
+    format!(
+        "StartSel={}, StopSel={}, MaxWords={}, MinWords={}, MaxFragments={}, FragmentDelimiter=\" … \"",
+        HIGHLIGHT_START, HIGHLIGHT_STOP, max_words, min_words, max_fragments
+    )
+}
+
+/// 공개된 글 가운데 검색 필터(게시판, 태그, 작성자)에 맞는 글의 조건
+fn search_filter_condition(query: &PostSearchQuery) -> Condition {
+    let mut condition = Condition::all()
+        .add(public_post_condition(chrono::Utc::now().naive_utc()));
+    if let Some(category_id) = query.category_id {
+        condition = condition.add(post::Column::CategoryId.eq(category_id));
+    }
+    if let Some(tag) = query.tag.clone() {
+        condition = condition.add(tagged_post_condition(tag));
+    }
+    if let Some(author_id) = query.author_id {
+        condition = condition.add(post::Column::MemberId.eq(author_id));
+    }
+    condition
+}
+
+/// 검색 결과에 쓰는 작성자, 게시판 정보를 함께 조회한다.
+fn select_search_result() -> Select<post::Entity> {
+    post::Entity::find()
+        .select_only()
+        .column(post::Column::Id)
+        .column_as(post::Column::MemberId, "writer_id")
+        .column_as(domain::member::schema::Column::Nickname, "writer_name")
+        .column_as(post::Column::CategoryId, "category_id")
+        .column_as(domain::board::schema::board::Column::Name, "category_name")
+        .column(post::Column::Title)
+        .join(
+            JoinType::LeftJoin,
+            post::Relation::Member.def()
+        )
+        .join(
+            JoinType::LeftJoin,
+            post::Relation::Board.def()
+        )
+}
+
+impl SearchIndexAdapter {
+    async fn search_postgres(&self, txn: &DatabaseTransaction, query: PostSearchQuery) -> Result<Vec<QPostSearchEntity>, DbErr> {
+        let condition = search_filter_condition(&query)
+            .add(Expr::cust_with_values(
+                format!(r#""article"."search_vector" @@ {}"#, TS_QUERY),
+                [query.keyword.clone()],
+            ));
+        let keyword = query.keyword;
+
+        select_search_result()
+            .expr_as(
+                Expr::cust_with_values(
+                    format!(r#"ts_headline('simple', "article"."title", {}, $2)"#, TS_QUERY),
+                    [keyword.clone(), headline_options(255, 1, 0)],
+                ),
+                "title_highlight",
+            )
+            .expr_as(
+                Expr::cust_with_values(
+                    format!(r#"ts_headline('simple', "article"."contents", {}, $2)"#, TS_QUERY),
+                    [keyword.clone(), headline_options(30, 10, 2)],
+                ),
+                "snippet",
+            )
+            .expr_as(
+                Expr::cust_with_values(
+                    format!(r#"ts_rank("article"."search_vector", {})"#, TS_QUERY),
+                    [keyword],
+                ),
+                "rank",
+            )
+            .column(post::Column::PublishedAt)
+            .filter(condition)
+            .order_by(Expr::col(sea_query::Alias::new("rank")), Order::Desc)
+            .order_by_desc(post::Column::PublishedAt)
+            .order_by_desc(post::Column::Id)
+            .offset(query.offset)
+            .limit(query.size)
+            .into_model::<QPostSearchEntity>()
+            .all(txn)
+            .await
+    }
+
+    /// 임베디드 색인에서 찾은 후보를 DB 에서 공개 여부와 필터로 거른 뒤 색인 점수 순으로 자른다.
+    async fn search_embedded(&self, txn: &DatabaseTransaction, path: &Path, query: PostSearchQuery) -> Result<Vec<QPostSearchEntity>, DbErr> {
+        let hits = self.embedded.read(path, |index| index.search(&query.keyword, EMBEDDED_CANDIDATE_LIMIT));
+        if hits.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let condition = search_filter_condition(&query)
+            .add(post::Column::Id.is_in(hits.iter().map(|(id, _)| *id)));
+        let mut posts: HashMap<Uuid, QPostSearchEntity> = select_search_result()
+            .expr_as(Expr::value(""), "title_highlight")
+            .expr_as(Expr::value(""), "snippet")
+            .expr_as(Expr::value(0.0_f32), "rank")
+            .column(post::Column::PublishedAt)
+            .filter(condition)
+            .into_model::<QPostSearchEntity>()
+            .all(txn)
+            .await?
+            .into_iter()
+            .map(|post| (post.id, post))
+            .collect();
+
+        let posts = hits
+            .into_iter()
+            .filter_map(|(id, rank)| posts.remove(&id).map(|post| (post, rank)))
+            .skip(query.offset as usize)
+            .take(query.size as usize)
+            .collect::<Vec<(QPostSearchEntity, f32)>>();
+
+        Ok(self.embedded.read(path, |index| {
+            posts
+                .into_iter()
+                .map(|(mut post, rank)| {
+                    if let Some((title, snippet)) = index.highlight(post.id, &query.keyword, HIGHLIGHT_START, HIGHLIGHT_STOP) {
+                        post.title_highlight = title;
+                        post.snippet = snippet;
+                    }
+                    post.rank = rank;
+                    post
+                })
+                .collect()
+        }))
+    }
+}
+
+#[async_trait::async_trait]
+impl SearchIndexPort for SearchIndexAdapter {
+
+    async fn search(&self, txn: &DatabaseTransaction, query: PostSearchQuery) -> Result<Vec<QPostSearchEntity>, DbErr> {
+        let config = self.config.get();
+
+        match config.search_backend.as_str() {
+            "embedded" => self.search_embedded(txn, Path::new(&config.search_index_path), query).await,
+            _ => self.search_postgres(txn, query).await,
+        }
+    }
+
+    async fn index(&self, document: IndexedDocument) {
+        let config = self.config.get();
+        if config.search_backend == "embedded" {
+            self.embedded.write(Path::new(&config.search_index_path), document.id, |index| index.upsert(document));
+        }
+    }
+
+    async fn remove(&self, post_id: Uuid) {
+        let config = self.config.get();
+        if config.search_backend == "embedded" {
+            self.embedded.write(Path::new(&config.search_index_path), post_id, |index| index.remove(post_id));
+        }
+    }
+
+    async fn flush(&self) -> Result<bool, DbErr> {
+        let config = self.config.get();
+        if config.search_backend != "embedded" {
+            return Ok(false);
+        }
+        self.embedded.flush(Path::new(&config.search_index_path)).await
+    }
+
+    async fn rebuild(&self, txn: &DatabaseTransaction) -> Result<u64, DbErr> {
+        let config = self.config.get();
+        if config.search_backend != "embedded" {
+            tracing::info!("PostgreSQL search vector is a generated column, nothing to rebuild");
+            return Ok(0);
+        }
+
+        let documents: Vec<IndexedDocument> = post::Entity::find()
+            .all(txn)
+            .await?
+            .into_iter()
+            .map(|post| IndexedDocument {
+                id: post.id,
+                title: post.title,
+                contents: post.contents,
+            })
+            .collect();
+        let rebuilt = NgramIndex::from_documents(documents);
+        let count = rebuilt.document_count() as u64;
+
+        self.embedded.replace(Path::new(&config.search_index_path), rebuilt).await?;
+
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use sea_orm::{DatabaseBackend, MockDatabase, TransactionTrait};
+
+    use crate::config::test_config;
+
+    use super::{EmbeddedSearchIndex, PostSearchQuery, SearchIndexAdapter, SearchIndexPort};
+    use crate::common::ngram_index::{IndexedDocument, NgramIndex};
+
+    #[tokio::test]
+    async fn search_query_test() {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([Vec::<std::collections::BTreeMap<String, sea_orm::Value>>::new()])
+            .into_connection();
+        let txn = db.begin().await.unwrap();
+
+        let adapter = SearchIndexAdapter {
+            config: test_config(&[]),
+            embedded: Default::default(),
+        };
+        let result = adapter
+            .search(&txn, PostSearchQuery {
+                keyword: "러스트 비동기".to_owned(),
+                category_id: Some(1),
+                tag: None,
+                author_id: None,
+                offset: 20,
+                size: 11,
+            })
+            .await
+            .unwrap();
+        assert!(result.is_empty());
+        txn.commit().await.unwrap();
+
+        let log = db.into_transaction_log();
+        let sql = &log[0].statements()[1].sql;
+        assert!(sql.contains(r#""article"."search_vector" @@ websearch_to_tsquery('simple', $"#));
+        assert!(sql.contains(r#"ORDER BY "rank" DESC"#));
+        assert!(sql.contains("LIMIT $") && sql.contains("OFFSET $"));
+    }
+
+    #[tokio::test]
+    async fn embedded_flush_test() {
+        let path = std::env::temp_dir().join(format!("search_index_{}.json", uuid::Uuid::new_v4()));
+        let document = |title: &str| IndexedDocument { id: uuid::Uuid::new_v4(), title: title.to_owned(), contents: String::new() };
+        let (first, rebuilt, added) = (document("first"), document("rebuilt"), document("added"));
+
+        let embedded = EmbeddedSearchIndex::default();
+        embedded.write(&path, first.id, |index| index.upsert(first.clone()));
+        assert!(embedded.flush(&path).await.unwrap());
+        assert!(!embedded.flush(&path).await.unwrap());
+        assert_eq!(NgramIndex::load(&path).unwrap().document_count(), 1);
+
+        // 다른 프로세스가 다시 만든 파일을 불러오고 아직 쓰지 않은 변경을 얹는다.
+        NgramIndex::save_documents(&path, std::slice::from_ref(&rebuilt)).unwrap();
+        let file = std::fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(std::time::SystemTime::now() + std::time::Duration::from_secs(1)).unwrap();
+        embedded.write(&path, added.id, |index| index.upsert(added.clone()));
+        assert!(embedded.flush(&path).await.unwrap());
+
+        let saved = NgramIndex::load(&path).unwrap();
+        assert!(saved.document(first.id).is_none());
+        assert!(saved.document(rebuilt.id).is_some());
+        assert!(saved.document(added.id).is_some());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,156 @@
+use sea_orm::entity::*;
+use sea_orm::prelude::*;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::*;
+use shaku::Component;
+use shaku::Interface;
+
+use crate::domain::board::entity::command::reaction_entity::{ReactionEntity, ReactionTarget};
+use crate::domain::board::entity::mapper::reaction_mapper;
+use crate::domain::board::entity::query::QReactionCountEntity;
+use crate::domain::board::schema::reaction;
+
+#[async_trait::async_trait]
+pub trait LoadReactionPort: Interface {
+
+    /// 여러 게시글의 반응 수를 종류별로 한 번에 조회한다. viewer 가 있으면 viewer 가 남긴 반응인지도 함께 센다.
+    async fn find_post_reactions(&self, txn: &DatabaseTransaction, post_ids: Vec<Uuid>, viewer: Option<Uuid>) -> Result<Vec<QReactionCountEntity>, DbErr>;
+
+    /// 여러 댓글의 반응 수를 종류별로 한 번에 조회한다.
+    async fn find_comment_reactions(&self, txn: &DatabaseTransaction, comment_ids: Vec<i64>, viewer: Option<Uuid>) -> Result<Vec<QReactionCountEntity>, DbErr>;
+}
+
+#[async_trait::async_trait]
+pub trait SaveReactionPort: Interface {
+
+    /// 같은 반응이 있으면 지우고 없으면 남긴다. 반응이 남았으면 true 를 돌려준다.
+    async fn toggle(&self, txn: &DatabaseTransaction, reaction: ReactionEntity) -> Result<bool, DbErr>;
+}
+
+#[derive(Component)]
+#[shaku(interface = LoadReactionPort)]
+pub struct SeaOrmLoadReactionAdapter {}
+
+#[derive(Component)]
+#[shaku(interface = SaveReactionPort)]
+pub struct SeaOrmSaveReactionAdapter {}
+
+/// 대상, 종류별 반응 수를 세는 쿼리
+fn count_reactions(condition: Condition, viewer: Option<Uuid>) -> Selector<SelectModel<QReactionCountEntity>> {
+    let reacted = match viewer {
+        Some(member_id) => Expr::cust_with_values(r#"bool_or("reaction"."member_id" = $1)"#, [member_id]),
+        None => Expr::value(false),
+    };
+
+    reaction::Entity::find()
+        .select_only()
+        .column_as(reaction::Column::ArticleId, "post_id")
+        .column(reaction::Column::CommentId)
+        .column(reaction::Column::Kind)
+        .column_as(reaction::Column::Id.count(), "count")
+        .expr_as(reacted, "reacted")
+        .filter(condition)
+        .group_by(reaction::Column::ArticleId)
+        .group_by(reaction::Column::CommentId)
+        .group_by(reaction::Column::Kind)
+        .order_by(reaction::Column::Id.count(), Order::Desc)
+        .order_by_asc(reaction::Column::Kind)
+        .into_model::<QReactionCountEntity>()
+}
+
+/// 회원이 대상에 남긴 같은 종류의 반응
+fn same_reaction_condition(reaction: &ReactionEntity) -> Condition {
+    let condition = Condition::all()
+        .add(reaction::Column::MemberId.eq(reaction.get_member_id()))
+        .add(reaction::Column::Kind.eq(reaction.get_kind().as_str()));
+
+    match reaction.get_target() {
+        ReactionTarget::Post(post_id) => condition
+            .add(reaction::Column::ArticleId.eq(post_id))
+            .add(reaction::Column::CommentId.is_null()),
+        ReactionTarget::Comment { comment_id, .. } => condition
+            .add(reaction::Column::CommentId.eq(comment_id)),
+    }
+}
+
+#[async_trait::async_trait]
+impl LoadReactionPort for SeaOrmLoadReactionAdapter {
+
+    async fn find_post_reactions(&self, txn: &DatabaseTransaction, post_ids: Vec<Uuid>, viewer: Option<Uuid>) -> Result<Vec<QReactionCountEntity>, DbErr> {
+        if post_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let condition = Condition::all()
+            .add(reaction::Column::ArticleId.is_in(post_ids))
+            .add(reaction::Column::CommentId.is_null());
+        count_reactions(condition, viewer).all(txn).await
+    }
+
+    async fn find_comment_reactions(&self, txn: &DatabaseTransaction, comment_ids: Vec<i64>, viewer: Option<Uuid>) -> Result<Vec<QReactionCountEntity>, DbErr> {
+        if comment_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let condition = Condition::all()
+            .add(reaction::Column::CommentId.is_in(comment_ids));
+        count_reactions(condition, viewer).all(txn).await
+    }
+}
+
+#[async_trait::async_trait]
+impl SaveReactionPort for SeaOrmSaveReactionAdapter {
+
+    async fn toggle(&self, txn: &DatabaseTransaction, reaction: ReactionEntity) -> Result<bool, DbErr> {
+        let deleted = reaction::Entity::delete_many()
+            .filter(same_reaction_condition(&reaction))
+            .exec(txn)
+            .await?;
+        if deleted.rows_affected > 0 {
+            return Ok(false);
+        }
+
+        // 같은 반응을 동시에 남기는 요청이 있어도 유니크 인덱스에 걸린 쪽은 그대로 둔다.
+        reaction::Entity::insert(reaction_mapper::to_orm(&reaction))
+            .on_conflict(OnConflict::new().do_nothing().to_owned())
+            .do_nothing()
+            .exec_without_returning(txn)
+            .await?;
+
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult, TransactionTrait};
+    use uuid::Uuid;
+
+    use crate::domain::board::entity::command::reaction_entity::{ReactionEntity, ReactionKind, ReactionTarget};
+
+    use super::{SaveReactionPort, SeaOrmSaveReactionAdapter};
+
+    #[tokio::test]
+    async fn toggle_reaction_test() {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_exec_results([
+                MockExecResult { last_insert_id: 0, rows_affected: 0 },
+                MockExecResult { last_insert_id: 1, rows_affected: 1 },
+                MockExecResult { last_insert_id: 0, rows_affected: 1 },
+            ])
+            .into_connection();
+        let txn = db.begin().await.unwrap();
+        let reaction = ReactionEntity::new(None, Uuid::new_v4(), ReactionTarget::Post(Uuid::new_v4()), ReactionKind::Like, None);
+
+        assert!(SeaOrmSaveReactionAdapter {}.toggle(&txn, reaction.clone()).await.unwrap());
+        assert!(!SeaOrmSaveReactionAdapter {}.toggle(&txn, reaction).await.unwrap());
+        txn.commit().await.unwrap();
+
+        let log = db.into_transaction_log();
+        let statements = log[0].statements();
+        assert!(statements[1].sql.starts_with(r#"DELETE FROM "reaction""#));
+        assert!(statements[1].sql.contains(r#""comment_id" IS NULL"#));
+        assert!(statements[2].sql.contains("ON CONFLICT") && statements[2].sql.ends_with("DO NOTHING"));
+        assert!(statements[3].sql.starts_with(r#"DELETE FROM "reaction""#));
+    }
+}
@@ -0,0 +1,134 @@
+use sea_orm::entity::*;
+use sea_orm::prelude::*;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::*;
+use shaku::Component;
+use shaku::Interface;
+
+use crate::domain::board::entity::command::tag_entity::TagEntity;
+use crate::domain::board::entity::mapper::tag_mapper;
+use crate::domain::board::entity::query::{QPostTagEntity, QTagEntity};
+use crate::domain::board::repository::public_post_condition;
+use crate::domain::board::schema::{post, post_tag, tag};
+
+#[async_trait::async_trait]
+pub trait LoadTagPort: Interface {
+
+    /// 공개된 글이 하나 이상 달린 태그를 글 수가 많은 순으로 조회한다.
+    async fn find_all(&self, txn: &DatabaseTransaction) -> Result<Vec<QTagEntity>, DbErr>;
+
+    /// 여러 게시글의 태그를 이름순으로 한 번에 조회한다.
+    async fn find_by_post_ids(&self, txn: &DatabaseTransaction, post_ids: Vec<Uuid>) -> Result<Vec<QPostTagEntity>, DbErr>;
+}
+
+#[async_trait::async_trait]
+pub trait SaveTagPort: Interface {
+
+    /// 없는 태그는 만들고, 주어진 이름의 태그를 모두 돌려준다.
+    async fn save_all(&self, txn: &DatabaseTransaction, names: Vec<String>) -> Result<Vec<TagEntity>, DbErr>;
+
+    /// 게시글의 태그를 주어진 태그로 바꾼다.
+    async fn replace_post_tags(&self, txn: &DatabaseTransaction, post_id: Uuid, tag_ids: Vec<i64>) -> Result<(), DbErr>;
+}
+
+#[derive(Component)]
+#[shaku(interface = LoadTagPort)]
+pub struct SeaOrmLoadTagAdapter {}
+
+#[derive(Component)]
+#[shaku(interface = SaveTagPort)]
+pub struct SeaOrmSaveTagAdapter {}
+
+#[async_trait::async_trait]
+impl LoadTagPort for SeaOrmLoadTagAdapter {
+
+    async fn find_all(&self, txn: &DatabaseTransaction) -> Result<Vec<QTagEntity>, DbErr> {
+        let now = chrono::Utc::now().naive_utc();
+        tag::Entity::find()
+            .select_only()
+            .column(tag::Column::Id)
+            .column(tag::Column::Name)
+            .column_as(post::Column::Id.count(), "count")
+            .join(
+                JoinType::InnerJoin,
+                tag::Relation::PostTag.def()
+            )
+            .join(
+                JoinType::InnerJoin,
+                post_tag::Relation::Post
+                    .def()
+                    .on_condition(move |_, _| public_post_condition(now))
+            )
+            .group_by(tag::Column::Id)
+            .order_by(post::Column::Id.count(), Order::Desc)
+            .order_by_asc(tag::Column::Name)
+            .into_model::<QTagEntity>()
+            .all(txn)
+            .await
+    }
+
+    async fn find_by_post_ids(&self, txn: &DatabaseTransaction, post_ids: Vec<Uuid>) -> Result<Vec<QPostTagEntity>, DbErr> {
+        if post_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        post_tag::Entity::find()
+            .select_only()
+            .column_as(post_tag::Column::ArticleId, "post_id")
+            .column(tag::Column::Name)
+            .join(
+                JoinType::InnerJoin,
+                post_tag::Relation::Tag.def()
+            )
+            .filter(post_tag::Column::ArticleId.is_in(post_ids))
+            .order_by_asc(tag::Column::Name)
+            .into_model::<QPostTagEntity>()
+            .all(txn)
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl SaveTagPort for SeaOrmSaveTagAdapter {
+
+    async fn save_all(&self, txn: &DatabaseTransaction, names: Vec<String>) -> Result<Vec<TagEntity>, DbErr> {
+        if names.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // 다른 요청이 같은 태그를 동시에 만들 수 있으므로 이미 있으면 그대로 둔다.
+        tag::Entity::insert_many(
+            names.iter().map(|name| tag_mapper::to_orm(&TagEntity::new(None, name.to_owned(), None)))
+        )
+            .on_conflict(OnConflict::column(tag::Column::Name).do_nothing().to_owned())
+            .do_nothing()
+            .exec_without_returning(txn)
+            .await?;
+
+        tag::Entity::find()
+            .filter(tag::Column::Name.is_in(names))
+            .all(txn)
+            .await
+            .map(|tags| tags.iter().map(tag_mapper::to_domain).collect())
+    }
+
+    async fn replace_post_tags(&self, txn: &DatabaseTransaction, post_id: Uuid, tag_ids: Vec<i64>) -> Result<(), DbErr> {
+        post_tag::Entity::delete_many()
+            .filter(post_tag::Column::ArticleId.eq(post_id))
+            .exec(txn)
+            .await?;
+
+        if tag_ids.is_empty() {
+            return Ok(());
+        }
+
+        post_tag::Entity::insert_many(tag_ids.into_iter().map(|tag_id| post_tag::ActiveModel {
+            article_id: Set(post_id),
+            tag_id: Set(tag_id),
+        }))
+            .exec_without_returning(txn)
+            .await?;
+
+        Ok(())
+    }
+}
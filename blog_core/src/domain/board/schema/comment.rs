@@ -0,0 +1,54 @@
+use chrono::NaiveDateTime;
+use sea_orm::entity::prelude::*;
+use sea_orm::ActiveModelBehavior;
+
+#[derive(Debug, Clone, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "article_comment")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = true)]
+    pub id: i64,
+    pub member_id: Uuid,
+    pub article_id: Uuid,
+    pub parent_id: Option<i64>,
+    #[sea_orm(column_type = "Text")]
+    pub contents: String,
+    pub is_deleted: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::post::Entity",
+        from = "Column::ArticleId",
+        to = "super::post::Column::Id"
+    )]
+    Post,
+    #[sea_orm(
+        belongs_to = "crate::domain::member::schema::Entity",
+        from = "Column::MemberId",
+        to = "crate::domain::member::schema::Column::Id"
+    )]
+    Member,
+    #[sea_orm(
+        belongs_to = "Entity",
+        from = "Column::ParentId",
+        to = "Column::Id"
+    )]
+    Parent,
+}
+
+impl Related<super::post::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Post.def()
+    }
+}
+
+impl Related<crate::domain::member::schema::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Member.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,2 +1,7 @@
 pub mod board;
+pub mod comment;
 pub mod post;
+pub mod post_revision;
+pub mod post_tag;
+pub mod reaction;
+pub mod tag;
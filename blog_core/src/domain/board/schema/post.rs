@@ -14,7 +14,18 @@ pub struct Model {
     pub title: String,
     #[sea_orm(column_type = "Text")]
     pub contents: String,
+    /// 본문 앞부분 (`EXCERPT_SOURCE_CHARS` 글자). 목록 요약을 만든다.
+    #[sea_orm(column_type = "Text")]
+    pub excerpt_source: String,
+    /// 본문 글자 수
+    pub contents_length: i32,
+    /// 본문의 첫 Markdown 이미지 주소
+    #[sea_orm(column_type = "Text", nullable)]
+    pub cover_image: Option<String>,
     pub view_count: i64,
+    #[sea_orm(column_type = "String(StringLen::N(16))")]
+    pub status: String,
+    pub published_at: Option<NaiveDateTime>,
     pub created_at: NaiveDateTime,
     pub updated_at: Option<NaiveDateTime>,
 }
@@ -0,0 +1,50 @@
+use chrono::NaiveDateTime;
+use sea_orm::entity::prelude::*;
+use sea_orm::ActiveModelBehavior;
+
+#[derive(Debug, Clone, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "article_revision")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = true)]
+    pub id: i64,
+    pub article_id: Uuid,
+    pub revision_no: i32,
+    pub member_id: Uuid,
+    pub category_id: i64,
+    #[sea_orm(column_type = "String(StringLen::N(255))")]
+    pub title: String,
+    #[sea_orm(column_type = "Text")]
+    pub contents: String,
+    pub restored_from: Option<i32>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::post::Entity",
+        from = "Column::ArticleId",
+        to = "super::post::Column::Id"
+    )]
+    Post,
+    #[sea_orm(
+        belongs_to = "crate::domain::member::schema::Entity",
+        from = "Column::MemberId",
+        to = "crate::domain::member::schema::Column::Id"
+    )]
+    Member,
+}
+
+impl Related<super::post::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Post.def()
+    }
+}
+
+impl Related<crate::domain::member::schema::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Member.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,100 @@
+use chrono::NaiveDateTime;
+use uuid::Uuid;
+
+/// 스크립트, CI 에서 쓰는 회원의 개인 액세스 토큰. 토큰 원문은 발급할 때 한 번만 보여주고 해시만 저장한다.
+/// 토큰 앞부분(token_prefix)은 목록에서 어떤 토큰인지 알아볼 수 있도록 원문 그대로 저장한다.
+#[derive(Debug, Clone)]
+pub struct AccessTokenEntity {
+    id: Uuid,
+    member_id: Uuid,
+    name: String,
+    token_prefix: String,
+    token_hash: String,
+    /// 토큰으로 쓸 수 있는 권한 (`post:write` 등)
+    scopes: Vec<String>,
+    /// 2단계 인증으로 로그인한 세션에서 발급했는지 여부
+    mfa_authenticated: bool,
+    expires_at: NaiveDateTime,
+    last_used_at: Option<NaiveDateTime>,
+    created_at: NaiveDateTime,
+    revoked_at: Option<NaiveDateTime>,
+}
+
+impl AccessTokenEntity {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: Uuid,
+        member_id: Uuid,
+        name: String,
+        token_prefix: String,
+        token_hash: String,
+        scopes: Vec<String>,
+        mfa_authenticated: bool,
+        expires_at: NaiveDateTime,
+        last_used_at: Option<NaiveDateTime>,
+        created_at: Option<NaiveDateTime>,
+        revoked_at: Option<NaiveDateTime>,
+    ) -> Self {
+        Self {
+            id,
+            member_id,
+            name,
+            token_prefix,
+            token_hash,
+            scopes,
+            mfa_authenticated,
+            expires_at,
+            last_used_at,
+            created_at: created_at.unwrap_or_else(|| chrono::Utc::now().naive_utc()),
+            revoked_at,
+        }
+    }
+
+    pub fn is_usable(&self, now: NaiveDateTime) -> bool {
+        self.revoked_at.is_none() && now < self.expires_at
+    }
+
+    pub fn get_id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn get_member_id(&self) -> Uuid {
+        self.member_id
+    }
+
+    pub fn get_name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn get_token_prefix(&self) -> &String {
+        &self.token_prefix
+    }
+
+    pub fn get_token_hash(&self) -> &String {
+        &self.token_hash
+    }
+
+    pub fn get_scopes(&self) -> &Vec<String> {
+        &self.scopes
+    }
+
+    pub fn is_mfa_authenticated(&self) -> bool {
+        self.mfa_authenticated
+    }
+
+    pub fn get_expires_at(&self) -> NaiveDateTime {
+        self.expires_at
+    }
+
+    pub fn get_last_used_at(&self) -> Option<NaiveDateTime> {
+        self.last_used_at
+    }
+
+    pub fn get_created_at(&self) -> NaiveDateTime {
+        self.created_at
+    }
+
+    pub fn get_revoked_at(&self) -> Option<NaiveDateTime> {
+        self.revoked_at
+    }
+}
@@ -0,0 +1,43 @@
+pub mod access_token_mapper {
+
+    use sea_orm::ActiveValue::Set;
+    use sea_orm::DbErr;
+
+    use crate::domain::member::access_token::entity::AccessTokenEntity;
+    use crate::domain::member::access_token::schema::{ActiveModel, Model};
+
+    pub fn to_domain(orm_model: Model) -> Result<AccessTokenEntity, DbErr> {
+        let scopes: Vec<String> = serde_json::from_value(orm_model.scopes)
+            .map_err(|e| DbErr::Type(format!("Invalid access token scopes: {}", e)))?;
+
+        Ok(AccessTokenEntity::new(
+            orm_model.id,
+            orm_model.member_id,
+            orm_model.name,
+            orm_model.token_prefix,
+            orm_model.token_hash,
+            scopes,
+            orm_model.mfa_authenticated,
+            orm_model.expires_at,
+            orm_model.last_used_at,
+            Some(orm_model.created_at),
+            orm_model.revoked_at,
+        ))
+    }
+
+    pub fn to_orm(entity: &AccessTokenEntity) -> ActiveModel {
+        ActiveModel {
+            id: Set(entity.get_id()),
+            member_id: Set(entity.get_member_id()),
+            name: Set(entity.get_name().clone()),
+            token_prefix: Set(entity.get_token_prefix().clone()),
+            token_hash: Set(entity.get_token_hash().clone()),
+            scopes: Set(serde_json::json!(entity.get_scopes())),
+            mfa_authenticated: Set(entity.is_mfa_authenticated()),
+            expires_at: Set(entity.get_expires_at()),
+            last_used_at: Set(entity.get_last_used_at()),
+            created_at: Set(entity.get_created_at()),
+            revoked_at: Set(entity.get_revoked_at()),
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use chrono::NaiveDateTime;
+use sea_orm::sea_query::Expr;
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseTransaction, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use shaku::{Component, Interface};
+use uuid::Uuid;
+use super::entity::AccessTokenEntity;
+use super::mapper::access_token_mapper;
+use super::schema::{Entity as AccessToken, Column};
+
+#[async_trait::async_trait]
+pub trait LoadAccessTokenPort: Interface {
+
+    async fn find_by_id(&self, txn: &DatabaseTransaction, id: Uuid) -> Result<Option<AccessTokenEntity>, DbErr>;
+
+    async fn find_by_token_hash(&self, txn: &DatabaseTransaction, token_hash: &str) -> Result<Option<AccessTokenEntity>, DbErr>;
+
+    /// 폐기되지 않은 토큰을 최근 발급 순으로 조회한다. (만료된 토큰도 포함)
+    async fn find_by_member_id(&self, txn: &DatabaseTransaction, member_id: Uuid) -> Result<Vec<AccessTokenEntity>, DbErr>;
+}
+
+#[async_trait::async_trait]
+pub trait SaveAccessTokenPort: Interface {
+
+    async fn save(&self, txn: &DatabaseTransaction, entity: AccessTokenEntity) -> Result<AccessTokenEntity, DbErr>;
+
+    async fn touch_last_used(&self, txn: &DatabaseTransaction, id: Uuid, used_at: NaiveDateTime) -> Result<(), DbErr>;
+
+    async fn revoke(&self, txn: &DatabaseTransaction, id: Uuid) -> Result<u64, DbErr>;
+}
+
+#[derive(Component)]
+#[shaku(interface = LoadAccessTokenPort)]
+pub struct SeaormLoadAccessTokenAdapter {}
+
+#[derive(Component)]
+#[shaku(interface = SaveAccessTokenPort)]
+pub struct SeaormSaveAccessTokenAdapter {}
+
+#[async_trait::async_trait]
+impl LoadAccessTokenPort for SeaormLoadAccessTokenAdapter {
+
+    async fn find_by_id(&self, txn: &DatabaseTransaction, id: Uuid) -> Result<Option<AccessTokenEntity>, DbErr> {
+        AccessToken::find_by_id(id)
+            .one(txn)
+            .await?
+            .map(access_token_mapper::to_domain)
+            .transpose()
+    }
+
+    async fn find_by_token_hash(&self, txn: &DatabaseTransaction, token_hash: &str) -> Result<Option<AccessTokenEntity>, DbErr> {
+        AccessToken::find()
+            .filter(Column::TokenHash.eq(token_hash))
+            .one(txn)
+            .await?
+            .map(access_token_mapper::to_domain)
+            .transpose()
+    }
+
+    async fn find_by_member_id(&self, txn: &DatabaseTransaction, member_id: Uuid) -> Result<Vec<AccessTokenEntity>, DbErr> {
+        AccessToken::find()
+            .filter(Column::MemberId.eq(member_id))
+            .filter(Column::RevokedAt.is_null())
+            .order_by_desc(Column::CreatedAt)
+            .all(txn)
+            .await?
+            .into_iter()
+            .map(access_token_mapper::to_domain)
+            .collect()
+    }
+}
+
+#[async_trait::async_trait]
+impl SaveAccessTokenPort for SeaormSaveAccessTokenAdapter {
+
+    async fn save(&self, txn: &DatabaseTransaction, entity: AccessTokenEntity) -> Result<AccessTokenEntity, DbErr> {
+        access_token_mapper::to_orm(&entity)
+            .insert(txn)
+            .await
+            .and_then(access_token_mapper::to_domain)
+    }
+
+    async fn touch_last_used(&self, txn: &DatabaseTransaction, id: Uuid, used_at: NaiveDateTime) -> Result<(), DbErr> {
+        AccessToken::update_many()
+            .col_expr(Column::LastUsedAt, Expr::value(used_at))
+            .filter(Column::Id.eq(id))
+            .exec(txn)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn revoke(&self, txn: &DatabaseTransaction, id: Uuid) -> Result<u64, DbErr> {
+        let result = AccessToken::update_many()
+            .col_expr(Column::RevokedAt, Expr::value(chrono::Utc::now().naive_utc()))
+            .filter(Column::Id.eq(id))
+            .filter(Column::RevokedAt.is_null())
+            .exec(txn)
+            .await?;
+
+        Ok(result.rows_affected)
+    }
+}
@@ -0,0 +1,40 @@
+use chrono::NaiveDateTime;
+use sea_orm::{ActiveModelBehavior, DeriveEntityModel, DeriveRelation, EnumIter, Related, RelationDef, RelationTrait};
+use sea_orm::prelude::*;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "personal_access_token")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub member_id: Uuid,
+    pub name: String,
+    pub token_prefix: String,
+    #[sea_orm(unique)]
+    pub token_hash: String,
+    pub scopes: Json,
+    pub mfa_authenticated: bool,
+    pub expires_at: NaiveDateTime,
+    pub last_used_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub revoked_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "crate::domain::member::schema::Entity",
+        from = "Column::MemberId",
+        to = "crate::domain::member::schema::Column::Id",
+    )]
+    Member,
+}
+
+impl Related<crate::domain::member::schema::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Member.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
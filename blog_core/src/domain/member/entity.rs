@@ -11,4 +11,6 @@ pub struct MemberEntity {
     pub created_at: NaiveDateTime,
     pub updated_at: Option<NaiveDateTime>,
     pub is_activated: bool,
+    /// 본인이 설정한 비밀번호가 있는지 여부 (OAuth2 로 가입한 회원은 false)
+    pub has_password: bool,
 }
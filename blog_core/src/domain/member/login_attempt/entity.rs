@@ -0,0 +1,139 @@
+use chrono::{Duration, NaiveDateTime};
+
+/// 로그인 실패를 세는 단위. 계정(이메일)과 IP 를 따로 센다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoginAttemptScope {
+    Account,
+    Ip,
+}
+
+impl LoginAttemptScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LoginAttemptScope::Account => "ACCOUNT",
+            LoginAttemptScope::Ip => "IP",
+        }
+    }
+
+    pub fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "ACCOUNT" => Some(LoginAttemptScope::Account),
+            "IP" => Some(LoginAttemptScope::Ip),
+            _ => None,
+        }
+    }
+}
+
+/// 잠금 정책. 실패가 threshold 번 쌓이면 base 만큼 잠그고, 그 뒤로 실패할 때마다 잠금 시간을 두 배로 늘린다. (최대 max)
+#[derive(Debug, Clone, Copy)]
+pub struct LockoutPolicy {
+    pub threshold: i32,
+    pub base: Duration,
+    pub max: Duration,
+    /// 마지막 실패 후 이 시간이 지나면 실패 횟수를 처음부터 센다.
+    pub reset_after: Duration,
+}
+
+impl LockoutPolicy {
+    fn lock_duration(&self, failure_count: i32) -> Duration {
+        // 2^20 배면 어떤 설정에서도 max 를 넘으므로 그 이상은 계산하지 않는다.
+        let exponent = (failure_count - self.threshold).clamp(0, 20) as u32;
+        (self.base * 2_i32.pow(exponent)).min(self.max)
+    }
+}
+
+/// 계정 또는 IP 하나의 로그인 실패 기록
+#[derive(Debug, Clone)]
+pub struct LoginAttemptEntity {
+    scope: LoginAttemptScope,
+    key: String,
+    failure_count: i32,
+    last_failed_at: NaiveDateTime,
+    locked_until: Option<NaiveDateTime>,
+}
+
+impl LoginAttemptEntity {
+    pub fn new(
+        scope: LoginAttemptScope,
+        key: String,
+        failure_count: i32,
+        last_failed_at: NaiveDateTime,
+        locked_until: Option<NaiveDateTime>,
+    ) -> Self {
+        Self {
+            scope,
+            key,
+            failure_count,
+            last_failed_at,
+            locked_until,
+        }
+    }
+
+    /// 잠겨 있으면 잠금이 풀리는 시각을 반환한다.
+    pub fn locked_until(&self, now: NaiveDateTime) -> Option<NaiveDateTime> {
+        self.locked_until.filter(|locked_until| *locked_until > now)
+    }
+
+    /// 실패가 기록된 뒤 호출한다. 실패 횟수가 잠글 만큼 쌓였으면 잠금이 풀리는 시각을 반환한다.
+    pub fn lock_after_failure(&mut self, now: NaiveDateTime, policy: &LockoutPolicy) -> Option<NaiveDateTime> {
+        if self.failure_count < policy.threshold {
+            return None;
+        }
+
+        let locked_until = now + policy.lock_duration(self.failure_count);
+        self.locked_until = Some(locked_until);
+        Some(locked_until)
+    }
+
+    pub fn get_scope(&self) -> LoginAttemptScope {
+        self.scope
+    }
+
+    pub fn get_key(&self) -> &String {
+        &self.key
+    }
+
+    pub fn get_failure_count(&self) -> i32 {
+        self.failure_count
+    }
+
+    pub fn get_last_failed_at(&self) -> NaiveDateTime {
+        self.last_failed_at
+    }
+
+    pub fn get_locked_until(&self) -> Option<NaiveDateTime> {
+        self.locked_until
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{LockoutPolicy, LoginAttemptEntity, LoginAttemptScope};
+    use chrono::Duration;
+
+    #[test]
+    fn exponential_backoff_test() {
+        let policy = LockoutPolicy {
+            threshold: 3,
+            base: Duration::seconds(30),
+            max: Duration::seconds(100),
+            reset_after: Duration::minutes(60),
+        };
+        let now = chrono::Utc::now().naive_utc();
+        let lock = |failure_count: i32| {
+            LoginAttemptEntity::new(LoginAttemptScope::Account, "member@dohoon-kim.kr".to_owned(), failure_count, now, None)
+                .lock_after_failure(now, &policy)
+        };
+
+        assert_eq!(lock(1), None);
+        assert_eq!(lock(2), None);
+        assert_eq!(lock(3), Some(now + Duration::seconds(30)));
+        assert_eq!(lock(4), Some(now + Duration::seconds(60)));
+        assert_eq!(lock(5), Some(now + Duration::seconds(100)));
+
+        let mut attempt = LoginAttemptEntity::new(LoginAttemptScope::Ip, "127.0.0.1".to_owned(), 3, now, None);
+        attempt.lock_after_failure(now, &policy);
+        assert!(attempt.locked_until(now).is_some());
+        assert!(attempt.locked_until(now + Duration::seconds(30)).is_none());
+    }
+}
@@ -0,0 +1,31 @@
+pub mod login_attempt_mapper {
+
+    use sea_orm::ActiveValue::Set;
+    use sea_orm::DbErr;
+
+    use crate::domain::member::login_attempt::entity::{LoginAttemptEntity, LoginAttemptScope};
+    use crate::domain::member::login_attempt::schema::{ActiveModel, Model};
+
+    pub fn to_domain(orm_model: Model) -> Result<LoginAttemptEntity, DbErr> {
+        let scope = LoginAttemptScope::from_str(&orm_model.scope)
+            .ok_or_else(|| DbErr::Type(format!("Unknown login attempt scope: {}", orm_model.scope)))?;
+
+        Ok(LoginAttemptEntity::new(
+            scope,
+            orm_model.attempt_key,
+            orm_model.failure_count,
+            orm_model.last_failed_at,
+            orm_model.locked_until,
+        ))
+    }
+
+    pub fn to_orm(entity: &LoginAttemptEntity) -> ActiveModel {
+        ActiveModel {
+            scope: Set(entity.get_scope().as_str().to_owned()),
+            attempt_key: Set(entity.get_key().clone()),
+            failure_count: Set(entity.get_failure_count()),
+            last_failed_at: Set(entity.get_last_failed_at()),
+            locked_until: Set(entity.get_locked_until()),
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use chrono::NaiveDateTime;
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{ColumnTrait, DatabaseTransaction, DbErr, EntityTrait, QueryFilter};
+use shaku::{Component, Interface};
+use super::entity::{LoginAttemptEntity, LoginAttemptScope};
+use super::mapper::login_attempt_mapper;
+use super::schema::{Entity as LoginAttempt, Column};
+
+#[async_trait::async_trait]
+pub trait LoadLoginAttemptPort: Interface {
+
+    async fn find(&self, txn: &DatabaseTransaction, scope: LoginAttemptScope, key: &str) -> Result<Option<LoginAttemptEntity>, DbErr>;
+}
+
+#[async_trait::async_trait]
+pub trait SaveLoginAttemptPort: Interface {
+
+    /// 실패 횟수를 한 문장으로 늘리고 늘어난 기록을 반환한다. 기록이 없으면 새로 만든다.
+    /// 마지막 실패가 reset_before 보다 오래되었으면 처음부터 센다.
+    async fn increment_failure(
+        &self,
+        txn: &DatabaseTransaction,
+        scope: LoginAttemptScope,
+        key: &str,
+        now: NaiveDateTime,
+        reset_before: NaiveDateTime,
+    ) -> Result<LoginAttemptEntity, DbErr>;
+
+    async fn lock(&self, txn: &DatabaseTransaction, scope: LoginAttemptScope, key: &str, locked_until: NaiveDateTime) -> Result<(), DbErr>;
+
+    async fn delete(&self, txn: &DatabaseTransaction, scope: LoginAttemptScope, key: &str) -> Result<(), DbErr>;
+}
+
+#[derive(Component)]
+#[shaku(interface = LoadLoginAttemptPort)]
+pub struct SeaormLoadLoginAttemptAdapter {}
+
+#[derive(Component)]
+#[shaku(interface = SaveLoginAttemptPort)]
+pub struct SeaormSaveLoginAttemptAdapter {}
+
+#[async_trait::async_trait]
+impl LoadLoginAttemptPort for SeaormLoadLoginAttemptAdapter {
+
+    async fn find(&self, txn: &DatabaseTransaction, scope: LoginAttemptScope, key: &str) -> Result<Option<LoginAttemptEntity>, DbErr> {
+        LoginAttempt::find_by_id((scope.as_str().to_owned(), key.to_owned()))
+            .one(txn)
+            .await?
+            .map(login_attempt_mapper::to_domain)
+            .transpose()
+    }
+}
+
+#[async_trait::async_trait]
+impl SaveLoginAttemptPort for SeaormSaveLoginAttemptAdapter {
+
+    async fn increment_failure(
+        &self,
+        txn: &DatabaseTransaction,
+        scope: LoginAttemptScope,
+        key: &str,
+        now: NaiveDateTime,
+        reset_before: NaiveDateTime,
+    ) -> Result<LoginAttemptEntity, DbErr> {
+        let entity = LoginAttemptEntity::new(scope, key.to_owned(), 1, now, None);
+        LoginAttempt::insert(login_attempt_mapper::to_orm(&entity))
+            .on_conflict(
+                OnConflict::columns([Column::Scope, Column::AttemptKey])
+                    .value(
+                        Column::FailureCount,
+                        Expr::case(Expr::col((LoginAttempt, Column::LastFailedAt)).lt(reset_before), 1)
+                            .finally(Expr::col((LoginAttempt, Column::FailureCount)).add(1)),
+                    )
+                    .value(Column::LastFailedAt, now)
+                    .to_owned(),
+            )
+            .exec_with_returning(txn)
+            .await
+            .and_then(login_attempt_mapper::to_domain)
+    }
+
+    async fn lock(&self, txn: &DatabaseTransaction, scope: LoginAttemptScope, key: &str, locked_until: NaiveDateTime) -> Result<(), DbErr> {
+        LoginAttempt::update_many()
+            .col_expr(Column::LockedUntil, Expr::value(locked_until))
+            .filter(Column::Scope.eq(scope.as_str()))
+            .filter(Column::AttemptKey.eq(key))
+            .exec(txn)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, txn: &DatabaseTransaction, scope: LoginAttemptScope, key: &str) -> Result<(), DbErr> {
+        LoginAttempt::delete_many()
+            .filter(Column::Scope.eq(scope.as_str()))
+            .filter(Column::AttemptKey.eq(key))
+            .exec(txn)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -13,6 +13,7 @@ pub mod member_mapper {
             created_at: orm_entity.created_at,
             updated_at: orm_entity.updated_at,
             is_activated: orm_entity.is_activated,
+            has_password: orm_entity.has_password,
         }
     }
 
@@ -26,6 +27,7 @@ pub mod member_mapper {
             created_at: domain_entity.created_at,
             updated_at: domain_entity.updated_at,
             is_activated: domain_entity.is_activated,
+            has_password: domain_entity.has_password,
         }
     }
 }
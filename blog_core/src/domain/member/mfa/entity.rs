@@ -0,0 +1,109 @@
+use chrono::NaiveDateTime;
+use uuid::Uuid;
+
+/// 회원의 TOTP 2단계 인증 설정. 등록을 시작하면 만들어지고, 첫 인증 코드를 확인해야 활성화된다.
+#[derive(Debug, Clone)]
+pub struct MfaEntity {
+    member_id: Uuid,
+    /// base32 로 인코딩된 TOTP secret
+    secret: String,
+    /// 복구 코드의 SHA-256 해시. 사용한 코드는 목록에서 제거한다.
+    recovery_code_hashes: Vec<String>,
+    /// 마지막으로 사용된 TOTP 시간 구간. 같은 코드를 다시 쓰지 못하게 한다.
+    last_used_step: Option<i64>,
+    created_at: NaiveDateTime,
+    enabled_at: Option<NaiveDateTime>,
+}
+
+impl MfaEntity {
+    pub fn new(
+        member_id: Uuid,
+        secret: String,
+        recovery_code_hashes: Vec<String>,
+        last_used_step: Option<i64>,
+        created_at: Option<NaiveDateTime>,
+        enabled_at: Option<NaiveDateTime>,
+    ) -> Self {
+        Self {
+            member_id,
+            secret,
+            recovery_code_hashes,
+            last_used_step,
+            created_at: created_at.unwrap_or_else(|| chrono::Utc::now().naive_utc()),
+            enabled_at,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled_at.is_some()
+    }
+
+    pub fn enable(&mut self, recovery_code_hashes: Vec<String>) {
+        self.enabled_at = Some(chrono::Utc::now().naive_utc());
+        self.recovery_code_hashes = recovery_code_hashes;
+    }
+
+    /// 이전에 사용된 구간보다 뒤의 코드만 받아들인다.
+    pub fn accept_step(&mut self, step: i64) -> bool {
+        if self.last_used_step.is_some_and(|last_used_step| step <= last_used_step) {
+            return false;
+        }
+        self.last_used_step = Some(step);
+        true
+    }
+
+    /// 복구 코드는 한 번만 사용할 수 있다.
+    pub fn use_recovery_code(&mut self, code_hash: &str) -> bool {
+        let before = self.recovery_code_hashes.len();
+        self.recovery_code_hashes.retain(|hash| hash != code_hash);
+        self.recovery_code_hashes.len() < before
+    }
+
+    pub fn get_member_id(&self) -> Uuid {
+        self.member_id
+    }
+
+    pub fn get_secret(&self) -> &String {
+        &self.secret
+    }
+
+    pub fn get_recovery_code_hashes(&self) -> &Vec<String> {
+        &self.recovery_code_hashes
+    }
+
+    pub fn get_last_used_step(&self) -> Option<i64> {
+        self.last_used_step
+    }
+
+    pub fn get_created_at(&self) -> NaiveDateTime {
+        self.created_at
+    }
+
+    pub fn get_enabled_at(&self) -> Option<NaiveDateTime> {
+        self.enabled_at
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::MfaEntity;
+    use uuid::Uuid;
+
+    #[test]
+    fn single_use_test() {
+        let mut mfa = MfaEntity::new(Uuid::new_v4(), "SECRET".to_string(), vec![], None, None, None);
+        assert!(!mfa.is_enabled());
+
+        mfa.enable(vec!["hash-1".to_string(), "hash-2".to_string()]);
+        assert!(mfa.is_enabled());
+
+        assert!(mfa.accept_step(100));
+        assert!(!mfa.accept_step(100));
+        assert!(!mfa.accept_step(99));
+        assert!(mfa.accept_step(101));
+
+        assert!(mfa.use_recovery_code("hash-1"));
+        assert!(!mfa.use_recovery_code("hash-1"));
+        assert_eq!(mfa.get_recovery_code_hashes(), &vec!["hash-2".to_string()]);
+    }
+}
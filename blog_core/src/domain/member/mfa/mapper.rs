@@ -0,0 +1,33 @@
+pub mod mfa_mapper {
+
+    use sea_orm::ActiveValue::Set;
+
+    use crate::common::{AppError, TokenCipher};
+    use crate::domain::member::mfa::entity::MfaEntity;
+    use crate::domain::member::mfa::schema::{ActiveModel, Model};
+
+    /// TOTP secret 은 암호화해서 저장하므로 복호화해서 엔티티에 담는다.
+    pub fn to_domain(orm_model: Model, cipher: &dyn TokenCipher) -> Result<MfaEntity, AppError> {
+        let recovery_code_hashes = serde_json::from_value(orm_model.recovery_codes).unwrap_or_default();
+
+        Ok(MfaEntity::new(
+            orm_model.member_id,
+            cipher.decrypt(&orm_model.secret)?,
+            recovery_code_hashes,
+            orm_model.last_used_step,
+            Some(orm_model.created_at),
+            orm_model.enabled_at,
+        ))
+    }
+
+    pub fn to_orm(entity: &MfaEntity, cipher: &dyn TokenCipher) -> ActiveModel {
+        ActiveModel {
+            member_id: Set(entity.get_member_id()),
+            secret: Set(cipher.encrypt(entity.get_secret())),
+            recovery_codes: Set(serde_json::json!(entity.get_recovery_code_hashes())),
+            last_used_step: Set(entity.get_last_used_step()),
+            created_at: Set(entity.get_created_at()),
+            enabled_at: Set(entity.get_enabled_at()),
+        }
+    }
+}
@@ -0,0 +1,4 @@
+pub mod schema;
+pub mod entity;
+pub mod repository;
+pub mod mapper;
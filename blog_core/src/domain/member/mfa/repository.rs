@@ -0,0 +1,99 @@
+use std::sync::Arc;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{ActiveModelTrait, DatabaseTransaction, EntityTrait};
+use shaku::{Component, Interface};
+use uuid::Uuid;
+use crate::common::error_code::ErrorCode;
+use crate::common::{AppError, TokenCipher};
+use super::entity::MfaEntity;
+use super::mapper::mfa_mapper;
+use super::schema::{Entity as MemberMfa, Column};
+
+#[async_trait::async_trait]
+pub trait LoadMfaPort: Interface {
+
+    async fn find_by_member_id(&self, txn: &DatabaseTransaction, member_id: Uuid) -> Result<Option<MfaEntity>, AppError>;
+}
+
+#[async_trait::async_trait]
+pub trait SaveMfaPort: Interface {
+
+    /// 회원의 설정이 이미 있으면 덮어쓴다. (등록을 다시 시작하는 경우)
+    async fn save(&self, txn: &DatabaseTransaction, entity: MfaEntity) -> Result<MfaEntity, AppError>;
+
+    async fn update(&self, txn: &DatabaseTransaction, entity: MfaEntity) -> Result<MfaEntity, AppError>;
+
+    async fn delete(&self, txn: &DatabaseTransaction, member_id: Uuid) -> Result<(), AppError>;
+}
+
+#[derive(Component)]
+#[shaku(interface = LoadMfaPort)]
+pub struct SeaormLoadMfaAdapter {
+    #[shaku(inject)]
+    pub(crate) token_cipher: Arc<dyn TokenCipher>,
+}
+
+#[derive(Component)]
+#[shaku(interface = SaveMfaPort)]
+pub struct SeaormSaveMfaAdapter {
+    #[shaku(inject)]
+    pub(crate) token_cipher: Arc<dyn TokenCipher>,
+}
+
+fn db_error(e: sea_orm::DbErr) -> AppError {
+    tracing::error!("Error accessing member MFA: {:?}", e);
+    AppError::from(ErrorCode::DbError)
+}
+
+#[async_trait::async_trait]
+impl LoadMfaPort for SeaormLoadMfaAdapter {
+
+    async fn find_by_member_id(&self, txn: &DatabaseTransaction, member_id: Uuid) -> Result<Option<MfaEntity>, AppError> {
+        MemberMfa::find_by_id(member_id)
+            .one(txn)
+            .await
+            .map_err(db_error)?
+            .map(|model| mfa_mapper::to_domain(model, self.token_cipher.as_ref()))
+            .transpose()
+    }
+}
+
+#[async_trait::async_trait]
+impl SaveMfaPort for SeaormSaveMfaAdapter {
+
+    async fn save(&self, txn: &DatabaseTransaction, entity: MfaEntity) -> Result<MfaEntity, AppError> {
+        MemberMfa::insert(mfa_mapper::to_orm(&entity, self.token_cipher.as_ref()))
+            .on_conflict(
+                OnConflict::column(Column::MemberId)
+                    .update_columns([
+                        Column::Secret,
+                        Column::RecoveryCodes,
+                        Column::LastUsedStep,
+                        Column::CreatedAt,
+                        Column::EnabledAt,
+                    ])
+                    .to_owned(),
+            )
+            .exec_without_returning(txn)
+            .await
+            .map_err(db_error)?;
+
+        Ok(entity)
+    }
+
+    async fn update(&self, txn: &DatabaseTransaction, entity: MfaEntity) -> Result<MfaEntity, AppError> {
+        mfa_mapper::to_orm(&entity, self.token_cipher.as_ref())
+            .update(txn)
+            .await
+            .map_err(db_error)
+            .and_then(|model| mfa_mapper::to_domain(model, self.token_cipher.as_ref()))
+    }
+
+    async fn delete(&self, txn: &DatabaseTransaction, member_id: Uuid) -> Result<(), AppError> {
+        MemberMfa::delete_by_id(member_id)
+            .exec(txn)
+            .await
+            .map(|_| ())
+            .map_err(db_error)
+    }
+}
@@ -0,0 +1,34 @@
+use chrono::NaiveDateTime;
+use sea_orm::{ActiveModelBehavior, DeriveEntityModel, DeriveRelation, EnumIter, Related, RelationDef, RelationTrait};
+use sea_orm::prelude::*;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "member_mfa")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub member_id: Uuid,
+    pub secret: String,
+    pub recovery_codes: Json,
+    pub last_used_step: Option<i64>,
+    pub created_at: NaiveDateTime,
+    pub enabled_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "crate::domain::member::schema::Entity",
+        from = "Column::MemberId",
+        to = "crate::domain::member::schema::Column::Id",
+    )]
+    Member,
+}
+
+impl Related<crate::domain::member::schema::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Member.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
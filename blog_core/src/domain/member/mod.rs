@@ -3,3 +3,9 @@ pub mod mapper;
 pub mod repository;
 pub mod schema;
 pub mod oauth2_member;
+pub mod refresh_token;
+pub mod access_token;
+pub mod login_attempt;
+pub mod mfa;
+pub mod session;
+pub mod verification_token;
@@ -2,21 +2,24 @@ pub mod oauth2_member_mapper {
     
     use sea_orm::ActiveValue::{NotSet, Set};
 
+    use crate::common::{AppError, TokenCipher};
     use crate::domain::member::oauth2_member::entity::OAuth2MemberEntity;
     use crate::domain::member::oauth2_member::schema::{Model, ActiveModel};
 
-    pub fn to_domain(orm_model: Model) -> OAuth2MemberEntity {
-        OAuth2MemberEntity::new(
+    /// 저장된 access token 은 복호화해서 엔티티에 담는다.
+    pub fn to_domain(orm_model: Model, cipher: &dyn TokenCipher) -> Result<OAuth2MemberEntity, AppError> {
+        Ok(OAuth2MemberEntity::new(
             Some(orm_model.id),
             orm_model.member_id,
             orm_model.provider,
             orm_model.user_id,
             orm_model.email,
-            orm_model.access_token,
-        )
+            cipher.decrypt(&orm_model.access_token)?,
+        ))
     }
 
-    pub fn to_orm(entity: OAuth2MemberEntity) -> ActiveModel {
+    /// access token 은 암호화해서 저장한다.
+    pub fn to_orm(entity: OAuth2MemberEntity, cipher: &dyn TokenCipher) -> ActiveModel {
         ActiveModel {
             id: if entity.get_id().is_none() {
                 NotSet
@@ -27,7 +30,7 @@ pub mod oauth2_member_mapper {
             user_id: Set(entity.get_user_id().clone()),
             member_id: Set(entity.get_member_id()),
             email: Set(entity.get_email().clone()),
-            access_token: Set(entity.get_access_token().clone()),
+            access_token: Set(cipher.encrypt(entity.get_access_token())),
         }
     }
 }
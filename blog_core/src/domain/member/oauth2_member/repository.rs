@@ -1,15 +1,22 @@
-use sea_orm::ActiveValue::Set;
-use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseTransaction, EntityTrait, QueryFilter};
+use std::sync::Arc;
+use sea_orm::ActiveValue::{Set, Unchanged};
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseTransaction, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder};
 use shaku::{Component, Interface};
 use crate::common::error_code::ErrorCode;
-use crate::common::AppError;
+use crate::common::{AppError, TokenCipher};
 use super::entity::OAuth2MemberEntity;
 use super::mapper::oauth2_member_mapper;
-use super::schema::{Entity as OAuth2Member, Column};
+use super::schema::{ActiveModel, Entity as OAuth2Member, Column};
+
+/// 다시 암호화할 때 한 번에 읽어오는 행 수
+const REENCRYPT_BATCH_SIZE: u64 = 100;
 
 #[derive(Component)]
 #[shaku(interface = LoadOAuth2MemberPort)]
-pub struct SeaormLoadOAuth2MemberAdapter {}
+pub struct SeaormLoadOAuth2MemberAdapter {
+    #[shaku(inject)]
+    pub(crate) token_cipher: Arc<dyn TokenCipher>,
+}
 
 #[async_trait::async_trait]
 impl LoadOAuth2MemberPort for SeaormLoadOAuth2MemberAdapter {
@@ -19,35 +26,55 @@ impl LoadOAuth2MemberPort for SeaormLoadOAuth2MemberAdapter {
         txn: &DatabaseTransaction,
         provider: String,
         user_id: String
-    ) -> Option<OAuth2MemberEntity> {
+    ) -> Result<Option<OAuth2MemberEntity>, AppError> {
 
-        let result = OAuth2Member::find()
+        OAuth2Member::find()
             .filter(Column::Provider.eq(provider))
             .filter(Column::UserId.eq(user_id))
             .one(txn)
-            .await;
-
-        match result {
-            Ok(Some(entity)) => Some(oauth2_member_mapper::to_domain(entity)),
-            Ok(None) => None,
-            Err(e) => {
+            .await
+            .map_err(|e| {
                 tracing::error!("Error loading OAuth2 member: {:?}", e);
-                None
-            }
-        }
+                AppError::from(ErrorCode::DbError)
+            })?
+            .map(|entity| oauth2_member_mapper::to_domain(entity, self.token_cipher.as_ref()))
+            .transpose()
+    }
+
+    async fn find_by_member_id(
+        &self,
+        txn: &DatabaseTransaction,
+        member_id: uuid::Uuid
+    ) -> Result<Vec<OAuth2MemberEntity>, AppError> {
+
+        OAuth2Member::find()
+            .filter(Column::MemberId.eq(member_id))
+            .order_by_asc(Column::Provider)
+            .all(txn)
+            .await
+            .map_err(|e| {
+                tracing::error!("Error loading OAuth2 members: {:?}", e);
+                AppError::from(ErrorCode::DbError)
+            })?
+            .into_iter()
+            .map(|entity| oauth2_member_mapper::to_domain(entity, self.token_cipher.as_ref()))
+            .collect()
     }
 }
 
 #[derive(Component)]
 #[shaku(interface = SaveOAuth2MemberPort)]
-pub struct SeaormSaveOAuth2MemberAdapter {}
+pub struct SeaormSaveOAuth2MemberAdapter {
+    #[shaku(inject)]
+    pub(crate) token_cipher: Arc<dyn TokenCipher>,
+}
 
 #[async_trait::async_trait]
 impl SaveOAuth2MemberPort for SeaormSaveOAuth2MemberAdapter {
 
     async fn save(&self, txn: &DatabaseTransaction, entity: OAuth2MemberEntity) -> Result<OAuth2MemberEntity, AppError> {
 
-        let mut active_model = oauth2_member_mapper::to_orm(entity);
+        let mut active_model = oauth2_member_mapper::to_orm(entity, self.token_cipher.as_ref());
         
         if active_model.id.is_set() {
             return Err(AppError::with_message(ErrorCode::InternalServerError, "Save 메서드는 신규 생성에만 사용해야 합니다."));
@@ -58,11 +85,54 @@ impl SaveOAuth2MemberPort for SeaormSaveOAuth2MemberAdapter {
         active_model
             .insert(txn)
             .await
-            .map(|orm_entity| oauth2_member_mapper::to_domain(orm_entity))
             .map_err(|e| {
                 tracing::error!("Error saving OAuth2 member: {:?}", e);
                 AppError::with_message(ErrorCode::InternalServerError, "Failed to save OAuth2 member")
             })
+            .and_then(|orm_entity| oauth2_member_mapper::to_domain(orm_entity, self.token_cipher.as_ref()))
+    }
+
+    async fn delete(&self, txn: &DatabaseTransaction, id: uuid::Uuid) -> Result<(), AppError> {
+        OAuth2Member::delete_by_id(id)
+            .exec(txn)
+            .await
+            .map(|_| ())
+            .map_err(|e| {
+                tracing::error!("Error deleting OAuth2 member: {:?}", e);
+                AppError::from(ErrorCode::DbError)
+            })
+    }
+
+    async fn reencrypt_access_tokens(&self, txn: &DatabaseTransaction) -> Result<u64, AppError> {
+        let db_error = |e| {
+            tracing::error!("Error re-encrypting OAuth2 access tokens: {:?}", e);
+            AppError::from(ErrorCode::DbError)
+        };
+
+        let mut paginator = OAuth2Member::find()
+            .order_by_asc(Column::Id)
+            .paginate(txn, REENCRYPT_BATCH_SIZE);
+        let mut reencrypted = 0;
+
+        while let Some(models) = paginator.fetch_and_next().await.map_err(db_error)? {
+            for model in models {
+                let Some(access_token) = self.token_cipher.rewrap(&model.access_token)? else {
+                    continue;
+                };
+
+                ActiveModel {
+                    id: Unchanged(model.id),
+                    access_token: Set(access_token),
+                    ..Default::default()
+                }
+                .update(txn)
+                .await
+                .map_err(db_error)?;
+                reencrypted += 1;
+            }
+        }
+
+        Ok(reencrypted)
     }
 }
 
@@ -76,11 +146,22 @@ pub trait LoadOAuth2MemberPort: Interface {
         txn: &DatabaseTransaction,
         provider: String,
         user_id: String
-    ) -> Option<OAuth2MemberEntity>;
+    ) -> Result<Option<OAuth2MemberEntity>, AppError>;
+
+    async fn find_by_member_id(
+        &self,
+        txn: &DatabaseTransaction,
+        member_id: uuid::Uuid
+    ) -> Result<Vec<OAuth2MemberEntity>, AppError>;
 }
 
 #[async_trait::async_trait]
 pub trait SaveOAuth2MemberPort: Interface {
 
     async fn save(&self, txn: &DatabaseTransaction, entity: OAuth2MemberEntity) -> Result<OAuth2MemberEntity, AppError>;
+
+    async fn delete(&self, txn: &DatabaseTransaction, id: uuid::Uuid) -> Result<(), AppError>;
+
+    /// 활성 키로 암호화되어 있지 않은 access token 을 다시 암호화하고, 바뀐 행 수를 반환한다.
+    async fn reencrypt_access_tokens(&self, txn: &DatabaseTransaction) -> Result<u64, AppError>;
 }
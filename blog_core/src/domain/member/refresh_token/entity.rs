@@ -0,0 +1,126 @@
+use chrono::NaiveDateTime;
+use uuid::Uuid;
+
+/// 서버에 저장된 refresh token 한 건. id 는 토큰의 jti 이며,
+/// 같은 로그인에서 회전(rotation)으로 발급된 토큰들은 family_id 를 공유한다.
+#[derive(Debug, Clone)]
+pub struct RefreshTokenEntity {
+    id: Uuid,
+    member_id: Uuid,
+    family_id: Uuid,
+    token_hash: String,
+    expires_at: NaiveDateTime,
+    created_at: NaiveDateTime,
+    revoked_at: Option<NaiveDateTime>,
+    replaced_by: Option<Uuid>,
+}
+
+impl RefreshTokenEntity {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: Uuid,
+        member_id: Uuid,
+        family_id: Uuid,
+        token_hash: String,
+        expires_at: NaiveDateTime,
+        created_at: Option<NaiveDateTime>,
+        revoked_at: Option<NaiveDateTime>,
+        replaced_by: Option<Uuid>,
+    ) -> Self {
+        Self {
+            id,
+            member_id,
+            family_id,
+            token_hash,
+            expires_at,
+            created_at: created_at.unwrap_or_else(|| chrono::Utc::now().naive_utc()),
+            revoked_at,
+            replaced_by,
+        }
+    }
+
+    pub fn is_revoked(&self) -> bool {
+        self.revoked_at.is_some()
+    }
+
+    pub fn is_expired(&self, now: NaiveDateTime) -> bool {
+        self.expires_at <= now
+    }
+
+    pub fn matches(&self, token_hash: &str) -> bool {
+        self.token_hash == token_hash
+    }
+
+    pub fn get_id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn get_member_id(&self) -> Uuid {
+        self.member_id
+    }
+
+    pub fn get_family_id(&self) -> Uuid {
+        self.family_id
+    }
+
+    pub fn get_token_hash(&self) -> &String {
+        &self.token_hash
+    }
+
+    pub fn get_expires_at(&self) -> NaiveDateTime {
+        self.expires_at
+    }
+
+    pub fn get_created_at(&self) -> NaiveDateTime {
+        self.created_at
+    }
+
+    pub fn get_revoked_at(&self) -> Option<NaiveDateTime> {
+        self.revoked_at
+    }
+
+    pub fn get_replaced_by(&self) -> Option<Uuid> {
+        self.replaced_by
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::RefreshTokenEntity;
+    use uuid::Uuid;
+
+    #[test]
+    fn state_test() {
+        let now = chrono::Utc::now().naive_utc();
+        let token = RefreshTokenEntity::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            "hash".to_string(),
+            now + chrono::Duration::days(1),
+            None,
+            None,
+            None,
+        );
+
+        assert!(!token.is_revoked());
+        assert!(!token.is_expired(now));
+        assert!(token.matches("hash"));
+
+        assert!(token.is_expired(now + chrono::Duration::days(2)));
+
+        let next = Uuid::new_v4();
+        let rotated = RefreshTokenEntity::new(
+            token.get_id(),
+            token.get_member_id(),
+            token.get_family_id(),
+            "hash".to_string(),
+            token.get_expires_at(),
+            Some(token.get_created_at()),
+            Some(now),
+            Some(next),
+        );
+        assert!(rotated.is_revoked());
+        assert_eq!(rotated.get_replaced_by(), Some(next));
+    }
+}
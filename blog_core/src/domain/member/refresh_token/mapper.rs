@@ -0,0 +1,33 @@
+pub mod refresh_token_mapper {
+
+    use sea_orm::ActiveValue::Set;
+
+    use crate::domain::member::refresh_token::entity::RefreshTokenEntity;
+    use crate::domain::member::refresh_token::schema::{ActiveModel, Model};
+
+    pub fn to_domain(orm_model: Model) -> RefreshTokenEntity {
+        RefreshTokenEntity::new(
+            orm_model.id,
+            orm_model.member_id,
+            orm_model.family_id,
+            orm_model.token_hash,
+            orm_model.expires_at,
+            Some(orm_model.created_at),
+            orm_model.revoked_at,
+            orm_model.replaced_by,
+        )
+    }
+
+    pub fn to_orm(entity: &RefreshTokenEntity) -> ActiveModel {
+        ActiveModel {
+            id: Set(entity.get_id()),
+            member_id: Set(entity.get_member_id()),
+            family_id: Set(entity.get_family_id()),
+            token_hash: Set(entity.get_token_hash().clone()),
+            expires_at: Set(entity.get_expires_at()),
+            created_at: Set(entity.get_created_at()),
+            revoked_at: Set(entity.get_revoked_at()),
+            replaced_by: Set(entity.get_replaced_by()),
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use sea_orm::sea_query::Expr;
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseTransaction, DbErr, EntityTrait, QueryFilter};
+use shaku::{Component, Interface};
+use uuid::Uuid;
+use super::entity::RefreshTokenEntity;
+use super::mapper::refresh_token_mapper;
+use super::schema::{Entity as RefreshToken, Column};
+
+#[async_trait::async_trait]
+pub trait LoadRefreshTokenPort: Interface {
+
+    async fn find_by_id(&self, txn: &DatabaseTransaction, id: Uuid) -> Result<Option<RefreshTokenEntity>, DbErr>;
+}
+
+#[async_trait::async_trait]
+pub trait SaveRefreshTokenPort: Interface {
+
+    async fn save(&self, txn: &DatabaseTransaction, entity: RefreshTokenEntity) -> Result<RefreshTokenEntity, DbErr>;
+
+    /// 아직 폐기되지 않은 토큰만 새 토큰으로 교체된 것으로 표시한다.
+    /// 동시에 같은 토큰으로 재발급을 요청하면 한 쪽만 true 를 받는다.
+    async fn rotate_if_active(&self, txn: &DatabaseTransaction, id: Uuid, replaced_by: Uuid) -> Result<bool, DbErr>;
+
+    /// 같은 family 에 속한 모든 토큰을 폐기한다.
+    async fn revoke_family(&self, txn: &DatabaseTransaction, family_id: Uuid) -> Result<u64, DbErr>;
+
+    /// 회원에게 발급된 모든 토큰을 폐기한다.
+    async fn revoke_all_by_member(&self, txn: &DatabaseTransaction, member_id: Uuid) -> Result<u64, DbErr>;
+}
+
+#[derive(Component)]
+#[shaku(interface = LoadRefreshTokenPort)]
+pub struct SeaormLoadRefreshTokenAdapter {}
+
+#[derive(Component)]
+#[shaku(interface = SaveRefreshTokenPort)]
+pub struct SeaormSaveRefreshTokenAdapter {}
+
+#[async_trait::async_trait]
+impl LoadRefreshTokenPort for SeaormLoadRefreshTokenAdapter {
+
+    async fn find_by_id(&self, txn: &DatabaseTransaction, id: Uuid) -> Result<Option<RefreshTokenEntity>, DbErr> {
+        let result = RefreshToken::find_by_id(id)
+            .one(txn)
+            .await?;
+
+        Ok(result.map(refresh_token_mapper::to_domain))
+    }
+}
+
+#[async_trait::async_trait]
+impl SaveRefreshTokenPort for SeaormSaveRefreshTokenAdapter {
+
+    async fn save(&self, txn: &DatabaseTransaction, entity: RefreshTokenEntity) -> Result<RefreshTokenEntity, DbErr> {
+        refresh_token_mapper::to_orm(&entity)
+            .insert(txn)
+            .await
+            .map(refresh_token_mapper::to_domain)
+    }
+
+    async fn rotate_if_active(&self, txn: &DatabaseTransaction, id: Uuid, replaced_by: Uuid) -> Result<bool, DbErr> {
+        let result = RefreshToken::update_many()
+            .col_expr(Column::RevokedAt, Expr::value(chrono::Utc::now().naive_utc()))
+            .col_expr(Column::ReplacedBy, Expr::value(replaced_by))
+            .filter(Column::Id.eq(id))
+            .filter(Column::RevokedAt.is_null())
+            .exec(txn)
+            .await?;
+
+        Ok(result.rows_affected == 1)
+    }
+
+    async fn revoke_family(&self, txn: &DatabaseTransaction, family_id: Uuid) -> Result<u64, DbErr> {
+        let result = RefreshToken::update_many()
+            .col_expr(Column::RevokedAt, Expr::value(chrono::Utc::now().naive_utc()))
+            .filter(Column::FamilyId.eq(family_id))
+            .filter(Column::RevokedAt.is_null())
+            .exec(txn)
+            .await?;
+
+        Ok(result.rows_affected)
+    }
+
+    async fn revoke_all_by_member(&self, txn: &DatabaseTransaction, member_id: Uuid) -> Result<u64, DbErr> {
+        let result = RefreshToken::update_many()
+            .col_expr(Column::RevokedAt, Expr::value(chrono::Utc::now().naive_utc()))
+            .filter(Column::MemberId.eq(member_id))
+            .filter(Column::RevokedAt.is_null())
+            .exec(txn)
+            .await?;
+
+        Ok(result.rows_affected)
+    }
+}
@@ -71,6 +71,7 @@ impl SaveMemberPort for MemberCommandRepository {
         orm_entity.password = Set(member.password.to_owned());
         orm_entity.role = Set(member.role.to_owned());
         orm_entity.is_activated = Set(member.is_activated.to_owned());
+        orm_entity.has_password = Set(member.has_password);
         orm_entity.created_at = Set(member.created_at.to_owned());
         orm_entity.updated_at = Set(Some(chrono::Utc::now().naive_utc()));
 
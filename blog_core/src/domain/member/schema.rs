@@ -14,6 +14,7 @@ pub struct Model {
     pub created_at: NaiveDateTime,
     pub updated_at: Option<NaiveDateTime>,
     pub is_activated: bool,
+    pub has_password: bool,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
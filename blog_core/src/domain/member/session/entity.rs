@@ -0,0 +1,100 @@
+use chrono::NaiveDateTime;
+use uuid::Uuid;
+
+/// 로그인 세션. 세션 id 는 해당 로그인에서 발급된 refresh token 들의 family_id 와 같다.
+#[derive(Debug, Clone)]
+pub struct SessionEntity {
+    id: Uuid,
+    member_id: Uuid,
+    user_agent: Option<String>,
+    ip_address: Option<String>,
+    created_at: NaiveDateTime,
+    last_used_at: NaiveDateTime,
+    /// 마지막으로 발급한 refresh token 의 만료 시각. 이후로는 재발급할 수 없으므로 세션도 끝난다.
+    expires_at: NaiveDateTime,
+    revoked_at: Option<NaiveDateTime>,
+    /// 로그인 시 2단계 인증(TOTP)을 거쳤는지 여부. access token 을 재발급해도 유지된다.
+    mfa_authenticated: bool,
+}
+
+impl SessionEntity {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: Uuid,
+        member_id: Uuid,
+        user_agent: Option<String>,
+        ip_address: Option<String>,
+        created_at: Option<NaiveDateTime>,
+        last_used_at: Option<NaiveDateTime>,
+        expires_at: NaiveDateTime,
+        revoked_at: Option<NaiveDateTime>,
+        mfa_authenticated: bool,
+    ) -> Self {
+        let now = chrono::Utc::now().naive_utc();
+        Self {
+            id,
+            member_id,
+            user_agent: user_agent.map(|value| value.chars().take(512).collect()),
+            ip_address,
+            created_at: created_at.unwrap_or(now),
+            last_used_at: last_used_at.unwrap_or(now),
+            expires_at,
+            revoked_at,
+            mfa_authenticated,
+        }
+    }
+
+    /// 폐기되지 않았고 refresh token 이 아직 만료되지 않은 세션
+    pub fn is_active(&self, now: NaiveDateTime) -> bool {
+        self.revoked_at.is_none() && self.expires_at > now
+    }
+
+    pub fn check_ownership(&self, member_id: Uuid) -> bool {
+        self.member_id == member_id
+    }
+
+    /// 토큰을 재발급할 때 사용 시각과 새 refresh token 의 만료 시각을 기록한다.
+    pub fn touch(&mut self, ip_address: Option<String>, expires_at: NaiveDateTime) {
+        self.last_used_at = chrono::Utc::now().naive_utc();
+        self.expires_at = expires_at;
+        if ip_address.is_some() {
+            self.ip_address = ip_address;
+        }
+    }
+
+    pub fn get_id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn get_member_id(&self) -> Uuid {
+        self.member_id
+    }
+
+    pub fn get_user_agent(&self) -> &Option<String> {
+        &self.user_agent
+    }
+
+    pub fn get_ip_address(&self) -> &Option<String> {
+        &self.ip_address
+    }
+
+    pub fn get_created_at(&self) -> NaiveDateTime {
+        self.created_at
+    }
+
+    pub fn get_last_used_at(&self) -> NaiveDateTime {
+        self.last_used_at
+    }
+
+    pub fn get_expires_at(&self) -> NaiveDateTime {
+        self.expires_at
+    }
+
+    pub fn get_revoked_at(&self) -> Option<NaiveDateTime> {
+        self.revoked_at
+    }
+
+    pub fn is_mfa_authenticated(&self) -> bool {
+        self.mfa_authenticated
+    }
+}
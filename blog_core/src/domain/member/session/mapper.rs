@@ -0,0 +1,35 @@
+pub mod session_mapper {
+
+    use sea_orm::ActiveValue::Set;
+
+    use crate::domain::member::session::entity::SessionEntity;
+    use crate::domain::member::session::schema::{ActiveModel, Model};
+
+    pub fn to_domain(orm_model: Model) -> SessionEntity {
+        SessionEntity::new(
+            orm_model.id,
+            orm_model.member_id,
+            orm_model.user_agent,
+            orm_model.ip_address,
+            Some(orm_model.created_at),
+            Some(orm_model.last_used_at),
+            orm_model.expires_at,
+            orm_model.revoked_at,
+            orm_model.mfa_authenticated,
+        )
+    }
+
+    pub fn to_orm(entity: &SessionEntity) -> ActiveModel {
+        ActiveModel {
+            id: Set(entity.get_id()),
+            member_id: Set(entity.get_member_id()),
+            user_agent: Set(entity.get_user_agent().clone()),
+            ip_address: Set(entity.get_ip_address().clone()),
+            created_at: Set(entity.get_created_at()),
+            last_used_at: Set(entity.get_last_used_at()),
+            expires_at: Set(entity.get_expires_at()),
+            revoked_at: Set(entity.get_revoked_at()),
+            mfa_authenticated: Set(entity.is_mfa_authenticated()),
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use sea_orm::sea_query::Expr;
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseTransaction, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use shaku::{Component, Interface};
+use uuid::Uuid;
+use super::entity::SessionEntity;
+use super::mapper::session_mapper;
+use super::schema::{Entity as Session, Column};
+
+#[async_trait::async_trait]
+pub trait LoadSessionPort: Interface {
+
+    async fn find_by_id(&self, txn: &DatabaseTransaction, id: Uuid) -> Result<Option<SessionEntity>, DbErr>;
+
+    /// 폐기되지 않았고 만료되지 않은 세션을 최근 사용 순으로 조회한다.
+    async fn find_active_by_member_id(&self, txn: &DatabaseTransaction, member_id: Uuid) -> Result<Vec<SessionEntity>, DbErr>;
+}
+
+#[async_trait::async_trait]
+pub trait SaveSessionPort: Interface {
+
+    async fn save(&self, txn: &DatabaseTransaction, entity: SessionEntity) -> Result<SessionEntity, DbErr>;
+
+    async fn update(&self, txn: &DatabaseTransaction, entity: SessionEntity) -> Result<SessionEntity, DbErr>;
+
+    async fn revoke(&self, txn: &DatabaseTransaction, id: Uuid) -> Result<u64, DbErr>;
+
+    async fn revoke_all_by_member(&self, txn: &DatabaseTransaction, member_id: Uuid) -> Result<u64, DbErr>;
+}
+
+#[derive(Component)]
+#[shaku(interface = LoadSessionPort)]
+pub struct SeaormLoadSessionAdapter {}
+
+#[derive(Component)]
+#[shaku(interface = SaveSessionPort)]
+pub struct SeaormSaveSessionAdapter {}
+
+#[async_trait::async_trait]
+impl LoadSessionPort for SeaormLoadSessionAdapter {
+
+    async fn find_by_id(&self, txn: &DatabaseTransaction, id: Uuid) -> Result<Option<SessionEntity>, DbErr> {
+        let result = Session::find_by_id(id)
+            .one(txn)
+            .await?;
+
+        Ok(result.map(session_mapper::to_domain))
+    }
+
+    async fn find_active_by_member_id(&self, txn: &DatabaseTransaction, member_id: Uuid) -> Result<Vec<SessionEntity>, DbErr> {
+        let result = Session::find()
+            .filter(Column::MemberId.eq(member_id))
+            .filter(Column::RevokedAt.is_null())
+            .filter(Column::ExpiresAt.gt(chrono::Utc::now().naive_utc()))
+            .order_by_desc(Column::LastUsedAt)
+            .all(txn)
+            .await?;
+
+        Ok(result.into_iter().map(session_mapper::to_domain).collect())
+    }
+}
+
+#[async_trait::async_trait]
+impl SaveSessionPort for SeaormSaveSessionAdapter {
+
+    async fn save(&self, txn: &DatabaseTransaction, entity: SessionEntity) -> Result<SessionEntity, DbErr> {
+        session_mapper::to_orm(&entity)
+            .insert(txn)
+            .await
+            .map(session_mapper::to_domain)
+    }
+
+    async fn update(&self, txn: &DatabaseTransaction, entity: SessionEntity) -> Result<SessionEntity, DbErr> {
+        session_mapper::to_orm(&entity)
+            .update(txn)
+            .await
+            .map(session_mapper::to_domain)
+    }
+
+    async fn revoke(&self, txn: &DatabaseTransaction, id: Uuid) -> Result<u64, DbErr> {
+        let result = Session::update_many()
+            .col_expr(Column::RevokedAt, Expr::value(chrono::Utc::now().naive_utc()))
+            .filter(Column::Id.eq(id))
+            .filter(Column::RevokedAt.is_null())
+            .exec(txn)
+            .await?;
+
+        Ok(result.rows_affected)
+    }
+
+    async fn revoke_all_by_member(&self, txn: &DatabaseTransaction, member_id: Uuid) -> Result<u64, DbErr> {
+        let result = Session::update_many()
+            .col_expr(Column::RevokedAt, Expr::value(chrono::Utc::now().naive_utc()))
+            .filter(Column::MemberId.eq(member_id))
+            .filter(Column::RevokedAt.is_null())
+            .exec(txn)
+            .await?;
+
+        Ok(result.rows_affected)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{LoadSessionPort, SeaormLoadSessionAdapter};
+    use crate::domain::member::session::schema::Model as SessionModel;
+    use sea_orm::{DatabaseConnection, MockDatabase, TransactionTrait};
+
+    #[tokio::test]
+    async fn find_active_by_member_id_test() {
+        let member_id = uuid::Uuid::new_v4();
+        let now = chrono::Utc::now().naive_utc();
+        let db: DatabaseConnection = MockDatabase::new(sea_orm::DatabaseBackend::Postgres)
+            .append_query_results([vec![
+                SessionModel {
+                    id: uuid::Uuid::new_v4(),
+                    member_id,
+                    user_agent: Some("Mozilla/5.0".to_string()),
+                    ip_address: Some("127.0.0.1".to_string()),
+                    created_at: chrono::Utc::now().naive_utc(),
+                    last_used_at: now,
+                    expires_at: now + chrono::Duration::days(1),
+                    revoked_at: None,
+                    mfa_authenticated: false,
+                },
+            ]])
+            .into_connection();
+        let txn = db.begin().await.unwrap();
+        let adapter = SeaormLoadSessionAdapter {};
+
+        let result = adapter.find_active_by_member_id(&txn, member_id).await.unwrap();
+        assert_eq!(result.len(), 1);
+        assert!(result[0].is_active(now));
+        // refresh token 이 만료되면 세션도 끝난다.
+        assert!(!result[0].is_active(now + chrono::Duration::days(2)));
+        assert_eq!(result[0].get_user_agent().as_deref(), Some("Mozilla/5.0"));
+    }
+}
@@ -0,0 +1,89 @@
+use chrono::NaiveDateTime;
+use uuid::Uuid;
+
+/// 메일 링크 등으로 전달하는 일회용 토큰의 용도
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerificationPurpose {
+    PasswordReset,
+    EmailVerification,
+    OAuth2Link,
+}
+
+impl VerificationPurpose {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VerificationPurpose::PasswordReset => "PASSWORD_RESET",
+            VerificationPurpose::EmailVerification => "EMAIL_VERIFICATION",
+            VerificationPurpose::OAuth2Link => "OAUTH2_LINK",
+        }
+    }
+
+    pub fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "PASSWORD_RESET" => Some(VerificationPurpose::PasswordReset),
+            "EMAIL_VERIFICATION" => Some(VerificationPurpose::EmailVerification),
+            "OAUTH2_LINK" => Some(VerificationPurpose::OAuth2Link),
+            _ => None,
+        }
+    }
+}
+
+/// 비밀번호 재설정, 이메일 인증 메일로 발급한 토큰 한 건. id 는 서명된 토큰의 jti 이다.
+/// 서명만으로는 재사용을 막을 수 없으므로 사용 시각(used_at)을 기록해 한 번만 쓸 수 있게 한다.
+#[derive(Debug, Clone)]
+pub struct VerificationTokenEntity {
+    id: Uuid,
+    member_id: Uuid,
+    purpose: VerificationPurpose,
+    expires_at: NaiveDateTime,
+    created_at: NaiveDateTime,
+    used_at: Option<NaiveDateTime>,
+}
+
+impl VerificationTokenEntity {
+    pub fn new(
+        id: Uuid,
+        member_id: Uuid,
+        purpose: VerificationPurpose,
+        expires_at: NaiveDateTime,
+        created_at: Option<NaiveDateTime>,
+        used_at: Option<NaiveDateTime>,
+    ) -> Self {
+        Self {
+            id,
+            member_id,
+            purpose,
+            expires_at,
+            created_at: created_at.unwrap_or_else(|| chrono::Utc::now().naive_utc()),
+            used_at,
+        }
+    }
+
+    pub fn is_usable(&self, purpose: VerificationPurpose, now: NaiveDateTime) -> bool {
+        self.purpose == purpose && self.used_at.is_none() && now < self.expires_at
+    }
+
+    pub fn get_id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn get_member_id(&self) -> Uuid {
+        self.member_id
+    }
+
+    pub fn get_purpose(&self) -> VerificationPurpose {
+        self.purpose
+    }
+
+    pub fn get_expires_at(&self) -> NaiveDateTime {
+        self.expires_at
+    }
+
+    pub fn get_created_at(&self) -> NaiveDateTime {
+        self.created_at
+    }
+
+    pub fn get_used_at(&self) -> Option<NaiveDateTime> {
+        self.used_at
+    }
+}
@@ -0,0 +1,33 @@
+pub mod verification_token_mapper {
+
+    use sea_orm::ActiveValue::Set;
+    use sea_orm::DbErr;
+
+    use crate::domain::member::verification_token::entity::{VerificationPurpose, VerificationTokenEntity};
+    use crate::domain::member::verification_token::schema::{ActiveModel, Model};
+
+    pub fn to_domain(orm_model: Model) -> Result<VerificationTokenEntity, DbErr> {
+        let purpose = VerificationPurpose::from_str(&orm_model.purpose)
+            .ok_or_else(|| DbErr::Type(format!("Unknown verification purpose: {}", orm_model.purpose)))?;
+
+        Ok(VerificationTokenEntity::new(
+            orm_model.id,
+            orm_model.member_id,
+            purpose,
+            orm_model.expires_at,
+            Some(orm_model.created_at),
+            orm_model.used_at,
+        ))
+    }
+
+    pub fn to_orm(entity: &VerificationTokenEntity) -> ActiveModel {
+        ActiveModel {
+            id: Set(entity.get_id()),
+            member_id: Set(entity.get_member_id()),
+            purpose: Set(entity.get_purpose().as_str().to_owned()),
+            expires_at: Set(entity.get_expires_at()),
+            created_at: Set(entity.get_created_at()),
+            used_at: Set(entity.get_used_at()),
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use sea_orm::sea_query::Expr;
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseTransaction, DbErr, EntityTrait, QueryFilter};
+use shaku::{Component, Interface};
+use uuid::Uuid;
+use super::entity::{VerificationPurpose, VerificationTokenEntity};
+use super::mapper::verification_token_mapper;
+use super::schema::{Entity as VerificationToken, Column};
+
+#[async_trait::async_trait]
+pub trait LoadVerificationTokenPort: Interface {
+
+    async fn find_by_id(&self, txn: &DatabaseTransaction, id: Uuid) -> Result<Option<VerificationTokenEntity>, DbErr>;
+}
+
+#[async_trait::async_trait]
+pub trait SaveVerificationTokenPort: Interface {
+
+    async fn save(&self, txn: &DatabaseTransaction, entity: VerificationTokenEntity) -> Result<VerificationTokenEntity, DbErr>;
+
+    /// 아직 쓰이지 않은 토큰만 사용 처리하고 성공 여부를 반환한다. 같은 토큰이 동시에 제출되어도 한 번만 성공한다.
+    async fn mark_used(&self, txn: &DatabaseTransaction, id: Uuid) -> Result<bool, DbErr>;
+
+    /// 회원에게 같은 용도로 발급되어 아직 쓰이지 않은 토큰을 모두 사용 처리한다. (가장 최근 메일의 링크만 유효)
+    async fn expire_unused(&self, txn: &DatabaseTransaction, member_id: Uuid, purpose: VerificationPurpose) -> Result<u64, DbErr>;
+}
+
+#[derive(Component)]
+#[shaku(interface = LoadVerificationTokenPort)]
+pub struct SeaormLoadVerificationTokenAdapter {}
+
+#[derive(Component)]
+#[shaku(interface = SaveVerificationTokenPort)]
+pub struct SeaormSaveVerificationTokenAdapter {}
+
+#[async_trait::async_trait]
+impl LoadVerificationTokenPort for SeaormLoadVerificationTokenAdapter {
+
+    async fn find_by_id(&self, txn: &DatabaseTransaction, id: Uuid) -> Result<Option<VerificationTokenEntity>, DbErr> {
+        VerificationToken::find_by_id(id)
+            .one(txn)
+            .await?
+            .map(verification_token_mapper::to_domain)
+            .transpose()
+    }
+}
+
+#[async_trait::async_trait]
+impl SaveVerificationTokenPort for SeaormSaveVerificationTokenAdapter {
+
+    async fn save(&self, txn: &DatabaseTransaction, entity: VerificationTokenEntity) -> Result<VerificationTokenEntity, DbErr> {
+        verification_token_mapper::to_orm(&entity)
+            .insert(txn)
+            .await
+            .and_then(verification_token_mapper::to_domain)
+    }
+
+    async fn mark_used(&self, txn: &DatabaseTransaction, id: Uuid) -> Result<bool, DbErr> {
+        let result = VerificationToken::update_many()
+            .col_expr(Column::UsedAt, Expr::value(chrono::Utc::now().naive_utc()))
+            .filter(Column::Id.eq(id))
+            .filter(Column::UsedAt.is_null())
+            .exec(txn)
+            .await?;
+
+        Ok(result.rows_affected == 1)
+    }
+
+    async fn expire_unused(&self, txn: &DatabaseTransaction, member_id: Uuid, purpose: VerificationPurpose) -> Result<u64, DbErr> {
+        let result = VerificationToken::update_many()
+            .col_expr(Column::UsedAt, Expr::value(chrono::Utc::now().naive_utc()))
+            .filter(Column::MemberId.eq(member_id))
+            .filter(Column::Purpose.eq(purpose.as_str()))
+            .filter(Column::UsedAt.is_null())
+            .exec(txn)
+            .await?;
+
+        Ok(result.rows_affected)
+    }
+}
@@ -13,7 +13,37 @@ pub struct LoginResponse {
     pub refresh_token: String,
 }
 
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MfaChallengeResponse {
+    pub mfa_token: String,
+    pub expires_in: i64,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MfaLoginRequest {
+    pub mfa_token: String,
+    /// TOTP 코드 또는 복구 코드
+    pub code: String,
+}
+
 #[derive(Deserialize, Serialize, Debug)]
 pub struct JwtReissueResponse {
     pub access_token: String,
+    pub refresh_token: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct PasswordResetRequest {
+    pub email: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct PasswordResetConfirmRequest {
+    pub token: String,
+    pub password: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct EmailVerificationConfirmRequest {
+    pub token: String,
 }
@@ -1,41 +1,101 @@
 use axum::extract::State;
-use axum::response::IntoResponse;
-use axum::Json;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::{Extension, Json};
 use axum_extra::extract::{cookie::Cookie, CookieJar};
 use shaku::HasComponent;
 use std::sync::Arc;
 use tracing::{debug, info};
 
-use super::dto::{JwtReissueResponse, LoginRequest, LoginResponse};
-use crate::application::auth::usecases::LoginUseCase;
-use crate::application::auth::JwtUseCase;
+use super::dto::{
+    EmailVerificationConfirmRequest, JwtReissueResponse, LoginRequest, LoginResponse,
+    MfaChallengeResponse, MfaLoginRequest, PasswordResetConfirmRequest, PasswordResetRequest,
+};
+use crate::application::auth::usecases::{LoginCommandResult, LoginOutcome, LoginUseCase, MfaLoginCommand};
+use crate::application::auth::{EmailVerificationUseCase, JwtUseCase, LogoutUseCase, PasswordResetUseCase};
 use crate::common::error::error_code::ErrorCode;
 use crate::common::wrapper::ReturnValue;
-use crate::config::ConfigProvider;
+use crate::common::{AppError, ClientInfo, LoginMember};
+use crate::config::{AppConfig, ConfigProvider};
 use crate::di::AppContext;
 
+const REFRESH_TOKEN_COOKIE_NAME: &str = "refresh-token";
+
+pub(crate) fn create_refresh_token_cookie(cfg: &AppConfig, refresh_token: String) -> Cookie<'static> {
+    let mut refresh_token_cookie = Cookie::new(REFRESH_TOKEN_COOKIE_NAME, refresh_token);
+    refresh_token_cookie.set_path("/");
+    refresh_token_cookie.set_http_only(cfg.is_production());
+    refresh_token_cookie.set_secure(cfg.is_production());
+    refresh_token_cookie.set_max_age(time::Duration::milliseconds(
+        cfg.jwt_refresh_token_expire as i64,
+    ));
+    refresh_token_cookie
+}
+
+fn token_response(jar: CookieJar, cfg: &AppConfig, result: LoginCommandResult, message: &str) -> Response {
+    let jar = jar.add(create_refresh_token_cookie(cfg, result.refresh_token.clone()));
+    let payload = LoginResponse {
+        typ: "Bearer".to_string(),
+        access_token: result.access_token,
+        refresh_token: result.refresh_token,
+    };
+    (jar, ReturnValue::new(201, message.to_string(), payload)).into_response()
+}
+
+/// 로그인 결과로 응답을 만든다.
+/// 2단계 인증이 필요하면 토큰 대신 202 와 mfa_token 을 돌려주고, `/api/v1/auth/mfa` 로 이어서 인증하게 한다.
+pub(crate) fn login_response(jar: CookieJar, cfg: &AppConfig, outcome: LoginOutcome, message: &str) -> Response {
+    match outcome {
+        LoginOutcome::Authenticated(result) => token_response(jar, cfg, result, message),
+        LoginOutcome::MfaRequired(challenge) => (
+            jar,
+            ReturnValue::new(
+                202,
+                "2단계 인증이 필요합니다.".to_string(),
+                MfaChallengeResponse {
+                    mfa_token: challenge.mfa_token,
+                    expires_in: challenge.expires_in,
+                },
+            ),
+        )
+            .into_response(),
+    }
+}
+
+fn remove_refresh_token_cookie(jar: CookieJar) -> CookieJar {
+    let mut remove_cookie = Cookie::new(REFRESH_TOKEN_COOKIE_NAME, "");
+    remove_cookie.set_path("/");
+    jar.remove(remove_cookie)
+}
+
 pub async fn reissue_jwt(
     jar: CookieJar,
+    client: ClientInfo,
     State(ctx): State<Arc<AppContext>>,
 ) -> Result<impl IntoResponse, impl IntoResponse> {
     let jwt_usecase: &dyn JwtUseCase = ctx.resolve_ref();
+    let config_provider: &dyn ConfigProvider = ctx.resolve_ref();
 
-    let rt_cookie = match jar.get("refresh-token") {
+    let rt_cookie = match jar.get(REFRESH_TOKEN_COOKIE_NAME) {
         Some(cookie) => cookie,
         None => {
             tracing::error!("refresh token이 존재하지 않습니다.");
             return Err((jar, ErrorCode::InvalidJwtToken));
         }
     };
-    let mut remove_cookie = Cookie::new("refresh-token", "");
-    remove_cookie.set_path("/");
     let token_string = rt_cookie.value().to_string();
 
-    match jwt_usecase.refresh_jwt(token_string).await {
+    match jwt_usecase.refresh_jwt(token_string, client).await {
         Ok(jwt_result) => {
             debug!("jwt result : {:?}", jwt_result);
+            // refresh token 은 재발급마다 교체되므로 쿠키도 새 토큰으로 갱신한다.
+            let jar = jar.add(create_refresh_token_cookie(
+                config_provider.get().as_ref(),
+                jwt_result.refresh_token.clone(),
+            ));
             let payload = JwtReissueResponse {
                 access_token: jwt_result.access_token,
+                refresh_token: jwt_result.refresh_token,
             };
             Ok((
                 jar,
@@ -46,16 +106,20 @@ pub async fn reissue_jwt(
                 ),
             ))
         }
-        Err(_) => {
+        Err(err) => {
             tracing::error!("access token 재발급에 실패하였습니다. refresh token이 만료되었거나 변조되었습습니다.");
-            let jar = jar.remove(remove_cookie);
-            Err((jar, ErrorCode::InvalidJwtToken))
+            let jar = remove_refresh_token_cookie(jar);
+            match err {
+                ErrorCode::RefreshTokenReused => Err((jar, err)),
+                _ => Err((jar, ErrorCode::InvalidJwtToken)),
+            }
         }
     }
 }
 
 pub async fn login(
-    mut jar: CookieJar,
+    jar: CookieJar,
+    client: ClientInfo,
     State(ctx): State<Arc<AppContext>>,
     Json(req): Json<LoginRequest>,
 ) -> Result<impl axum::response::IntoResponse, ErrorCode> {
@@ -65,30 +129,111 @@ pub async fn login(
     let config_provider: &dyn ConfigProvider = ctx.resolve_ref();
     let cfg = config_provider.get();
 
-    let result = login_usecase
+    let outcome = login_usecase
         .login(crate::application::auth::usecases::LoginCommand {
             principal: req.email,
             credential: req.password,
+            client,
         })
         .await?;
 
-    let payload = LoginResponse {
-        typ: "Bearer".to_string(),
-        access_token: result.access_token,
-        refresh_token: result.refresh_token,
-    };
+    Ok(login_response(jar, cfg.as_ref(), outcome, "로그인에 성공하였습니다."))
+}
 
-    let mut refresh_token_cookie = Cookie::new("refresh-token", payload.refresh_token.clone());
-    refresh_token_cookie.set_path("/");
-    refresh_token_cookie.set_http_only(cfg.is_production());
-    refresh_token_cookie.set_secure(cfg.is_production());
-    refresh_token_cookie.set_max_age(time::Duration::milliseconds(
-        cfg.jwt_refresh_token_expire as i64,
-    ));
+pub async fn login_with_mfa(
+    jar: CookieJar,
+    client: ClientInfo,
+    State(ctx): State<Arc<AppContext>>,
+    Json(req): Json<MfaLoginRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let login_usecase: &dyn LoginUseCase = ctx.resolve_ref();
+    let config_provider: &dyn ConfigProvider = ctx.resolve_ref();
+
+    let result = login_usecase
+        .login_with_mfa(MfaLoginCommand {
+            mfa_token: req.mfa_token,
+            code: req.code,
+            client,
+        })
+        .await?;
+
+    Ok(token_response(jar, config_provider.get().as_ref(), result, "로그인에 성공하였습니다."))
+}
+
+pub async fn logout(
+    jar: CookieJar,
+    State(ctx): State<Arc<AppContext>>,
+) -> Result<impl IntoResponse, ErrorCode> {
+    let logout_usecase: &dyn LogoutUseCase = ctx.resolve_ref();
+
+    if let Some(cookie) = jar.get(REFRESH_TOKEN_COOKIE_NAME) {
+        logout_usecase.logout(cookie.value().to_string()).await?;
+    }
+
+    Ok((remove_refresh_token_cookie(jar), StatusCode::NO_CONTENT))
+}
 
-    jar = jar.add(refresh_token_cookie.clone());
-    Ok((
-        jar,
-        ReturnValue::new(201, "로그인에 성공하였습니다.".to_string(), payload),
+pub async fn logout_all(
+    jar: CookieJar,
+    State(ctx): State<Arc<AppContext>>,
+    Extension(login_member): Extension<LoginMember>,
+) -> Result<impl IntoResponse, ErrorCode> {
+    let logout_usecase: &dyn LogoutUseCase = ctx.resolve_ref();
+    logout_usecase.logout_all(login_member).await?;
+
+    Ok((remove_refresh_token_cookie(jar), StatusCode::NO_CONTENT))
+}
+
+pub async fn request_password_reset(
+    State(ctx): State<Arc<AppContext>>,
+    Json(req): Json<PasswordResetRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let password_reset_usecase: &dyn PasswordResetUseCase = ctx.resolve_ref();
+    password_reset_usecase.request_reset(req.email).await?;
+
+    Ok(ReturnValue::new(
+        202,
+        "가입된 이메일이라면 비밀번호 재설정 메일이 발송됩니다.".to_string(),
+        (),
+    ))
+}
+
+pub async fn confirm_password_reset(
+    State(ctx): State<Arc<AppContext>>,
+    Json(req): Json<PasswordResetConfirmRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let password_reset_usecase: &dyn PasswordResetUseCase = ctx.resolve_ref();
+    password_reset_usecase
+        .confirm_reset(req.token, req.password)
+        .await?;
+
+    Ok(ReturnValue::new(
+        200,
+        "비밀번호가 변경되었습니다. 다시 로그인해주세요.".to_string(),
+        (),
     ))
 }
+
+pub async fn request_email_verification(
+    State(ctx): State<Arc<AppContext>>,
+    Extension(login_member): Extension<LoginMember>,
+) -> Result<impl IntoResponse, AppError> {
+    let email_verification_usecase: &dyn EmailVerificationUseCase = ctx.resolve_ref();
+    email_verification_usecase
+        .request_verification(login_member.id)
+        .await?;
+
+    Ok(ReturnValue::new(202, "인증 메일을 보냈습니다.".to_string(), ()))
+}
+
+pub async fn confirm_email_verification(
+    State(ctx): State<Arc<AppContext>>,
+    Json(req): Json<EmailVerificationConfirmRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let email_verification_usecase: &dyn EmailVerificationUseCase = ctx.resolve_ref();
+    email_verification_usecase
+        .confirm_verification(req.token)
+        .await?;
+
+    Ok(ReturnValue::new(200, "이메일 인증이 완료되었습니다.".to_string(), ()))
+}
@@ -2,7 +2,10 @@ pub mod dto;
 pub mod handler;
 pub mod vo;
 
+use crate::common::middleware::rate_limit::{rate_limit, RateLimit};
+use crate::common::middleware::security::with_role_member;
 use crate::di::AppContext;
+use axum::middleware::{from_fn, from_fn_with_state};
 use axum::routing::post;
 use axum::Router;
 use handler::login;
@@ -10,7 +13,33 @@ use std::sync::Arc;
 
 pub fn router(ctx: Arc<AppContext>) -> Router {
     Router::new()
-        .route("/", post(login))
+        .route(
+            "/",
+            post(login).route_layer(from_fn_with_state(RateLimit::policy(ctx.clone(), "login"), rate_limit)),
+        )
+        .route(
+            "/mfa",
+            post(handler::login_with_mfa)
+                .route_layer(from_fn_with_state(RateLimit::policy(ctx.clone(), "login"), rate_limit)),
+        )
         .route("/jwt/reissue", post(handler::reissue_jwt))
+        .route("/logout", post(handler::logout))
+        .route(
+            "/logout/all",
+            post(handler::logout_all).route_layer(from_fn(with_role_member)),
+        )
+        .route(
+            "/password-reset",
+            post(handler::request_password_reset)
+                .route_layer(from_fn_with_state(RateLimit::policy(ctx.clone(), "mail"), rate_limit)),
+        )
+        .route("/password-reset/confirm", post(handler::confirm_password_reset))
+        .route(
+            "/email-verification",
+            post(handler::request_email_verification)
+                .route_layer(from_fn(with_role_member))
+                .route_layer(from_fn_with_state(RateLimit::policy(ctx.clone(), "mail"), rate_limit)),
+        )
+        .route("/email-verification/confirm", post(handler::confirm_email_verification))
         .with_state(ctx.clone())
 }
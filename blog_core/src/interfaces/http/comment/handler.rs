@@ -0,0 +1,189 @@
+use std::sync::Arc;
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::{Extension, Json};
+use chrono::NaiveDateTime;
+use uuid::Uuid;
+use crate::application::board::{CommentCreateUsecase, CommentDeleteUsecase, CommentDto, CommentModifyUsecase, CommentQueryUsecase, CreateCommentCommand, ModifyCommentCommand, QCommentDto, ReactionCountVo, ReactionToggleUsecase, WriterVo};
+use crate::common::{AppError, CursorList, CursorListBuilder, LoginMember, ReturnValue, SecurityContext};
+use crate::config::ConfigProvider;
+use crate::di::AppContext;
+use crate::interfaces::http::post::parse_reaction_kind;
+use shaku::HasComponent;
+
+pub async fn create_comment(
+    State(ctx): State<Arc<AppContext>>,
+    Extension(login_member): Extension<LoginMember>,
+    Path(post_id): Path<Uuid>,
+    Json(request): Json<CreateCommentRequest>,
+) -> Result<ReturnValue<CommentCommandResponse>, AppError> {
+    let usecase: &dyn CommentCreateUsecase = ctx.resolve_ref();
+    let comment = usecase.create(login_member, post_id, request.into()).await?;
+
+    Ok(ReturnValue {
+        status: 201,
+        data: CommentCommandResponse::from(comment),
+        message: "댓글이 작성되었습니다.".to_owned()
+    })
+}
+
+pub async fn update_comment(
+    State(ctx): State<Arc<AppContext>>,
+    Extension(login_member): Extension<LoginMember>,
+    Path((post_id, comment_id)): Path<(Uuid, i64)>,
+    Json(request): Json<ModifyCommentRequest>,
+) -> Result<ReturnValue<CommentCommandResponse>, AppError> {
+    let usecase: &dyn CommentModifyUsecase = ctx.resolve_ref();
+    let comment = usecase.update(login_member, post_id, comment_id, request.into()).await?;
+
+    Ok(ReturnValue {
+        status: 200,
+        data: CommentCommandResponse::from(comment),
+        message: "댓글이 수정되었습니다.".to_owned()
+    })
+}
+
+pub async fn delete_comment(
+    State(ctx): State<Arc<AppContext>>,
+    Extension(login_member): Extension<LoginMember>,
+    Path((post_id, comment_id)): Path<(Uuid, i64)>,
+) -> Result<Response, AppError> {
+    let usecase: &dyn CommentDeleteUsecase = ctx.resolve_ref();
+    usecase.delete(login_member, post_id, comment_id).await?;
+    Ok((StatusCode::NO_CONTENT, ()).into_response())
+}
+
+pub async fn get_comments(
+    State(ctx): State<Arc<AppContext>>,
+    Extension(security_context): Extension<SecurityContext>,
+    Path(post_id): Path<Uuid>,
+    Query(params): Query<CommentQueryParams>,
+) -> Result<ReturnValue<CursorList<CommentQueryResponse>>, AppError> {
+    let size = params.size.unwrap_or(20);
+    let usecase: &dyn CommentQueryUsecase = ctx.resolve_ref();
+    let config_provider: &dyn ConfigProvider = ctx.resolve_ref();
+    let comments = usecase.get_comments(security_context.get_member().cloned(), post_id, params.id, size as u64).await?;
+
+    let comments = comments.into_iter()
+        .map(CommentQueryResponse::from)
+        .collect::<Vec<CommentQueryResponse>>();
+
+    let cursor_list_builder = CursorListBuilder::new(comments, size)
+        .set_target("id".to_owned());
+
+    Ok(ReturnValue {
+        status: 200,
+        data: cursor_list_builder.build(config_provider.get_uri(&format!("/api/v1/posts/{}/comments", post_id))),
+        message: "댓글 목록을 가져왔습니다.".to_owned()
+    })
+}
+
+/// 같은 반응을 다시 누르면 취소한다.
+pub async fn toggle_comment_reaction(
+    State(ctx): State<Arc<AppContext>>,
+    Extension(login_member): Extension<LoginMember>,
+    Path((post_id, comment_id, kind)): Path<(Uuid, i64, String)>,
+) -> Result<ReturnValue<Vec<ReactionCountVo>>, AppError> {
+    let kind = parse_reaction_kind(&kind)?;
+    let reaction_usecase: &dyn ReactionToggleUsecase = ctx.resolve_ref();
+    let reactions = reaction_usecase.toggle_comment_reaction(login_member, post_id, comment_id, kind).await?;
+
+    Ok(ReturnValue {
+        status: 200,
+        data: reactions,
+        message: "반응을 반영했습니다.".to_owned()
+    })
+}
+
+#[derive(serde::Deserialize)]
+pub struct CreateCommentRequest {
+    parent_id: Option<i64>,
+    contents: String,
+}
+
+impl From<CreateCommentRequest> for CreateCommentCommand {
+    fn from(request: CreateCommentRequest) -> Self {
+        CreateCommentCommand {
+            parent_id: request.parent_id,
+            contents: request.contents,
+        }
+    }
+}
+
+#[derive(serde::Deserialize)]
+pub struct ModifyCommentRequest {
+    contents: String,
+}
+
+impl From<ModifyCommentRequest> for ModifyCommentCommand {
+    fn from(request: ModifyCommentRequest) -> Self {
+        ModifyCommentCommand {
+            contents: request.contents,
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+pub struct CommentCommandResponse {
+    id: i64,
+    post_id: Uuid,
+    parent_id: Option<i64>,
+    writer_id: Uuid,
+    contents: String,
+    created_at: NaiveDateTime,
+    updated_at: Option<NaiveDateTime>
+}
+
+impl From<CommentDto> for CommentCommandResponse {
+    fn from(comment: CommentDto) -> Self {
+        CommentCommandResponse {
+            id: comment.id,
+            post_id: comment.post_id,
+            parent_id: comment.parent_id,
+            writer_id: comment.writer_id,
+            contents: comment.contents,
+            created_at: comment.created_at,
+            updated_at: comment.updated_at
+        }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct CommentQueryResponse {
+    id: i64,
+    parent_id: Option<i64>,
+    writer: WriterVo,
+    contents: Option<String>,
+    is_deleted: bool,
+    created_at: NaiveDateTime,
+    updated_at: Option<NaiveDateTime>,
+    reactions: Vec<ReactionCountVo>,
+    children: Vec<CommentQueryResponse>,
+}
+
+impl From<QCommentDto> for CommentQueryResponse {
+    fn from(comment: QCommentDto) -> Self {
+        CommentQueryResponse {
+            id: comment.id,
+            parent_id: comment.parent_id,
+            writer: comment.writer,
+            contents: comment.contents,
+            is_deleted: comment.is_deleted,
+            created_at: comment.created_at,
+            updated_at: comment.updated_at,
+            reactions: comment.reactions,
+            children: comment.children
+                .into_iter()
+                .map(CommentQueryResponse::from)
+                .collect(),
+        }
+    }
+}
+
+#[derive(serde::Deserialize)]
+pub struct CommentQueryParams {
+    id: Option<i64>,
+    size: Option<usize>,
+}
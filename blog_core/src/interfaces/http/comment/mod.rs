@@ -0,0 +1,28 @@
+pub mod handler;
+use axum::middleware::from_fn_with_state;
+use axum::Router;
+pub use handler::*;
+
+use axum::routing::{post, put, get, delete};
+use std::sync::Arc;
+use crate::common::{require_permission, Permission};
+use crate::di::AppContext;
+
+pub fn router(ctx: Arc<AppContext>) -> axum::Router {
+
+    let comment_command_router = axum::Router::new()
+        .route("/", post(create_comment))
+        .route("/{comment_id}", put(update_comment))
+        .route("/{comment_id}", delete(delete_comment))
+        .route("/{comment_id}/reactions/{kind}", post(toggle_comment_reaction))
+        .layer(from_fn_with_state(Permission::CommentWrite, require_permission))
+        .with_state(ctx.clone());
+
+    let comment_query_router = axum::Router::new()
+        .route("/", get(get_comments))
+        .with_state(ctx.clone());
+
+    Router::new()
+        .merge(comment_command_router)
+        .merge(comment_query_router)
+}
@@ -0,0 +1,372 @@
+use std::sync::Arc;
+
+use atom_syndication::{CategoryBuilder, ContentBuilder, EntryBuilder, FeedBuilder, LinkBuilder, PersonBuilder, Text};
+use axum::extract::{Path, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
+use rss::extension::atom::AtomExtensionBuilder;
+use rss::{CategoryBuilder as RssCategoryBuilder, ChannelBuilder, GuidBuilder, ItemBuilder};
+use sha2::{Digest, Sha256};
+use shaku::HasComponent;
+
+use crate::application::board::{FeedDto, FeedQueryUsecase};
+use crate::common::AppError;
+use crate::config::ConfigProvider;
+use crate::di::AppContext;
+
+/// HTTP-date (RFC 7231) 형식
+const HTTP_DATE_FORMAT: &str = "%a, %d %b %Y %H:%M:%S GMT";
+
+/// URL 경로 한 조각에 그대로 둘 수 없는 문자 (RFC 3986 path segment)
+const PATH_SEGMENT: &AsciiSet = &CONTROLS
+    .add(b' ').add(b'"').add(b'#').add(b'%').add(b'/').add(b'<').add(b'>')
+    .add(b'?').add(b'[').add(b'\\').add(b']').add(b'^').add(b'`').add(b'{').add(b'|').add(b'}');
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedFormat {
+    Rss,
+    Atom,
+}
+
+impl FeedFormat {
+    fn file_name(&self) -> &'static str {
+        match self {
+            FeedFormat::Rss => "feed.xml",
+            FeedFormat::Atom => "atom.xml",
+        }
+    }
+
+    fn content_type(&self) -> &'static str {
+        match self {
+            FeedFormat::Rss => "application/rss+xml; charset=utf-8",
+            FeedFormat::Atom => "application/atom+xml; charset=utf-8",
+        }
+    }
+}
+
+/// 피드가 가리키는 페이지 경로. 사이트 전체면 "", 게시판이면 "/categories/{id}" 처럼 쓴다.
+/// 태그는 퍼센트 인코딩해서 경로에 넣는다.
+struct FeedScope {
+    category_id: Option<i64>,
+    tag: Option<String>,
+    page_path: String,
+}
+
+impl FeedScope {
+    fn site() -> Self {
+        FeedScope { category_id: None, tag: None, page_path: String::new() }
+    }
+
+    fn category(category_id: i64) -> Self {
+        FeedScope { category_id: Some(category_id), tag: None, page_path: format!("/categories/{}", category_id) }
+    }
+
+    fn tag(tag: String) -> Self {
+        let page_path = format!("/tags/{}", utf8_percent_encode(&tag, PATH_SEGMENT));
+        FeedScope { page_path, category_id: None, tag: Some(tag) }
+    }
+}
+
+pub async fn get_site_rss(State(ctx): State<Arc<AppContext>>, headers: HeaderMap) -> Result<Response, AppError> {
+    feed_response(ctx, headers, FeedFormat::Rss, FeedScope::site()).await
+}
+
+pub async fn get_site_atom(State(ctx): State<Arc<AppContext>>, headers: HeaderMap) -> Result<Response, AppError> {
+    feed_response(ctx, headers, FeedFormat::Atom, FeedScope::site()).await
+}
+
+pub async fn get_category_rss(
+    State(ctx): State<Arc<AppContext>>,
+    Path(id): Path<i64>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    feed_response(ctx, headers, FeedFormat::Rss, FeedScope::category(id)).await
+}
+
+pub async fn get_category_atom(
+    State(ctx): State<Arc<AppContext>>,
+    Path(id): Path<i64>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    feed_response(ctx, headers, FeedFormat::Atom, FeedScope::category(id)).await
+}
+
+pub async fn get_tag_rss(
+    State(ctx): State<Arc<AppContext>>,
+    Path(tag): Path<String>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    feed_response(ctx, headers, FeedFormat::Rss, FeedScope::tag(tag)).await
+}
+
+pub async fn get_tag_atom(
+    State(ctx): State<Arc<AppContext>>,
+    Path(tag): Path<String>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    feed_response(ctx, headers, FeedFormat::Atom, FeedScope::tag(tag)).await
+}
+
+/// 피드를 만들어 내려준다. 피드 리더가 보낸 If-None-Match, If-Modified-Since 로 바뀐 것이 없으면 304 를 준다.
+async fn feed_response(
+    ctx: Arc<AppContext>,
+    headers: HeaderMap,
+    format: FeedFormat,
+    scope: FeedScope,
+) -> Result<Response, AppError> {
+    let usecase: &dyn FeedQueryUsecase = ctx.resolve_ref();
+    let config_provider: &dyn ConfigProvider = ctx.resolve_ref();
+
+    let feed = usecase.get_feed(scope.category_id, scope.tag.clone()).await?;
+    let body = render_feed(config_provider, &feed, format, &scope.page_path);
+    let etag = etag(&body);
+    let last_modified = feed.last_modified();
+
+    let mut response_headers = HeaderMap::new();
+    response_headers.insert(header::ETAG, etag.parse().unwrap());
+    if let Some(last_modified) = last_modified {
+        response_headers.insert(header::LAST_MODIFIED, http_date(last_modified).parse().unwrap());
+    }
+    response_headers.insert(header::CACHE_CONTROL, "public, max-age=300".parse().unwrap());
+
+    if is_not_modified(&headers, &etag, last_modified) {
+        return Ok((StatusCode::NOT_MODIFIED, response_headers).into_response());
+    }
+
+    response_headers.insert(header::CONTENT_TYPE, format.content_type().parse().unwrap());
+    Ok((StatusCode::OK, response_headers, body).into_response())
+}
+
+fn render_feed(config_provider: &dyn ConfigProvider, feed: &FeedDto, format: FeedFormat, page_path: &str) -> String {
+    let config = config_provider.get();
+    let title = match &feed.scope_name {
+        Some(scope_name) => format!("{} - {}", config.feed_title, scope_name),
+        None => config.feed_title.clone(),
+    };
+    let page_link = config_provider.get_uri(if page_path.is_empty() { "/" } else { page_path });
+    let self_link = config_provider.get_uri(&format!("{}/{}", page_path, format.file_name()));
+    let post_link = |id| config_provider.get_uri(&format!("/posts/{}", id));
+
+    match format {
+        FeedFormat::Rss => {
+            let items: Vec<rss::Item> = feed.items.iter()
+                .map(|item| {
+                    let link = post_link(item.id);
+                    let categories = std::iter::once(&item.category.name)
+                        .chain(item.tags.iter())
+                        .map(|name| RssCategoryBuilder::default().name(name.as_str()).build())
+                        .collect::<Vec<_>>();
+                    ItemBuilder::default()
+                        .title(Some(item.title.clone()))
+                        .link(Some(link.clone()))
+                        .guid(Some(GuidBuilder::default().value(link).permalink(true).build()))
+                        .pub_date(Some(utc(item.published_at).to_rfc2822()))
+                        .categories(categories)
+                        .description(Some(item.summary.clone()))
+                        .content(item.content.clone())
+                        .build()
+                })
+                .collect();
+
+            ChannelBuilder::default()
+                .title(title)
+                .link(page_link)
+                .description(config.feed_description.clone())
+                .language(Some("ko".to_owned()))
+                .last_build_date(feed.last_modified().map(|last_modified| utc(last_modified).to_rfc2822()))
+                .atom_ext(Some(AtomExtensionBuilder::default()
+                    .link(LinkBuilder::default()
+                        .href(self_link)
+                        .rel("self")
+                        .mime_type(Some("application/rss+xml".to_owned()))
+                        .build())
+                    .build()))
+                .items(items)
+                .build()
+                .to_string()
+        }
+        FeedFormat::Atom => {
+            let entries: Vec<atom_syndication::Entry> = feed.items.iter()
+                .map(|item| {
+                    let link = post_link(item.id);
+                    let categories = std::iter::once(&item.category.name)
+                        .chain(item.tags.iter())
+                        .map(|name| CategoryBuilder::default().term(name.as_str()).build())
+                        .collect::<Vec<_>>();
+                    EntryBuilder::default()
+                        .id(link.clone())
+                        .title(Text::plain(item.title.clone()))
+                        .updated(utc(item.updated_at).fixed_offset())
+                        .published(Some(utc(item.published_at).fixed_offset()))
+                        .author(PersonBuilder::default().name(item.writer.name.clone()).build())
+                        .link(LinkBuilder::default().href(link).rel("alternate").build())
+                        .categories(categories)
+                        .summary(Some(Text::plain(item.summary.clone())))
+                        // content 는 MarkdownRenderer 로 정리한 HTML 이다.
+                        .content(item.content.clone().map(|content| {
+                            ContentBuilder::default()
+                                .value(Some(content))
+                                .content_type(Some("html".to_owned()))
+                                .build()
+                        }))
+                        .build()
+                })
+                .collect();
+
+            // 글이 없는 피드는 시각이 바뀌지 않도록 epoch 를 쓴다.
+            let updated = feed.last_modified().map_or(DateTime::UNIX_EPOCH, utc);
+            FeedBuilder::default()
+                .id(self_link.clone())
+                .title(Text::plain(title))
+                .subtitle(Some(Text::plain(config.feed_description.clone())))
+                .updated(updated.fixed_offset())
+                .link(LinkBuilder::default().href(self_link).rel("self").build())
+                .link(LinkBuilder::default().href(page_link).rel("alternate").build())
+                .lang(Some("ko".to_owned()))
+                .entries(entries)
+                .build()
+                .to_string()
+        }
+    }
+}
+
+fn utc(datetime: NaiveDateTime) -> DateTime<Utc> {
+    Utc.from_utc_datetime(&datetime)
+}
+
+fn http_date(datetime: NaiveDateTime) -> String {
+    utc(datetime).format(HTTP_DATE_FORMAT).to_string()
+}
+
+/// 본문 SHA-256 해시 앞 32자리로 만든 strong ETag
+fn etag(body: &str) -> String {
+    let digest = format!("{:x}", Sha256::digest(body.as_bytes()));
+    format!("\"{}\"", &digest[..32])
+}
+
+/// If-None-Match 가 있으면 ETag 로만 판단하고, 없으면 If-Modified-Since 와 마지막 수정 시각을 비교한다.
+fn is_not_modified(headers: &HeaderMap, etag: &str, last_modified: Option<NaiveDateTime>) -> bool {
+    if let Some(if_none_match) = headers.get(header::IF_NONE_MATCH).and_then(|value| value.to_str().ok()) {
+        return if_none_match
+            .split(',')
+            .map(str::trim)
+            .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag);
+    }
+
+    let if_modified_since = headers.get(header::IF_MODIFIED_SINCE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| DateTime::parse_from_rfc2822(value).ok());
+    match (if_modified_since, last_modified) {
+        // HTTP-date 는 초 단위까지만 담는다.
+        (Some(since), Some(last_modified)) => utc(last_modified).timestamp() <= since.timestamp(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use axum::http::{header, HeaderMap};
+    use chrono::NaiveDateTime;
+    use rss::validation::Validate;
+    use uuid::Uuid;
+
+    use crate::application::board::{CategoryVo, FeedDto, FeedItemDto, WriterVo};
+    use crate::config::{test_config, ConfigProvider};
+
+    use super::{etag, http_date, is_not_modified, render_feed, FeedFormat, FeedScope};
+
+    fn feed() -> FeedDto {
+        let published_at = NaiveDateTime::parse_from_str("2024-03-01 09:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        FeedDto {
+            scope_name: Some("rust".to_owned()),
+            items: vec![FeedItemDto {
+                id: Uuid::new_v4(),
+                title: "러스트 & 피드".to_owned(),
+                writer: WriterVo { id: Uuid::new_v4(), name: "dohoon".to_owned() },
+                category: CategoryVo { id: 1, name: "개발".to_owned() },
+                tags: vec!["rust".to_owned()],
+                summary: "요약 <b>".to_owned(),
+                content: Some("<p>본문 전체</p>".to_owned()),
+                published_at,
+                updated_at: published_at + chrono::Duration::hours(1),
+            }],
+        }
+    }
+
+    fn config_provider() -> Arc<dyn ConfigProvider> {
+        test_config(&["--server-host", "blog.example.com", "--protocol", "https"])
+    }
+
+    #[test]
+    fn render_rss_test() {
+        let feed = feed();
+        let body = render_feed(config_provider().as_ref(), &feed, FeedFormat::Rss, "/tags/rust");
+
+        let channel = rss::Channel::read_from(body.as_bytes()).unwrap();
+        channel.validate().unwrap();
+        assert_eq!(channel.title(), "dohoon-kim.kr - rust");
+        assert_eq!(channel.link(), "https://blog.example.com/tags/rust");
+        assert_eq!(channel.atom_ext().unwrap().links()[0].href(), "https://blog.example.com/tags/rust/feed.xml");
+
+        let item = &channel.items()[0];
+        let link = format!("https://blog.example.com/posts/{}", feed.items[0].id);
+        assert_eq!(item.title(), Some("러스트 & 피드"));
+        assert_eq!(item.link(), Some(link.as_str()));
+        assert_eq!(item.description(), Some("요약 <b>"));
+        assert_eq!(item.content(), Some("<p>본문 전체</p>"));
+        assert_eq!(item.categories().len(), 2);
+    }
+
+    #[test]
+    fn render_atom_test() {
+        let feed = feed();
+        let body = render_feed(config_provider().as_ref(), &feed, FeedFormat::Atom, "");
+
+        let atom = atom_syndication::Feed::read_from(body.as_bytes()).unwrap();
+        assert_eq!(atom.id(), "https://blog.example.com/atom.xml");
+        assert_eq!(atom.updated().naive_utc(), feed.items[0].updated_at);
+
+        let entry = &atom.entries()[0];
+        let link = format!("https://blog.example.com/posts/{}", feed.items[0].id);
+        assert_eq!(entry.id(), link);
+        assert_eq!(entry.links()[0].href(), link);
+        assert_eq!(entry.authors()[0].name(), "dohoon");
+        assert_eq!(entry.summary().unwrap().as_str(), "요약 <b>");
+        assert_eq!(entry.content().unwrap().value(), Some("<p>본문 전체</p>"));
+        assert_eq!(entry.content().unwrap().content_type(), Some("html"));
+    }
+
+    #[test]
+    fn tag_scope_test() {
+        let scope = FeedScope::tag("c# & ?x/y 러스트".to_owned());
+        assert_eq!(scope.page_path, "/tags/c%23%20&%20%3Fx%2Fy%20%EB%9F%AC%EC%8A%A4%ED%8A%B8");
+        assert_eq!(scope.tag.as_deref(), Some("c# & ?x/y 러스트"));
+
+        let body = render_feed(config_provider().as_ref(), &feed(), FeedFormat::Atom, &scope.page_path);
+        let atom = atom_syndication::Feed::read_from(body.as_bytes()).unwrap();
+        assert_eq!(atom.id(), "https://blog.example.com/tags/c%23%20&%20%3Fx%2Fy%20%EB%9F%AC%EC%8A%A4%ED%8A%B8/atom.xml");
+    }
+
+    #[test]
+    fn not_modified_test() {
+        let last_modified = NaiveDateTime::parse_from_str("2024-03-01 10:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let etag = etag("<rss/>");
+
+        let mut headers = HeaderMap::new();
+        assert!(!is_not_modified(&headers, &etag, Some(last_modified)));
+
+        headers.insert(header::IF_MODIFIED_SINCE, http_date(last_modified).parse().unwrap());
+        assert!(is_not_modified(&headers, &etag, Some(last_modified)));
+        assert!(!is_not_modified(&headers, &etag, Some(last_modified + chrono::Duration::seconds(1))));
+
+        // If-None-Match 가 있으면 If-Modified-Since 보다 먼저 본다.
+        headers.insert(header::IF_NONE_MATCH, "\"other\"".parse().unwrap());
+        assert!(!is_not_modified(&headers, &etag, Some(last_modified)));
+        headers.insert(header::IF_NONE_MATCH, format!("\"other\", W/{}", etag).parse().unwrap());
+        assert!(is_not_modified(&headers, &etag, Some(last_modified)));
+    }
+}
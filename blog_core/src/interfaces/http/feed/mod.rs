@@ -0,0 +1,18 @@
+pub mod handler;
+
+use crate::di::AppContext;
+use axum::routing::get;
+use axum::Router;
+use handler::*;
+use std::sync::Arc;
+
+pub fn router(ctx: Arc<AppContext>) -> Router {
+    Router::new()
+        .route("/feed.xml", get(get_site_rss))
+        .route("/atom.xml", get(get_site_atom))
+        .route("/categories/{id}/feed.xml", get(get_category_rss))
+        .route("/categories/{id}/atom.xml", get(get_category_atom))
+        .route("/tags/{tag}/feed.xml", get(get_tag_rss))
+        .route("/tags/{tag}/atom.xml", get(get_tag_atom))
+        .with_state(ctx.clone())
+}
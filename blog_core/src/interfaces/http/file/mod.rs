@@ -1,13 +1,17 @@
 use std::sync::Arc;
-use axum::{middleware::from_fn, routing::put, Router};
+use axum::{middleware::from_fn_with_state, routing::put, Router};
 use handler::upload_image;
-use crate::{common::with_role_admin, di::AppContext};
+use crate::common::middleware::rate_limit::{rate_limit, RateLimit};
+use crate::{common::{require_permission, Permission}, di::AppContext};
 pub mod handler;
 
 pub fn router(ctx: Arc<AppContext>) -> Router {
 
     Router::new()
-        .route("/images", put(upload_image))
-        .route_layer(from_fn(with_role_admin))
+        .route(
+            "/images",
+            put(upload_image).route_layer(from_fn_with_state(RateLimit::policy(ctx.clone(), "upload"), rate_limit)),
+        )
+        .route_layer(from_fn_with_state(Permission::PostWrite, require_permission))
         .with_state(ctx.clone())
 }
@@ -1,6 +1,11 @@
+use crate::application::auth::usecases::{
+    AccessTokenCreateCommand, AccessTokenDto, IdentityDto, SessionDto, TotpEnrollmentDto,
+};
+use crate::application::auth::{EmailVerificationUseCase, MfaUseCase, OAuth2IdentityUseCase, PersonalAccessTokenUseCase, SessionQueryUseCase, SessionRevokeUseCase};
 use crate::application::member::*;
 use crate::common::error::error_code::ErrorCode;
 use crate::common::middleware::security::LoginMember;
+use crate::common::{AppError, ClientInfo};
 use crate::{common::wrapper::ReturnValue, di::AppContext};
 use axum::extract::{Extension, Json, Path, State};
 use chrono::NaiveDateTime;
@@ -75,6 +80,12 @@ pub async fn create_new_member(
     let cmd = payload.into();
     let member_dto = member_service.create(cmd).await?;
 
+    // 인증 메일은 응답과 별도로 보낸다. 실패해도 가입은 유지하고, 회원이 인증 메일을 다시 요청할 수 있게 한다.
+    let email_verification_usecase: &dyn EmailVerificationUseCase = ctx.resolve_ref();
+    if let Err(e) = email_verification_usecase.request_verification(member_dto.id).await {
+        tracing::error!("Failed to send verification mail: {}", e.message);
+    }
+
     Ok(ReturnValue {
         status: 201,
         message: "회원 가입이 완료되었습니다.".to_string(),
@@ -112,3 +123,270 @@ pub async fn delete_member(
         .body(axum::body::Body::empty())
         .unwrap())
 }
+
+#[derive(Serialize, Deserialize)]
+pub struct SessionResponse {
+    id: uuid::Uuid,
+    user_agent: Option<String>,
+    ip_address: Option<String>,
+    created_at: NaiveDateTime,
+    last_used_at: NaiveDateTime,
+    is_current: bool,
+}
+
+impl From<SessionDto> for SessionResponse {
+    fn from(session: SessionDto) -> Self {
+        Self {
+            id: session.id,
+            user_agent: session.user_agent,
+            ip_address: session.ip_address,
+            created_at: session.created_at,
+            last_used_at: session.last_used_at,
+            is_current: session.is_current,
+        }
+    }
+}
+
+pub async fn get_member_sessions(
+    State(ctx): State<Arc<AppContext>>,
+    Extension(login_member): Extension<LoginMember>,
+    Path(id): Path<uuid::Uuid>,
+) -> Result<ReturnValue<Vec<SessionResponse>>, ErrorCode> {
+    let session_query_usecase: &dyn SessionQueryUseCase = ctx.resolve_ref();
+    let sessions = session_query_usecase.get_sessions(login_member, id).await?;
+
+    Ok(ReturnValue {
+        status: 200,
+        message: "로그인 세션 목록을 가져왔습니다.".to_string(),
+        data: sessions.into_iter().map(SessionResponse::from).collect(),
+    })
+}
+
+pub async fn revoke_member_session(
+    State(ctx): State<Arc<AppContext>>,
+    Extension(login_member): Extension<LoginMember>,
+    Path((id, session_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+) -> Result<axum::response::Response, ErrorCode> {
+    let session_revoke_usecase: &dyn SessionRevokeUseCase = ctx.resolve_ref();
+    session_revoke_usecase.revoke(login_member, id, session_id).await?;
+    Ok(axum::response::Response::builder()
+        .status(204)
+        .body(axum::body::Body::empty())
+        .unwrap())
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct IdentityResponse {
+    provider: String,
+    user_id: String,
+    email: Option<String>,
+}
+
+impl From<IdentityDto> for IdentityResponse {
+    fn from(identity: IdentityDto) -> Self {
+        Self {
+            provider: identity.provider,
+            user_id: identity.user_id,
+            email: identity.email,
+        }
+    }
+}
+
+pub async fn get_member_identities(
+    State(ctx): State<Arc<AppContext>>,
+    Extension(login_member): Extension<LoginMember>,
+    Path(id): Path<uuid::Uuid>,
+) -> Result<ReturnValue<Vec<IdentityResponse>>, AppError> {
+    let identity_usecase: &dyn OAuth2IdentityUseCase = ctx.resolve_ref();
+    let identities = identity_usecase.get_identities(login_member, id).await?;
+
+    Ok(ReturnValue {
+        status: 200,
+        message: "연결된 계정 목록을 가져왔습니다.".to_string(),
+        data: identities.into_iter().map(IdentityResponse::from).collect(),
+    })
+}
+
+pub async fn unlink_member_identity(
+    State(ctx): State<Arc<AppContext>>,
+    Extension(login_member): Extension<LoginMember>,
+    Path((id, provider)): Path<(uuid::Uuid, String)>,
+) -> Result<axum::response::Response, AppError> {
+    let identity_usecase: &dyn OAuth2IdentityUseCase = ctx.resolve_ref();
+    identity_usecase.unlink(login_member, id, provider).await?;
+    Ok(axum::response::Response::builder()
+        .status(204)
+        .body(axum::body::Body::empty())
+        .unwrap())
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct TotpEnrollmentResponse {
+    secret: String,
+    otpauth_uri: String,
+}
+
+impl From<TotpEnrollmentDto> for TotpEnrollmentResponse {
+    fn from(enrollment: TotpEnrollmentDto) -> Self {
+        Self {
+            secret: enrollment.secret,
+            otpauth_uri: enrollment.otpauth_uri,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct MfaCodeRequest {
+    /// TOTP 코드 또는 복구 코드
+    code: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RecoveryCodesResponse {
+    recovery_codes: Vec<String>,
+}
+
+pub async fn begin_totp_enrollment(
+    State(ctx): State<Arc<AppContext>>,
+    Extension(login_member): Extension<LoginMember>,
+    Path(id): Path<uuid::Uuid>,
+) -> Result<ReturnValue<TotpEnrollmentResponse>, AppError> {
+    let mfa_usecase: &dyn MfaUseCase = ctx.resolve_ref();
+    let enrollment = mfa_usecase.begin_totp_enrollment(login_member, id).await?;
+
+    Ok(ReturnValue {
+        status: 201,
+        message: "인증 앱에 등록한 뒤 코드를 입력해주세요.".to_string(),
+        data: TotpEnrollmentResponse::from(enrollment),
+    })
+}
+
+pub async fn confirm_totp_enrollment(
+    State(ctx): State<Arc<AppContext>>,
+    Extension(login_member): Extension<LoginMember>,
+    client: ClientInfo,
+    Path(id): Path<uuid::Uuid>,
+    Json(req): Json<MfaCodeRequest>,
+) -> Result<ReturnValue<RecoveryCodesResponse>, AppError> {
+    let mfa_usecase: &dyn MfaUseCase = ctx.resolve_ref();
+    let recovery_codes = mfa_usecase
+        .confirm_totp_enrollment(login_member, id, req.code, client)
+        .await?;
+
+    Ok(ReturnValue {
+        status: 200,
+        message: "2단계 인증이 설정되었습니다. 복구 코드는 다시 볼 수 없으니 안전한 곳에 보관해주세요.".to_string(),
+        data: RecoveryCodesResponse { recovery_codes },
+    })
+}
+
+pub async fn disable_totp(
+    State(ctx): State<Arc<AppContext>>,
+    Extension(login_member): Extension<LoginMember>,
+    client: ClientInfo,
+    Path(id): Path<uuid::Uuid>,
+    Json(req): Json<MfaCodeRequest>,
+) -> Result<axum::response::Response, AppError> {
+    let mfa_usecase: &dyn MfaUseCase = ctx.resolve_ref();
+    mfa_usecase.disable_totp(login_member, id, req.code, client).await?;
+    Ok(axum::response::Response::builder()
+        .status(204)
+        .body(axum::body::Body::empty())
+        .unwrap())
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct AccessTokenCreateRequest {
+    name: String,
+    scopes: Vec<String>,
+    expires_in_days: Option<i64>,
+}
+
+impl From<AccessTokenCreateRequest> for AccessTokenCreateCommand {
+    fn from(req: AccessTokenCreateRequest) -> Self {
+        Self {
+            name: req.name,
+            scopes: req.scopes,
+            expires_in_days: req.expires_in_days,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct AccessTokenResponse {
+    id: uuid::Uuid,
+    name: String,
+    token_prefix: String,
+    scopes: Vec<String>,
+    expires_at: NaiveDateTime,
+    last_used_at: Option<NaiveDateTime>,
+    created_at: NaiveDateTime,
+}
+
+impl From<AccessTokenDto> for AccessTokenResponse {
+    fn from(access_token: AccessTokenDto) -> Self {
+        Self {
+            id: access_token.id,
+            name: access_token.name,
+            token_prefix: access_token.token_prefix,
+            scopes: access_token.scopes,
+            expires_at: access_token.expires_at,
+            last_used_at: access_token.last_used_at,
+            created_at: access_token.created_at,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct IssuedAccessTokenResponse {
+    token: String,
+    #[serde(flatten)]
+    access_token: AccessTokenResponse,
+}
+
+pub async fn create_member_access_token(
+    State(ctx): State<Arc<AppContext>>,
+    Extension(login_member): Extension<LoginMember>,
+    Path(id): Path<uuid::Uuid>,
+    Json(req): Json<AccessTokenCreateRequest>,
+) -> Result<ReturnValue<IssuedAccessTokenResponse>, AppError> {
+    let access_token_usecase: &dyn PersonalAccessTokenUseCase = ctx.resolve_ref();
+    let issued = access_token_usecase.create(login_member, id, req.into()).await?;
+
+    Ok(ReturnValue {
+        status: 201,
+        message: "액세스 토큰이 발급되었습니다. 토큰은 다시 볼 수 없으니 안전한 곳에 보관해주세요.".to_string(),
+        data: IssuedAccessTokenResponse {
+            token: issued.token,
+            access_token: AccessTokenResponse::from(issued.access_token),
+        },
+    })
+}
+
+pub async fn get_member_access_tokens(
+    State(ctx): State<Arc<AppContext>>,
+    Extension(login_member): Extension<LoginMember>,
+    Path(id): Path<uuid::Uuid>,
+) -> Result<ReturnValue<Vec<AccessTokenResponse>>, AppError> {
+    let access_token_usecase: &dyn PersonalAccessTokenUseCase = ctx.resolve_ref();
+    let access_tokens = access_token_usecase.get_tokens(login_member, id).await?;
+
+    Ok(ReturnValue {
+        status: 200,
+        message: "액세스 토큰 목록을 가져왔습니다.".to_string(),
+        data: access_tokens.into_iter().map(AccessTokenResponse::from).collect(),
+    })
+}
+
+pub async fn revoke_member_access_token(
+    State(ctx): State<Arc<AppContext>>,
+    Extension(login_member): Extension<LoginMember>,
+    Path((id, token_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+) -> Result<axum::response::Response, AppError> {
+    let access_token_usecase: &dyn PersonalAccessTokenUseCase = ctx.resolve_ref();
+    access_token_usecase.revoke(login_member, id, token_id).await?;
+    Ok(axum::response::Response::builder()
+        .status(204)
+        .body(axum::body::Body::empty())
+        .unwrap())
+}
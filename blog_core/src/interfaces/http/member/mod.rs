@@ -1,13 +1,24 @@
-mod handler;
+pub mod handler;
+use crate::common::middleware::rate_limit::{rate_limit, RateLimit};
 use crate::{common::middleware::security::with_role_member, di::AppContext};
-use axum::routing::{delete, post, put};
-use axum::{middleware::from_fn, Router};
-use handler::{create_new_member, delete_member, update_member_info};
+use axum::middleware::{from_fn, from_fn_with_state};
+use axum::routing::{delete, get, post, put};
+use axum::Router;
+use handler::{
+    begin_totp_enrollment, confirm_totp_enrollment, create_member_access_token, create_new_member,
+    delete_member, disable_totp, get_member_access_tokens, get_member_identities,
+    get_member_sessions, revoke_member_access_token, revoke_member_session,
+    unlink_member_identity, update_member_info,
+};
 use std::sync::Arc;
 
 pub fn router(ctx: Arc<AppContext>) -> Router {
     Router::new()
-        .route("/", post(create_new_member))
+        .route(
+            "/",
+            post(create_new_member)
+                .route_layer(from_fn_with_state(RateLimit::policy(ctx.clone(), "signup"), rate_limit)),
+        )
         .route(
             "/{id}",
             put(update_member_info).route_layer(from_fn(with_role_member)),
@@ -16,5 +27,48 @@ pub fn router(ctx: Arc<AppContext>) -> Router {
             "/{id}",
             delete(delete_member).route_layer(from_fn(with_role_member)),
         )
+        .route(
+            "/{id}/sessions",
+            get(get_member_sessions).route_layer(from_fn(with_role_member)),
+        )
+        .route(
+            "/{id}/sessions/{session_id}",
+            delete(revoke_member_session).route_layer(from_fn(with_role_member)),
+        )
+        .route(
+            "/{id}/identities",
+            get(get_member_identities).route_layer(from_fn(with_role_member)),
+        )
+        .route(
+            "/{id}/identities/{provider}",
+            delete(unlink_member_identity).route_layer(from_fn(with_role_member)),
+        )
+        .route(
+            "/{id}/tokens",
+            get(get_member_access_tokens)
+                .post(create_member_access_token)
+                .route_layer(from_fn(with_role_member)),
+        )
+        .route(
+            "/{id}/tokens/{token_id}",
+            delete(revoke_member_access_token).route_layer(from_fn(with_role_member)),
+        )
+        .route(
+            "/{id}/mfa/totp",
+            post(begin_totp_enrollment).route_layer(from_fn(with_role_member)),
+        )
+        // 인증 코드를 받는 곳은 로그인과 같은 요청 제한을 건다.
+        .route(
+            "/{id}/mfa/totp",
+            delete(disable_totp)
+                .route_layer(from_fn(with_role_member))
+                .route_layer(from_fn_with_state(RateLimit::policy(ctx.clone(), "login"), rate_limit)),
+        )
+        .route(
+            "/{id}/mfa/totp/confirm",
+            post(confirm_totp_enrollment)
+                .route_layer(from_fn(with_role_member))
+                .route_layer(from_fn_with_state(RateLimit::policy(ctx.clone(), "login"), rate_limit)),
+        )
         .with_state(ctx.clone())
 }
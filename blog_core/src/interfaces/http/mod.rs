@@ -1,20 +1,35 @@
 pub mod auth;
 pub mod board;
+pub mod comment;
+pub mod feed;
 pub mod member;
 pub mod post;
+pub mod search;
+pub mod sitemap;
+pub mod tag;
 pub mod file;
 pub mod test;
 pub mod oauth2;
+pub mod well_known;
 
-use crate::{common::middleware::security::jwt_authentication_filter, di::AppContext};
+use crate::{
+    common::{middleware::security::jwt_authentication_filter, TrustedProxies},
+    config::ConfigProvider,
+    di::AppContext,
+};
 use axum::{
     middleware::{from_fn_with_state},
+    Extension,
     Router,
     
 };
+use shaku::HasComponent;
 use std::sync::Arc;
 
 pub fn create_routers(ctx: Arc<AppContext>) -> Router {
+    let config_provider: &dyn ConfigProvider = ctx.resolve_ref();
+    let trusted_proxies = TrustedProxies::parse(&config_provider.get().trusted_proxies);
+
     Router::new()
         .nest("/api/v1/tests", test::router(ctx.clone()))
         .nest("/api/v1/auth", auth::router(ctx.clone()))
@@ -22,6 +37,13 @@ pub fn create_routers(ctx: Arc<AppContext>) -> Router {
         .nest("/api/v1/members", member::router(ctx.clone()))
         .nest("/api/v1/boards", board::router(ctx.clone()))
         .nest("/api/v1/posts", post::router(ctx.clone()))
+        .nest("/api/v1/posts/{id}/comments", comment::router(ctx.clone()))
+        .nest("/api/v1/tags", tag::router(ctx.clone()))
+        .nest("/api/v1/search", search::router(ctx.clone()))
         .nest("/api/v1/files", file::router(ctx.clone()))
+        .nest("/.well-known", well_known::router(ctx.clone()))
+        .merge(feed::router(ctx.clone()))
+        .merge(sitemap::router(ctx.clone()))
         .layer(from_fn_with_state(ctx.clone(), jwt_authentication_filter))
+        .layer(Extension(trusted_proxies))
 }
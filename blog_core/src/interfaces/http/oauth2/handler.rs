@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use axum::extract::{Path, Query, State};
+use axum::response::{IntoResponse, Redirect, Response};
+use axum::Extension;
+use axum_extra::extract::CookieJar;
+use serde::Serialize;
+use shaku::HasComponent;
+
+use crate::application::auth::usecases::{LinkTicketDto, LoginUseCase, OAuth2IdentityUseCase, OAuth2LinkCommand, OAuth2LoginCommand};
+use crate::application::oauth2::{OAuth2Mode, OAuth2Provider, OAuth2Usecase, OAuth2UsecaseImpl};
+use crate::common::error::error_code::ErrorCode;
+use crate::common::{AppError, ClientInfo, LoginMember, ReturnValue};
+use crate::config::{ConfigProvider, OAuth2ConfigProvider};
+use crate::di::AppContext;
+use crate::interfaces::http::auth::handler::login_response;
+use crate::interfaces::http::member::handler::IdentityResponse;
+
+/// 경로의 제공자 이름으로 설정된 OAuth2 구현체를 만든다. 설정되지 않은 제공자는 404 로 응답한다.
+fn resolve_oauth2_usecase<'a>(ctx: &'a AppContext, provider: &str) -> Result<OAuth2UsecaseImpl<'a>, AppError> {
+    let not_found = || AppError::with_message(ErrorCode::NotFound, "지원하지 않는 OAuth2 제공자입니다.");
+    let provider = OAuth2Provider::from_path(provider).ok_or_else(not_found)?;
+    let oauth2_config_provider: &dyn OAuth2ConfigProvider = ctx.resolve_ref();
+    let provider_config = oauth2_config_provider
+        .provider_config(&provider)
+        .ok_or_else(not_found)?;
+
+    Ok(OAuth2UsecaseImpl::new(
+        provider_config,
+        ctx.resolve_ref(),
+        ctx.resolve_ref(),
+        ctx.resolve_ref(),
+    ))
+}
+
+#[derive(Serialize)]
+pub struct LinkTicketResponse {
+    ticket: String,
+    expires_in: i64,
+}
+
+impl From<LinkTicketDto> for LinkTicketResponse {
+    fn from(ticket: LinkTicketDto) -> Self {
+        Self {
+            ticket: ticket.ticket,
+            expires_in: ticket.expires_in,
+        }
+    }
+}
+
+/// 계정 연결용 일회용 티켓을 발급한다. 받은 티켓을 `?mode=link&ticket=` 으로 실어 인가 요청 화면으로 이동한다.
+pub async fn issue_link_ticket(
+    State(ctx): State<Arc<AppContext>>,
+    Extension(login_member): Extension<LoginMember>,
+    Path(provider): Path<String>,
+) -> Result<ReturnValue<LinkTicketResponse>, AppError> {
+    resolve_oauth2_usecase(&ctx, &provider)?;
+    let identity_usecase: &dyn OAuth2IdentityUseCase = ctx.resolve_ref();
+    let ticket = identity_usecase.issue_link_ticket(login_member).await?;
+
+    Ok(ReturnValue {
+        status: 201,
+        data: LinkTicketResponse::from(ticket),
+        message: "계정 연결 티켓이 발급되었습니다.".to_string(),
+    })
+}
+
+pub async fn redirect_to_login_page(
+    cookie_jar: CookieJar,
+    State(ctx): State<Arc<AppContext>>,
+    Path(provider): Path<String>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Result<impl IntoResponse, AppError> {
+    let oauth2_service = resolve_oauth2_usecase(&ctx, &provider)?;
+    let mode = match query.get("mode").map(String::as_str) {
+        Some("sign-in") => OAuth2Mode::SignIn,
+        // 브라우저 이동에는 bearer token 이 실리지 않으므로 로그인한 상태에서 미리 받은 티켓으로 회원을 확인한다.
+        Some("link") => {
+            let ticket = query.get("ticket").ok_or_else(|| {
+                AppError::with_message(ErrorCode::Unauthorized, "ticket is required")
+            })?;
+            let identity_usecase: &dyn OAuth2IdentityUseCase = ctx.resolve_ref();
+            OAuth2Mode::Link(identity_usecase.consume_link_ticket(ticket.to_string()).await?)
+        }
+        Some(_) => return Err(AppError::with_message(ErrorCode::BadRequest, "mode must be sign-in or link")),
+        None => return Err(AppError::with_message(ErrorCode::BadRequest, "mode is required")),
+    };
+
+    let (cookie_jar, redirect_uri) = oauth2_service.redirect_to_login_page(cookie_jar, mode).await?;
+    tracing::debug!("redirect_uri : {:?}", redirect_uri);
+    tracing::debug!("cookie_jar : {:?}", cookie_jar);
+
+    Ok((cookie_jar, Redirect::to(redirect_uri.to_string().as_str())).into_response())
+}
+
+pub async fn oauth2_sign_in(
+    cookie_jar: CookieJar,
+    client: ClientInfo,
+    State(ctx): State<Arc<AppContext>>,
+    Path(provider): Path<String>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Result<Response, AppError> {
+    tracing::debug!("/api/v1/oauth2/{}/callback called", provider);
+    let oauth2_service = resolve_oauth2_usecase(&ctx, &provider)?;
+
+    let state = query.get("state").ok_or_else(|| {
+        AppError::with_message(ErrorCode::Unauthorized, "state is required")
+    })?;
+
+    tracing::debug!("state : {:?}", state);
+
+    let code = query.get("code").ok_or_else(|| {
+        AppError::with_message(ErrorCode::Unauthorized, "code is required")
+    })?;
+
+    tracing::debug!("code : {:?}", code);
+
+    let (cookie_jar, user_profile) = oauth2_service
+        .get_userinfo(cookie_jar,
+            state.to_string(),
+            code.to_string())
+        .await?;
+
+    tracing::debug!("user_profile : {:?}", user_profile);
+
+    if let OAuth2Mode::Link(member_id) = user_profile.mode {
+        let identity_usecase: &dyn OAuth2IdentityUseCase = ctx.resolve_ref();
+        let identity = identity_usecase
+            .link(OAuth2LinkCommand {
+                member_id,
+                provider: user_profile.provider,
+                user_id: user_profile.user_id,
+                email: user_profile.email,
+                access_token: user_profile.access_token,
+            })
+            .await?;
+
+        return Ok((cookie_jar, ReturnValue {
+            status: 201,
+            data: IdentityResponse::from(identity),
+            message: "계정이 연결되었습니다.".to_string(),
+        }).into_response());
+    }
+
+    let auth_service: &dyn LoginUseCase = ctx.resolve_ref();
+    let login_command = OAuth2LoginCommand {
+        provider: user_profile.provider,
+        user_id: user_profile.user_id,
+        email: user_profile.email,
+        access_token: user_profile.access_token,
+        client,
+    };
+
+    let login_outcome = auth_service.login_by_oauth2(login_command).await?;
+    tracing::debug!("login_outcome : {:?}", login_outcome);
+
+    let config_provider: &dyn ConfigProvider = ctx.resolve_ref();
+    Ok(login_response(cookie_jar, &config_provider.get(), login_outcome, "OAuth2 로그인 성공"))
+}
@@ -1,11 +1,20 @@
-pub mod github;
+pub mod handler;
 
 use std::sync::Arc;
+use axum::middleware::from_fn;
+use axum::routing::{get, post};
 use axum::Router;
+use crate::common::middleware::security::with_role_member;
 use crate::di::AppContext;
 
 
 pub fn router(ctx: Arc<AppContext>) -> Router {
     Router::new()
-        .nest("/github", github::router(ctx))
+        .route("/{provider}", get(handler::redirect_to_login_page))
+        .route("/{provider}/callback", get(handler::oauth2_sign_in))
+        .route(
+            "/{provider}/link-ticket",
+            post(handler::issue_link_ticket).route_layer(from_fn(with_role_member)),
+        )
+        .with_state(ctx.clone())
 }
@@ -6,9 +6,13 @@ use axum::response::{IntoResponse, Response};
 use axum::{Extension, Json};
 use chrono::NaiveDateTime;
 use uuid::Uuid;
-// use crate::common::error_code::ErrorCode;
-use crate::application::board::{CategoryVo, CreatePostCommand, ModifyPostCommand, PostCreateUsecase, PostDeleteUsecase, PostDto, PostModifyUsecase, PostQueryUsecase, QPostDto, WriterVo};
-use crate::common::{AppError, CursorList, CursorListBuilder, LoginMember, ReturnValue};
+use crate::application::board::{CategoryVo, CreatePostCommand, ModifyPostCommand, PostCreateUsecase, PostDeleteUsecase, PostDto, PostModifyUsecase, PostQueryUsecase, PostRevisionDiffDto, PostRevisionDto, PostRevisionQueryUsecase, PostRevisionRestoreUsecase, QPostDto, QPostRevisionDto, ReactionCountVo, ReactionToggleUsecase, WriterVo};
+use crate::common::error_code::ErrorCode;
+use crate::common::markdown::TocEntry;
+use crate::common::{AppError, ClientInfo, CursorList, CursorListBuilder, LoginMember, ReturnValue, SecurityContext};
+use crate::domain::board::entity::command::post_entity::PostStatus;
+use crate::domain::board::entity::command::reaction_entity::ReactionKind;
+use crate::domain::board::repository::PostListOrder;
 use crate::config::ConfigProvider;
 use crate::di::AppContext;
 use shaku::HasComponent;
@@ -19,7 +23,7 @@ pub async fn create_post(
     Extension(login_member): Extension<LoginMember>,
     Json(request): Json<CreatePostRequest>,
 ) -> Result<ReturnValue<PostCommandResponse>, AppError> {
-    let command = request.into();
+    let command = request.try_into()?;
     let post_service: &dyn PostCreateUsecase = ctx.resolve_ref();
     let post = post_service.create(login_member, command).await?;
 
@@ -36,7 +40,7 @@ pub async fn update_post(
     Path(id): Path<Uuid>,
     Json(request): Json<ModifyPostRequest>,
 ) -> Result<ReturnValue<PostCommandResponse>, AppError> {
-    let command: ModifyPostCommand = request.into();
+    let command: ModifyPostCommand = request.try_into()?;
     let post_service: &dyn PostModifyUsecase = ctx.resolve_ref();
     let post = post_service.update(login_member, id, command).await?;
 
@@ -57,28 +61,57 @@ pub async fn delete_post(
     Ok((StatusCode::NO_CONTENT, ()).into_response())
 }
 
+/// 공개 목록은 `published_at` 커서로, 작성자 본인의 임시 저장/예약/보관 목록은 `created_at` 커서로 넘긴다.
+/// 공개 목록에서 `created_at` 을 커서로 주던 기존 요청도 받아준다.
+/// `sort=views` 면 조회수순으로 정렬하고 각 글의 순번(`offset`)을 커서로 쓴다.
 pub async fn get_posts(
     State(ctx): State<Arc<AppContext>>,
+    Extension(security_context): Extension<SecurityContext>,
     Query(params): Query<PostQueryParams>,
 ) -> Result<ReturnValue<CursorList<PostQueryResponse>>, AppError> {
     //let size  = size.unwrap_or(20);
     let size = params.size.unwrap_or(20);
     let category_id = params.category_id;
-    let created_at = params.created_at;
+    let status = parse_status(params.status)?;
+    let (cursor_target, order) = match params.sort.as_deref() {
+        None | Some("latest") => match status {
+            None | Some(PostStatus::Published) => ("published_at", PostListOrder::Latest { cursor: params.published_at.or(params.created_at) }),
+            Some(_) => ("created_at", PostListOrder::Latest { cursor: params.created_at }),
+        },
+        Some("views") => ("offset", PostListOrder::MostViewed { offset: params.offset.unwrap_or(0) }),
+        Some(_) => return Err(AppError::with_message(ErrorCode::BadRequest, "알 수 없는 정렬 기준입니다.")),
+    };
     let post_service: &dyn PostQueryUsecase = ctx.resolve_ref();
     let config_provider: &dyn ConfigProvider = ctx.resolve_ref();
-    let posts = post_service.get_posts(category_id, created_at, size as u64).await?;
+    let viewer = security_context.get_member().cloned();
+    let posts = post_service.get_posts(viewer, category_id, params.tag.clone(), status, order, size as u64).await?;
 
     let posts = posts.into_iter()
-        .map(PostQueryResponse::from)
+        .enumerate()
+        .map(|(index, post)| {
+            let mut post = PostQueryResponse::from(post);
+            if let PostListOrder::MostViewed { offset } = order {
+                post.offset = Some(offset + index as u64);
+            }
+            post
+        })
         .collect::<Vec<PostQueryResponse>>();
 
     let mut cursor_list_builder = CursorListBuilder::new(posts, size)
-        .set_target("created_at".to_owned());
+        .set_target(cursor_target.to_owned());
 
     if let Some(category_id) = category_id {
         cursor_list_builder = cursor_list_builder.register_query("category_id".to_owned(), category_id.to_string());
     }
+    if let Some(tag) = params.tag {
+        cursor_list_builder = cursor_list_builder.register_query("tag".to_owned(), tag);
+    }
+    if let Some(status) = status {
+        cursor_list_builder = cursor_list_builder.register_query("status".to_owned(), status.as_str().to_owned());
+    }
+    if let PostListOrder::MostViewed { .. } = order {
+        cursor_list_builder = cursor_list_builder.register_query("sort".to_owned(), "views".to_owned());
+    }
 
     Ok(ReturnValue {
         status: 200,
@@ -87,13 +120,21 @@ pub async fn get_posts(
     })
 }
 
+/// 로그인한 방문자는 회원 ID 로, 그렇지 않으면 IP 로 중복 조회를 가린다.
 pub async fn get_post(
     State(ctx): State<Arc<AppContext>>,
+    Extension(security_context): Extension<SecurityContext>,
+    client: ClientInfo,
     Path(id): Path<Uuid>,
 ) -> Result<ReturnValue<PostQueryResponse>, AppError> {
     let query_usecase: &dyn PostQueryUsecase = ctx.resolve_ref();
 
-    let post = query_usecase.get_post(id).await?;
+    let viewer = security_context.get_member().cloned();
+    let visitor = viewer
+        .as_ref()
+        .map(|member| format!("member:{}", member.id))
+        .or_else(|| client.ip_address.map(|ip| format!("ip:{}", ip)));
+    let post = query_usecase.get_post(viewer, visitor, id).await?;
 
     Ok(ReturnValue {
         status: 200,
@@ -102,20 +143,121 @@ pub async fn get_post(
     })
 }
 
+pub async fn get_post_revisions(
+    State(ctx): State<Arc<AppContext>>,
+    Extension(login_member): Extension<LoginMember>,
+    Path(id): Path<Uuid>,
+) -> Result<ReturnValue<Vec<QPostRevisionDto>>, AppError> {
+    let revision_usecase: &dyn PostRevisionQueryUsecase = ctx.resolve_ref();
+    let revisions = revision_usecase.get_revisions(login_member, id).await?;
+
+    Ok(ReturnValue {
+        status: 200,
+        data: revisions,
+        message: "게시글 리비전 목록을 가져왔습니다.".to_owned()
+    })
+}
+
+pub async fn get_post_revision(
+    State(ctx): State<Arc<AppContext>>,
+    Extension(login_member): Extension<LoginMember>,
+    Path((id, revision_no)): Path<(Uuid, i32)>,
+) -> Result<ReturnValue<PostRevisionResponse>, AppError> {
+    let revision_usecase: &dyn PostRevisionQueryUsecase = ctx.resolve_ref();
+    let revision = revision_usecase.get_revision(login_member, id, revision_no).await?;
+
+    Ok(ReturnValue {
+        status: 200,
+        data: PostRevisionResponse::from(revision),
+        message: "게시글 리비전을 가져왔습니다.".to_owned()
+    })
+}
+
+pub async fn diff_post_revisions(
+    State(ctx): State<Arc<AppContext>>,
+    Extension(login_member): Extension<LoginMember>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<PostRevisionDiffParams>,
+) -> Result<ReturnValue<PostRevisionDiffResponse>, AppError> {
+    let revision_usecase: &dyn PostRevisionQueryUsecase = ctx.resolve_ref();
+    let diff = revision_usecase.diff(login_member, id, params.from, params.to).await?;
+
+    Ok(ReturnValue {
+        status: 200,
+        data: PostRevisionDiffResponse::from(diff),
+        message: "게시글 리비전 비교 결과를 가져왔습니다.".to_owned()
+    })
+}
+
+pub async fn restore_post_revision(
+    State(ctx): State<Arc<AppContext>>,
+    Extension(login_member): Extension<LoginMember>,
+    Path((id, revision_no)): Path<(Uuid, i32)>,
+) -> Result<ReturnValue<PostCommandResponse>, AppError> {
+    let restore_usecase: &dyn PostRevisionRestoreUsecase = ctx.resolve_ref();
+    let post = restore_usecase.restore(login_member, id, revision_no).await?;
+
+    Ok(ReturnValue {
+        status: 200,
+        data: PostCommandResponse::from(post),
+        message: "게시글을 리비전으로 복원했습니다.".to_owned()
+    })
+}
+
+/// 같은 반응을 다시 누르면 취소한다.
+pub async fn toggle_post_reaction(
+    State(ctx): State<Arc<AppContext>>,
+    Extension(login_member): Extension<LoginMember>,
+    Path((id, kind)): Path<(Uuid, String)>,
+) -> Result<ReturnValue<Vec<ReactionCountVo>>, AppError> {
+    let kind = parse_reaction_kind(&kind)?;
+    let reaction_usecase: &dyn ReactionToggleUsecase = ctx.resolve_ref();
+    let reactions = reaction_usecase.toggle_post_reaction(login_member, id, kind).await?;
+
+    Ok(ReturnValue {
+        status: 200,
+        data: reactions,
+        message: "반응을 반영했습니다.".to_owned()
+    })
+}
+
+pub fn parse_reaction_kind(kind: &str) -> Result<ReactionKind, AppError> {
+    ReactionKind::from_str(kind)
+        .ok_or_else(|| AppError::with_message(ErrorCode::BadRequest, "알 수 없는 반응입니다."))
+}
+
+fn parse_status(status: Option<String>) -> Result<Option<PostStatus>, AppError> {
+    status
+        .map(|status| {
+            PostStatus::from_str(&status)
+                .ok_or_else(|| AppError::with_message(ErrorCode::BadRequest, "알 수 없는 게시글 상태입니다."))
+        })
+        .transpose()
+}
+
 #[derive(serde::Deserialize)]
 pub struct CreatePostRequest {
     category_id: i64,
     title: String,
     contents: String,
+    status: Option<String>,
+    publish_at: Option<NaiveDateTime>,
+    #[serde(default)]
+    tags: Vec<String>,
 }
 
-impl Into<CreatePostCommand> for CreatePostRequest {
-    fn into(self) -> CreatePostCommand {
-        CreatePostCommand {
-            category_id: self.category_id,
-            title: self.title,
-            contents: self.contents,
-        }
+impl TryFrom<CreatePostRequest> for CreatePostCommand {
+    type Error = AppError;
+
+    fn try_from(request: CreatePostRequest) -> Result<Self, Self::Error> {
+        Ok(CreatePostCommand {
+            category_id: request.category_id,
+            title: request.title,
+            contents: request.contents,
+            status: parse_status(request.status)?,
+            publish_at: request.publish_at,
+            tags: request.tags,
+        })
     }
 }
 
@@ -125,15 +267,23 @@ pub struct ModifyPostRequest {
     category_id: i64,
     title: String,
     contents: String,
+    status: Option<String>,
+    publish_at: Option<NaiveDateTime>,
+    tags: Option<Vec<String>>,
 }
 
-impl Into<ModifyPostCommand> for ModifyPostRequest {
-    fn into(self) -> ModifyPostCommand {
-        ModifyPostCommand {
-            title: self.title,
-            contents: self.contents,
-            category_id: self.category_id
-        }
+impl TryFrom<ModifyPostRequest> for ModifyPostCommand {
+    type Error = AppError;
+
+    fn try_from(request: ModifyPostRequest) -> Result<Self, Self::Error> {
+        Ok(ModifyPostCommand {
+            title: request.title,
+            contents: request.contents,
+            category_id: request.category_id,
+            status: parse_status(request.status)?,
+            publish_at: request.publish_at,
+            tags: request.tags,
+        })
     }
 }
 
@@ -144,6 +294,9 @@ pub struct PostCommandResponse {
     writer_id: Uuid,
     title: String,
     contents: String,
+    status: String,
+    published_at: Option<NaiveDateTime>,
+    tags: Vec<String>,
     created_at: NaiveDateTime,
     updated_at: Option<NaiveDateTime>
 }
@@ -156,6 +309,9 @@ impl From<PostDto> for PostCommandResponse {
             writer_id: post.writer_id,
             title: post.title,
             contents: post.contents,
+            status: post.status,
+            published_at: post.published_at,
+            tags: post.tags,
             created_at: post.created_at,
             updated_at: post.updated_at
         }
@@ -169,8 +325,21 @@ pub struct PostQueryResponse {
     category: CategoryVo,
     title: String,
     contents: Option<String>,
+    contents_html: Option<String>,
+    toc: Vec<TocEntry>,
+    excerpt: String,
+    reading_time_minutes: i64,
+    cover_image: Option<String>,
+    status: String,
+    published_at: Option<NaiveDateTime>,
+    tags: Vec<String>,
+    view_count: i64,
+    reactions: Vec<ReactionCountVo>,
     created_at: NaiveDateTime,
-    updated_at: Option<NaiveDateTime>
+    updated_at: Option<NaiveDateTime>,
+    /// 조회수순 목록에서의 순번 (0부터)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    offset: Option<u64>,
 }
 
 impl From<QPostDto> for PostQueryResponse {
@@ -181,8 +350,19 @@ impl From<QPostDto> for PostQueryResponse {
             category: post.category,
             title: post.title,
             contents: post.contents,
+            contents_html: post.contents_html,
+            toc: post.toc,
+            excerpt: post.excerpt,
+            reading_time_minutes: post.reading_time_minutes,
+            cover_image: post.cover_image,
+            status: post.status,
+            published_at: post.published_at,
+            tags: post.tags,
+            view_count: post.view_count,
+            reactions: post.reactions,
             created_at: post.created_at,
-            updated_at: post.updated_at
+            updated_at: post.updated_at,
+            offset: None,
         }
     }
 }
@@ -190,6 +370,69 @@ impl From<QPostDto> for PostQueryResponse {
 #[derive(serde::Deserialize)]
 pub struct PostQueryParams {
     category_id: Option<i64>,
+    tag: Option<String>,
+    status: Option<String>,
+    published_at: Option<NaiveDateTime>,
     created_at: Option<NaiveDateTime>,
+    sort: Option<String>,
+    offset: Option<u64>,
     size: Option<usize>,
 }
+
+#[derive(serde::Serialize)]
+pub struct PostRevisionResponse {
+    post_id: Uuid,
+    revision_no: i32,
+    writer_id: Uuid,
+    category_id: i64,
+    title: String,
+    contents: String,
+    restored_from: Option<i32>,
+    created_at: NaiveDateTime,
+}
+
+impl From<PostRevisionDto> for PostRevisionResponse {
+    fn from(revision: PostRevisionDto) -> Self {
+        PostRevisionResponse {
+            post_id: revision.post_id,
+            revision_no: revision.revision_no,
+            writer_id: revision.writer_id,
+            category_id: revision.category_id,
+            title: revision.title,
+            contents: revision.contents,
+            restored_from: revision.restored_from,
+            created_at: revision.created_at,
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+pub struct PostRevisionDiffResponse {
+    from_revision_no: i32,
+    to_revision_no: i32,
+    from_title: String,
+    to_title: String,
+    from_category_id: i64,
+    to_category_id: i64,
+    diff: String,
+}
+
+impl From<PostRevisionDiffDto> for PostRevisionDiffResponse {
+    fn from(diff: PostRevisionDiffDto) -> Self {
+        PostRevisionDiffResponse {
+            from_revision_no: diff.from_revision_no,
+            to_revision_no: diff.to_revision_no,
+            from_title: diff.from_title,
+            to_title: diff.to_title,
+            from_category_id: diff.from_category_id,
+            to_category_id: diff.to_category_id,
+            diff: diff.diff,
+        }
+    }
+}
+
+#[derive(serde::Deserialize)]
+pub struct PostRevisionDiffParams {
+    from: i32,
+    to: i32,
+}
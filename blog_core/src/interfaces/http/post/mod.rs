@@ -1,11 +1,11 @@
 pub mod handler;
-use axum::middleware::from_fn;
+use axum::middleware::from_fn_with_state;
 use axum::Router;
 pub use handler::*;
 
 use axum::routing::{post, put, get, delete};
 use std::sync::Arc;
-use crate::common::with_role_admin;
+use crate::common::{require_permission, Permission};
 use crate::di::AppContext;
 
 pub fn router(ctx: Arc<AppContext>) -> axum::Router {
@@ -14,7 +14,16 @@ pub fn router(ctx: Arc<AppContext>) -> axum::Router {
         .route("/", post(create_post))
         .route("/{id}", put(update_post))
         .route("/{id}", delete(delete_post))
-        .layer(from_fn(with_role_admin))
+        .route("/{id}/revisions", get(get_post_revisions))
+        .route("/{id}/revisions/diff", get(diff_post_revisions))
+        .route("/{id}/revisions/{revision_no}", get(get_post_revision))
+        .route("/{id}/revisions/{revision_no}/restore", post(restore_post_revision))
+        .layer(from_fn_with_state(Permission::PostWrite, require_permission))
+        .with_state(ctx.clone());
+
+    let post_reaction_router = axum::Router::new()
+        .route("/{id}/reactions/{kind}", post(toggle_post_reaction))
+        .layer(from_fn_with_state(Permission::CommentWrite, require_permission))
         .with_state(ctx.clone());
 
     let post_query_router = axum::Router::new()
@@ -24,5 +33,6 @@ pub fn router(ctx: Arc<AppContext>) -> axum::Router {
 
     Router::new()
         .merge(post_command_router)
+        .merge(post_reaction_router)
         .merge(post_query_router)
 }
@@ -0,0 +1,97 @@
+use std::sync::Arc;
+
+use axum::extract::{Query, State};
+use chrono::NaiveDateTime;
+use shaku::HasComponent;
+use uuid::Uuid;
+
+use crate::application::board::{CategoryVo, PostSearchUsecase, QPostSearchDto, SearchPostCommand, WriterVo};
+use crate::common::{AppError, CursorList, CursorListBuilder, ReturnValue};
+use crate::config::ConfigProvider;
+use crate::di::AppContext;
+
+/// 공개된 게시글 검색. 결과는 관련도 순이므로 각 결과의 순번(`offset`)을 커서로 쓴다.
+pub async fn search_posts(
+    State(ctx): State<Arc<AppContext>>,
+    Query(params): Query<SearchQueryParams>,
+) -> Result<ReturnValue<CursorList<PostSearchResponse>>, AppError> {
+    let size = params.size.unwrap_or(20);
+    let offset = params.offset.unwrap_or(0);
+    let search_usecase: &dyn PostSearchUsecase = ctx.resolve_ref();
+    let config_provider: &dyn ConfigProvider = ctx.resolve_ref();
+    let posts = search_usecase
+        .search(SearchPostCommand {
+            keyword: params.q.clone(),
+            category_id: params.category_id,
+            tag: params.tag.clone(),
+            author_id: params.author_id,
+            offset,
+            size: size as u64 + 1,
+        })
+        .await?;
+
+    let posts = posts.into_iter()
+        .enumerate()
+        .map(|(index, post)| PostSearchResponse::new(offset + index as u64, post))
+        .collect::<Vec<PostSearchResponse>>();
+
+    let mut cursor_list_builder = CursorListBuilder::new(posts, size)
+        .set_target("offset".to_owned())
+        .register_query("q".to_owned(), params.q);
+
+    if let Some(category_id) = params.category_id {
+        cursor_list_builder = cursor_list_builder.register_query("category_id".to_owned(), category_id.to_string());
+    }
+    if let Some(tag) = params.tag {
+        cursor_list_builder = cursor_list_builder.register_query("tag".to_owned(), tag);
+    }
+    if let Some(author_id) = params.author_id {
+        cursor_list_builder = cursor_list_builder.register_query("author_id".to_owned(), author_id.to_string());
+    }
+
+    Ok(ReturnValue {
+        status: 200,
+        data: cursor_list_builder.build(config_provider.get_uri("/api/v1/search")),
+        message: "검색 결과를 가져왔습니다.".to_owned()
+    })
+}
+
+#[derive(serde::Deserialize)]
+pub struct SearchQueryParams {
+    q: String,
+    category_id: Option<i64>,
+    tag: Option<String>,
+    author_id: Option<Uuid>,
+    offset: Option<u64>,
+    size: Option<usize>,
+}
+
+#[derive(serde::Serialize, Debug, Clone)]
+pub struct PostSearchResponse {
+    /// 전체 검색 결과에서의 순번 (0부터)
+    offset: u64,
+    id: Uuid,
+    writer: WriterVo,
+    category: CategoryVo,
+    title: String,
+    title_highlight: String,
+    snippet: String,
+    rank: f32,
+    published_at: Option<NaiveDateTime>,
+}
+
+impl PostSearchResponse {
+    fn new(offset: u64, post: QPostSearchDto) -> Self {
+        PostSearchResponse {
+            offset,
+            id: post.id,
+            writer: post.writer,
+            category: post.category,
+            title: post.title,
+            title_highlight: post.title_highlight,
+            snippet: post.snippet,
+            rank: post.rank,
+            published_at: post.published_at,
+        }
+    }
+}
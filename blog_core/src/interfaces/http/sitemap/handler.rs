@@ -0,0 +1,178 @@
+use std::fmt::Write;
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::http::header;
+use axum::response::IntoResponse;
+use chrono::{SecondsFormat, TimeZone, Utc};
+use shaku::HasComponent;
+
+use crate::application::board::{SitemapDto, SitemapEntryDto, SitemapQueryUsecase};
+use crate::common::error_code::ErrorCode;
+use crate::common::AppError;
+use crate::config::ConfigProvider;
+use crate::di::AppContext;
+
+const SITEMAP_NAMESPACE: &str = "http://www.sitemaps.org/schemas/sitemap/0.9";
+
+/// 게시판과 공개된 글이 5만 개를 넘으면 사이트맵 목록(sitemapindex)을 준다.
+pub async fn get_sitemap(State(ctx): State<Arc<AppContext>>) -> Result<impl IntoResponse, AppError> {
+    let usecase: &dyn SitemapQueryUsecase = ctx.resolve_ref();
+    let config_provider: &dyn ConfigProvider = ctx.resolve_ref();
+
+    let body = match usecase.get_sitemap().await? {
+        SitemapDto::UrlSet(entries) => render_url_set(config_provider, &entries),
+        SitemapDto::Index(sitemaps) => render_sitemap_index(config_provider, &sitemaps),
+    };
+    Ok(xml_response(body))
+}
+
+pub async fn get_board_sitemap(State(ctx): State<Arc<AppContext>>) -> Result<impl IntoResponse, AppError> {
+    let usecase: &dyn SitemapQueryUsecase = ctx.resolve_ref();
+    let config_provider: &dyn ConfigProvider = ctx.resolve_ref();
+
+    let entries = usecase.get_board_sitemap().await?;
+    Ok(xml_response(render_url_set(config_provider, &entries)))
+}
+
+/// `/sitemaps/posts/{page}.xml`
+pub async fn get_post_sitemap(
+    State(ctx): State<Arc<AppContext>>,
+    Path(file): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let usecase: &dyn SitemapQueryUsecase = ctx.resolve_ref();
+    let config_provider: &dyn ConfigProvider = ctx.resolve_ref();
+
+    let page = file.strip_suffix(".xml")
+        .and_then(|page| page.parse::<u64>().ok())
+        .ok_or_else(|| AppError::with_message(ErrorCode::NotFound, "존재하지 않는 사이트맵입니다."))?;
+    let entries = usecase.get_post_sitemap(page).await?;
+    Ok(xml_response(render_url_set(config_provider, &entries)))
+}
+
+pub async fn get_robots_txt(State(ctx): State<Arc<AppContext>>) -> impl IntoResponse {
+    let config_provider: &dyn ConfigProvider = ctx.resolve_ref();
+    (
+        [
+            (header::CONTENT_TYPE, "text/plain; charset=utf-8"),
+            (header::CACHE_CONTROL, "public, max-age=3600"),
+        ],
+        render_robots_txt(config_provider),
+    )
+}
+
+fn xml_response(body: String) -> impl IntoResponse {
+    (
+        [
+            (header::CONTENT_TYPE, "application/xml; charset=utf-8"),
+            (header::CACHE_CONTROL, "public, max-age=3600"),
+        ],
+        body,
+    )
+}
+
+fn render_url_set(config_provider: &dyn ConfigProvider, entries: &[SitemapEntryDto]) -> String {
+    render_entries(config_provider, "urlset", "url", entries)
+}
+
+fn render_sitemap_index(config_provider: &dyn ConfigProvider, sitemaps: &[SitemapEntryDto]) -> String {
+    render_entries(config_provider, "sitemapindex", "sitemap", sitemaps)
+}
+
+fn render_entries(config_provider: &dyn ConfigProvider, root: &str, element: &str, entries: &[SitemapEntryDto]) -> String {
+    let mut xml = String::from(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    let _ = write!(xml, r#"<{} xmlns="{}">"#, root, SITEMAP_NAMESPACE);
+    for entry in entries {
+        let _ = write!(xml, "<{}><loc>{}</loc>", element, escape_xml(&config_provider.get_uri(&entry.path)));
+        if let Some(last_modified) = entry.last_modified {
+            let last_modified = Utc.from_utc_datetime(&last_modified).to_rfc3339_opts(SecondsFormat::Secs, true);
+            let _ = write!(xml, "<lastmod>{}</lastmod>", last_modified);
+        }
+        let _ = write!(xml, "</{}>", element);
+    }
+    let _ = write!(xml, "</{}>", root);
+    xml
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn render_robots_txt(config_provider: &dyn ConfigProvider) -> String {
+    let config = config_provider.get();
+    let mut robots = String::from("User-agent: *\n");
+    let disallow: Vec<&String> = config
+        .robots_disallow
+        .iter()
+        .filter(|path| !path.trim().is_empty())
+        .collect();
+    if disallow.is_empty() {
+        robots.push_str("Disallow:\n");
+    }
+    for path in disallow {
+        let _ = writeln!(robots, "Disallow: {}", path.trim());
+    }
+    let _ = write!(robots, "\nSitemap: {}\n", config_provider.get_uri("/sitemap.xml"));
+    robots
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use chrono::NaiveDateTime;
+
+    use crate::application::board::SitemapEntryDto;
+    use crate::config::{test_config, ConfigProvider};
+
+    use super::{render_robots_txt, render_sitemap_index, render_url_set};
+
+    fn config_provider(args: &[&str]) -> Arc<dyn ConfigProvider> {
+        let mut args = args.to_vec();
+        args.splice(0..0, ["--server-host", "blog.example.com", "--protocol", "https"]);
+        test_config(&args)
+    }
+
+    #[test]
+    fn render_sitemap_test() {
+        let last_modified = NaiveDateTime::parse_from_str("2024-03-01 09:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let entries = vec![
+            SitemapEntryDto { path: "/".to_owned(), last_modified: Some(last_modified) },
+            SitemapEntryDto { path: "/tags/a&b".to_owned(), last_modified: None },
+        ];
+
+        let url_set = render_url_set(config_provider(&[]).as_ref(), &entries);
+        assert!(url_set.starts_with(r#"<?xml version="1.0" encoding="UTF-8"?><urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">"#));
+        assert!(url_set.contains("<url><loc>https://blog.example.com/</loc><lastmod>2024-03-01T09:00:00Z</lastmod></url>"));
+        assert!(url_set.contains("<url><loc>https://blog.example.com/tags/a&amp;b</loc></url>"));
+        assert!(url_set.ends_with("</urlset>"));
+
+        let index = render_sitemap_index(config_provider(&[]).as_ref(), &entries[..1]);
+        assert!(index.contains("<sitemapindex "));
+        assert!(index.contains("<sitemap><loc>https://blog.example.com/</loc>"));
+    }
+
+    #[test]
+    fn render_robots_txt_test() {
+        assert_eq!(
+            render_robots_txt(config_provider(&[]).as_ref()),
+            "User-agent: *\nDisallow: /api/\n\nSitemap: https://blog.example.com/sitemap.xml\n",
+        );
+        assert_eq!(
+            render_robots_txt(config_provider(&["--robots-disallow", ""]).as_ref()),
+            "User-agent: *\nDisallow:\n\nSitemap: https://blog.example.com/sitemap.xml\n",
+        );
+        assert!(render_robots_txt(config_provider(&["--robots-disallow", "/api/,/drafts/"]).as_ref()).contains("Disallow: /api/\nDisallow: /drafts/\n"));
+    }
+}
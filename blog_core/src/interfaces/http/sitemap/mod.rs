@@ -0,0 +1,16 @@
+pub mod handler;
+
+use crate::di::AppContext;
+use axum::routing::get;
+use axum::Router;
+use handler::*;
+use std::sync::Arc;
+
+pub fn router(ctx: Arc<AppContext>) -> Router {
+    Router::new()
+        .route("/sitemap.xml", get(get_sitemap))
+        .route("/sitemaps/boards.xml", get(get_board_sitemap))
+        .route("/sitemaps/posts/{file}", get(get_post_sitemap))
+        .route("/robots.txt", get(get_robots_txt))
+        .with_state(ctx.clone())
+}
@@ -0,0 +1,39 @@
+use std::sync::Arc;
+
+use axum::extract::State;
+use shaku::HasComponent;
+
+use crate::application::board::{QTagDto, TagQueryUsecase};
+use crate::common::{AppError, ReturnValue};
+use crate::di::AppContext;
+
+/// 태그 구름. 공개된 글이 달린 태그를 글 수가 많은 순으로 준다.
+pub async fn get_tags(
+    State(ctx): State<Arc<AppContext>>,
+) -> Result<ReturnValue<Vec<TagQueryResponse>>, AppError> {
+    let usecase: &dyn TagQueryUsecase = ctx.resolve_ref();
+    let tags = usecase.get_all().await?;
+
+    Ok(ReturnValue {
+        status: 200,
+        data: tags.into_iter().map(TagQueryResponse::from).collect(),
+        message: "태그 목록을 가져왔습니다.".to_owned()
+    })
+}
+
+#[derive(serde::Serialize)]
+pub struct TagQueryResponse {
+    id: i64,
+    name: String,
+    count: i64,
+}
+
+impl From<QTagDto> for TagQueryResponse {
+    fn from(tag: QTagDto) -> Self {
+        TagQueryResponse {
+            id: tag.id,
+            name: tag.name,
+            count: tag.count,
+        }
+    }
+}
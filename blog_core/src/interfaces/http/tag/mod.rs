@@ -0,0 +1,14 @@
+pub mod handler;
+
+use axum::routing::get;
+use axum::Router;
+use handler::*;
+use std::sync::Arc;
+
+use crate::di::AppContext;
+
+pub fn router(ctx: Arc<AppContext>) -> axum::Router {
+    Router::new()
+        .route("/", get(get_tags))
+        .with_state(ctx.clone())
+}
@@ -0,0 +1,19 @@
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::http::header;
+use axum::response::IntoResponse;
+use axum::Json;
+use shaku::HasComponent;
+
+use crate::common::jwt::JwtService;
+use crate::di::AppContext;
+
+/// 다른 서비스가 access token 을 검증할 수 있도록 공개키를 JWK Set 형식으로 내려준다.
+pub async fn get_jwks(State(ctx): State<Arc<AppContext>>) -> impl IntoResponse {
+    let jwt_service: &dyn JwtService = ctx.resolve_ref();
+    (
+        [(header::CACHE_CONTROL, "public, max-age=300")],
+        Json(jwt_service.jwks()),
+    )
+}
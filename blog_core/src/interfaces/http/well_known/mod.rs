@@ -1,14 +1,13 @@
 pub mod handler;
-use std::sync::Arc;
-use axum::routing::get;
-use axum::Router;
 
 use crate::di::AppContext;
+use axum::routing::get;
+use axum::Router;
+use handler::get_jwks;
+use std::sync::Arc;
 
 pub fn router(ctx: Arc<AppContext>) -> Router {
-
     Router::new()
-        .route("/", get(handler::redirect_to_github_login_page))
-        .route("/callback", get(handler::oauth2_sign_in))
+        .route("/jwks.json", get(get_jwks))
         .with_state(ctx.clone())
 }
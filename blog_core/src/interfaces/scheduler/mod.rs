@@ -0,0 +1,73 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use shaku::HasComponent;
+
+use crate::application::board::{PostPublishUsecase, PostViewFlushUsecase, SearchIndexFlushUsecase};
+use crate::config::ConfigProvider;
+use crate::di::AppContext;
+
+/// 주기적으로 도는 백그라운드 작업을 띄운다.
+pub fn spawn(ctx: Arc<AppContext>) {
+    tokio::spawn(publish_scheduled_posts(ctx.clone()));
+    tokio::spawn(flush_view_counts(ctx.clone()));
+    tokio::spawn(flush_search_index(ctx));
+}
+
+/// 예약 시각이 지난 게시글을 발행한다.
+/// 여러 인스턴스가 함께 돌아도 상태가 `SCHEDULED` 인 글만 바꾸므로 같은 글을 두 번 발행하지 않는다.
+async fn publish_scheduled_posts(ctx: Arc<AppContext>) {
+    let config_provider: &dyn ConfigProvider = ctx.resolve_ref();
+    let period = Duration::from_secs(config_provider.get().post_scheduler_interval_seconds.max(1));
+    let mut interval = tokio::time::interval(period);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        interval.tick().await;
+
+        let publish_usecase: &dyn PostPublishUsecase = ctx.resolve_ref();
+        match publish_usecase.publish_due_posts().await {
+            Ok(0) => {}
+            Ok(count) => tracing::info!("Published {} scheduled posts", count),
+            Err(e) => tracing::error!("Failed to publish scheduled posts: {}", e.message),
+        }
+    }
+}
+
+/// 메모리에 모아 둔 조회수를 DB 에 반영한다.
+async fn flush_view_counts(ctx: Arc<AppContext>) {
+    let config_provider: &dyn ConfigProvider = ctx.resolve_ref();
+    let period = Duration::from_secs(config_provider.get().view_flush_interval_seconds.max(1));
+    let mut interval = tokio::time::interval(period);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        interval.tick().await;
+
+        let flush_usecase: &dyn PostViewFlushUsecase = ctx.resolve_ref();
+        match flush_usecase.flush_view_counts().await {
+            Ok(0) => {}
+            Ok(count) => tracing::debug!("Flushed view counts of {} posts", count),
+            Err(e) => tracing::error!("Failed to flush view counts: {}", e.message),
+        }
+    }
+}
+
+/// 임베디드 검색 색인의 변경을 파일에 쓴다. 다른 프로세스가 다시 만든 색인 파일도 이때 불러온다.
+async fn flush_search_index(ctx: Arc<AppContext>) {
+    let config_provider: &dyn ConfigProvider = ctx.resolve_ref();
+    let period = Duration::from_secs(config_provider.get().search_index_flush_interval_seconds.max(1));
+    let mut interval = tokio::time::interval(period);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        interval.tick().await;
+
+        let flush_usecase: &dyn SearchIndexFlushUsecase = ctx.resolve_ref();
+        match flush_usecase.flush_search_index().await {
+            Ok(false) => {}
+            Ok(true) => tracing::debug!("Flushed search index"),
+            Err(e) => tracing::error!("Failed to flush search index: {}", e.message),
+        }
+    }
+}
@@ -3,7 +3,7 @@ use clap::Parser;
 use dotenvy::dotenv;
 use reqwest::{header, Method};
 use tower_http::cors::{AllowOrigin, Any, CorsLayer};
-use std::{env, sync::Arc};
+use std::{env, net::SocketAddr, sync::Arc};
 use tracing::info;
 mod application;
 mod common;
@@ -38,17 +38,81 @@ async fn main() {
         })
         .build();
 
+    // 서명 키, 데이터 암호화 키 설정 오류는 첫 요청이 아니라 기동 시점에 드러나도록 미리 불러온다.
+    let jwt_service: &dyn common::jwt::JwtService = shaku::HasComponent::resolve_ref(&ctx);
+    jwt_service.jwks();
+    let token_cipher: &dyn common::TokenCipher = shaku::HasComponent::resolve_ref(&ctx);
+    info!("Data encryption key: {}", token_cipher.active_kid());
+
+    if cfg.reencrypt_oauth2_tokens {
+        let reencrypt_usecase: &dyn application::auth::OAuth2TokenReencryptUseCase = shaku::HasComponent::resolve_ref(&ctx);
+        match reencrypt_usecase.reencrypt_all().await {
+            Ok(count) => info!("Re-encrypted {} OAuth2 access tokens", count),
+            Err(e) => panic!("Failed to re-encrypt OAuth2 access tokens: {}", e.message),
+        }
+        return;
+    }
+
+    if cfg.rebuild_search_index {
+        let rebuild_usecase: &dyn application::board::SearchIndexRebuildUsecase = shaku::HasComponent::resolve_ref(&ctx);
+        match rebuild_usecase.rebuild().await {
+            Ok(count) => info!("Rebuilt search index with {} posts", count),
+            Err(e) => panic!("Failed to rebuild search index: {}", e.message),
+        }
+        return;
+    }
+
+    let ctx = Arc::new(ctx);
+    interfaces::scheduler::spawn(ctx.clone());
+
     let cors_layers = get_cors_layers(cfg);
-    let app = interfaces::http::create_routers(Arc::new(ctx))
+    let app = interfaces::http::create_routers(ctx.clone())
         .layer(cors_layers);
     let listener = tokio::net::TcpListener::bind("127.0.0.1:8080")
         .await
         .expect("Failed to bind TCP listener");
     info!("Listening on {}", listener.local_addr().unwrap());
 
-    axum::serve(listener, app)
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+        .with_graceful_shutdown(shutdown_signal())
         .await
         .expect("Failed to start server");
+
+    // 메모리에만 있는 조회수와 검색 색인 변경을 잃지 않도록 종료 전에 한 번 더 반영한다.
+    let view_flush_usecase: &dyn application::board::PostViewFlushUsecase = shaku::HasComponent::resolve_ref(ctx.as_ref());
+    match view_flush_usecase.flush_view_counts().await {
+        Ok(count) => info!("Flushed view counts of {} posts before shutdown", count),
+        Err(e) => tracing::error!("Failed to flush view counts before shutdown: {}", e.message),
+    }
+    let search_flush_usecase: &dyn application::board::SearchIndexFlushUsecase = shaku::HasComponent::resolve_ref(ctx.as_ref());
+    if let Err(e) = search_flush_usecase.flush_search_index().await {
+        tracing::error!("Failed to flush search index before shutdown: {}", e.message);
+    }
+}
+
+/// Ctrl+C 또는 SIGTERM 을 받으면 끝난다.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("Failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+    info!("Shutting down");
 }
 
 fn get_cors_layers(cfg: Arc<AppConfig>) -> CorsLayer {
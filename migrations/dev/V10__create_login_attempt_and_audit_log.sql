@@ -0,0 +1,21 @@
+create table if not exists login_attempt(
+    scope varchar(16),
+    attempt_key varchar(255),
+    failure_count integer not null default 0,
+    last_failed_at timestamp(6) not null,
+    locked_until timestamp(6),
+    primary key (scope, attempt_key)
+);
+
+create table if not exists audit_log(
+    id uuid,
+    event_type varchar(64) not null,
+    member_id uuid,
+    ip_address varchar(64),
+    detail jsonb not null default '{}'::jsonb,
+    created_at timestamp(6) not null default current_timestamp,
+    primary key (id)
+);
+
+create index if not exists IDX_audit_log_created_at on audit_log(created_at);
+create index if not exists IDX_audit_log_member on audit_log(member_id);
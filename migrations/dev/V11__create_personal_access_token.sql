@@ -0,0 +1,18 @@
+create table if not exists personal_access_token(
+    id uuid,
+    member_id uuid not null,
+    name varchar(100) not null,
+    token_prefix varchar(16) not null,
+    token_hash varchar(64) not null,
+    scopes jsonb not null default '[]'::jsonb,
+    mfa_authenticated boolean not null default false,
+    expires_at timestamp(6) not null,
+    last_used_at timestamp(6),
+    created_at timestamp(6) not null default current_timestamp,
+    revoked_at timestamp(6),
+    primary key (id),
+    constraint UK_personal_access_token_hash unique (token_hash),
+    constraint FK_personal_access_token_table_member foreign key(member_id) references member(id) on delete cascade
+);
+
+create index if not exists IDX_personal_access_token_member on personal_access_token(member_id);
@@ -0,0 +1,6 @@
+alter table article add column if not exists status varchar(16) not null default 'PUBLISHED';
+alter table article add column if not exists published_at timestamp(6);
+
+update article set published_at = created_at where published_at is null;
+
+create index if not exists IDX_article_status_published_at on article(status, published_at);
@@ -0,0 +1,21 @@
+create table if not exists article_revision(
+    id bigserial,
+    article_id uuid not null,
+    revision_no integer not null,
+    member_id uuid not null,
+    category_id bigint not null,
+    title varchar(255) not null,
+    contents text not null,
+    restored_from integer,
+    created_at timestamp(6) not null default current_timestamp,
+    primary key (id),
+    constraint UK_article_revision_no unique (article_id, revision_no),
+    constraint FK_article_revision_table_article foreign key(article_id) references article(id) on delete cascade,
+    constraint FK_article_revision_table_member foreign key(member_id) references member(id) on delete cascade
+);
+
+-- 기존 게시글은 현재 내용을 첫 번째 리비전으로 남긴다.
+insert into article_revision(article_id, revision_no, member_id, category_id, title, contents, created_at)
+select id, 1, member_id, category_id, title, contents, coalesce(updated_at, created_at)
+from article
+where not exists (select 1 from article_revision r where r.article_id = article.id);
@@ -0,0 +1,17 @@
+create table if not exists tag(
+    id bigserial,
+    name varchar(50) not null,
+    created_at timestamp(6) not null default current_timestamp,
+    primary key (id),
+    constraint UK_tag_name unique (name)
+);
+
+create table if not exists article_tag(
+    article_id uuid not null,
+    tag_id bigint not null,
+    primary key (article_id, tag_id),
+    constraint FK_article_tag_table_article foreign key(article_id) references article(id) on delete cascade,
+    constraint FK_article_tag_table_tag foreign key(tag_id) references tag(id) on delete cascade
+);
+
+create index if not exists IDX_article_tag_tag on article_tag(tag_id);
@@ -0,0 +1,8 @@
+-- 한국어 형태소 사전이 없으므로 'simple' 설정으로 띄어쓰기 단위 토큰을 만든다. 제목(A)을 본문(B)보다 높게 친다.
+alter table article add column if not exists search_vector tsvector
+    generated always as (
+        setweight(to_tsvector('simple', coalesce(title, '')), 'A') ||
+        setweight(to_tsvector('simple', coalesce(contents, '')), 'B')
+    ) stored;
+
+create index if not exists IDX_article_search_vector on article using gin(search_vector);
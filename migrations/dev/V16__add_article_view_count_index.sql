@@ -0,0 +1,4 @@
+update article set view_count = 0 where view_count is null;
+alter table article alter column view_count set not null;
+
+create index if not exists IDX_article_view_count on article(view_count desc, id);
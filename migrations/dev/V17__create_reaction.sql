@@ -0,0 +1,18 @@
+create table if not exists reaction(
+    id bigserial,
+    member_id uuid not null,
+    article_id uuid not null,
+    comment_id bigint,
+    kind varchar(16) not null,
+    created_at timestamp(6) not null default current_timestamp,
+    primary key (id),
+    constraint FK_reaction_table_member foreign key(member_id) references member(id) on delete cascade,
+    constraint FK_reaction_table_article foreign key(article_id) references article(id) on delete cascade,
+    constraint FK_reaction_table_comment foreign key(comment_id) references article_comment(id) on delete cascade
+);
+
+-- 게시글 반응은 comment_id 가 없고, 댓글 반응은 댓글이 달린 게시글과 댓글을 함께 가리킨다.
+create unique index if not exists UK_reaction_article on reaction(member_id, article_id, kind) where comment_id is null;
+create unique index if not exists UK_reaction_comment on reaction(member_id, comment_id, kind) where comment_id is not null;
+create index if not exists IDX_reaction_article on reaction(article_id);
+create index if not exists IDX_reaction_comment on reaction(comment_id);
@@ -0,0 +1,13 @@
+-- 목록 요약용 값을 글을 쓸 때 계산해 두고, 목록 조회에서 본문 전체(TOAST)를 읽지 않도록 한다.
+-- excerpt_source 는 본문 앞 2000자(EXCERPT_SOURCE_CHARS), cover_image 는 본문의 첫 Markdown 이미지 주소다.
+alter table article add column if not exists excerpt_source text;
+alter table article add column if not exists contents_length integer;
+alter table article add column if not exists cover_image text;
+
+update article
+set excerpt_source = left(contents, 2000),
+    contents_length = char_length(contents),
+    cover_image = substring(contents from '!\[[^]]*\]\([[:space:]]*<?([^)[:space:]>]+)');
+
+alter table article alter column excerpt_source set not null;
+alter table article alter column contents_length set not null;
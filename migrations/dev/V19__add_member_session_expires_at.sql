@@ -0,0 +1,10 @@
+-- 세션은 마지막으로 발급한 refresh token 이 만료되면 함께 끝난다. 목록과 세션 확인에서 만료된 세션을 빼려고 저장해 둔다.
+alter table member_session add column if not exists expires_at timestamp(6);
+
+update member_session s
+set expires_at = coalesce(
+    (select max(r.expires_at) from refresh_token r where r.family_id = s.id),
+    s.last_used_at
+);
+
+alter table member_session alter column expires_at set not null;
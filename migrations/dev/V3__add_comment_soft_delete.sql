@@ -0,0 +1,3 @@
+alter table article_comment add column if not exists is_deleted boolean not null default false;
+
+create index if not exists IDX_article_comment_article_parent on article_comment(article_id, parent_id);
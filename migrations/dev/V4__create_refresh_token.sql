@@ -0,0 +1,15 @@
+create table if not exists refresh_token(
+    id uuid,
+    member_id uuid not null,
+    family_id uuid not null,
+    token_hash varchar(64) not null unique,
+    expires_at timestamp(6) not null,
+    created_at timestamp(6) not null default current_timestamp,
+    revoked_at timestamp(6),
+    replaced_by uuid,
+    primary key (id),
+    constraint FK_refresh_token_table_member foreign key(member_id) references member(id) on delete cascade
+);
+
+create index if not exists IDX_refresh_token_family on refresh_token(family_id);
+create index if not exists IDX_refresh_token_member on refresh_token(member_id);
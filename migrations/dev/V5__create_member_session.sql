@@ -0,0 +1,29 @@
+create table if not exists member_session(
+    id uuid,
+    member_id uuid not null,
+    user_agent varchar(512),
+    ip_address varchar(64),
+    created_at timestamp(6) not null default current_timestamp,
+    last_used_at timestamp(6) not null default current_timestamp,
+    revoked_at timestamp(6),
+    primary key (id),
+    constraint FK_member_session_table_member foreign key(member_id) references member(id) on delete cascade
+);
+
+create index if not exists IDX_member_session_member on member_session(member_id);
+
+-- 세션 도입 전에 발급된 token family 도 세션으로 옮겨 제약을 걸기 전에 모든 토큰이 세션을 가리키게 한다.
+-- 기기 정보는 알 수 없으므로 비워 두고, 폐기 여부는 family 안에 살아 있는 토큰이 있는지로 정한다.
+insert into member_session(id, member_id, created_at, last_used_at, revoked_at)
+select family_id,
+       min(member_id::text)::uuid,
+       min(created_at),
+       max(created_at),
+       case when bool_or(revoked_at is null and expires_at > current_timestamp) then null else current_timestamp end
+from refresh_token
+group by family_id
+on conflict (id) do nothing;
+
+-- refresh token family 는 곧 로그인 세션이다.
+alter table refresh_token
+    add constraint FK_refresh_token_table_member_session foreign key(family_id) references member_session(id) on delete cascade;
@@ -0,0 +1,10 @@
+-- OAuth2 로 가입한 회원은 임의의 비밀번호가 설정되어 있어 비밀번호 로그인을 할 수 없다.
+alter table member add column if not exists has_password boolean not null default true;
+
+update member set has_password = false
+where email like '%-%@dohoon-kim.kr'
+  and id in (select member_id from oauth2_member);
+
+-- 하나의 제공자 계정은 한 회원에게만, 한 회원은 제공자별로 하나의 계정만 연결할 수 있다.
+create unique index if not exists UK_oauth2_member_provider_user on oauth2_member(provider, user_id);
+create unique index if not exists UK_oauth2_member_member_provider on oauth2_member(member_id, provider);
@@ -0,0 +1,2 @@
+-- 암호화된 access token(kid, 감싼 데이터 키, 암호문)은 255자를 넘을 수 있다.
+alter table oauth2_member alter column access_token type text;
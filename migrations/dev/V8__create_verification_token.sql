@@ -0,0 +1,12 @@
+create table if not exists verification_token(
+    id uuid,
+    member_id uuid not null,
+    purpose varchar(32) not null,
+    expires_at timestamp(6) not null,
+    created_at timestamp(6) not null default current_timestamp,
+    used_at timestamp(6),
+    primary key (id),
+    constraint FK_verification_token_table_member foreign key(member_id) references member(id) on delete cascade
+);
+
+create index if not exists IDX_verification_token_member on verification_token(member_id, purpose);
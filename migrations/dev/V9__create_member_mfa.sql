@@ -0,0 +1,12 @@
+create table if not exists member_mfa(
+    member_id uuid,
+    secret text not null,
+    recovery_codes jsonb not null default '[]',
+    last_used_step bigint,
+    created_at timestamp(6) not null default current_timestamp,
+    enabled_at timestamp(6),
+    primary key (member_id),
+    constraint FK_member_mfa_table_member foreign key(member_id) references member(id) on delete cascade
+);
+
+alter table member_session add column if not exists mfa_authenticated boolean not null default false;